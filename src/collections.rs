@@ -60,6 +60,10 @@ impl<T> UndoHistory<T> {
         &self.history[self.current_index]
     }
 
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.history.get(index)
+    }
+
     pub fn current_index(&self) -> usize {
         self.current_index
     }
@@ -398,6 +398,30 @@ fn redo_changes_with_override() {
     assert_eq!(undo_history.current_index, 1);
 }
 
+#[test]
+fn get() {
+    let mut undo_history = UndoHistory::new(3, 1);
+    undo_history.commit_change(2);
+    undo_history.commit_change(3);
+
+    assert_eq!(undo_history.get(0), Some(&1));
+    assert_eq!(undo_history.get(1), Some(&2));
+    assert_eq!(undo_history.get(2), Some(&3));
+    assert_eq!(undo_history.get(3), None);
+
+    undo_history.undo();
+    assert_eq!(undo_history.get(2), Some(&3));
+
+    undo_history.commit_change(4);
+    assert_eq!(undo_history.get(1), Some(&2));
+    assert_eq!(undo_history.get(2), Some(&4));
+
+    undo_history.commit_change(5);
+    assert_eq!(undo_history.get(0), Some(&2));
+    assert_eq!(undo_history.get(1), Some(&4));
+    assert_eq!(undo_history.get(2), Some(&5));
+}
+
 #[test]
 fn clear() {
     let mut undo_history = UndoHistory::new(5, 1);
@@ -7,14 +7,29 @@ use std::mem;
 use std::fs::File;
 use std::io::Read;
 use std::path::Path;
+use crate::game::canvas::Canvas;
 use crate::game::help_page::HelpPage;
-use crate::game::level::{Level, LevelPack, Tile};
-use crate::game::screen::{Screen, ScreenId, ScreenInGame, ScreenLevelEditor, ScreenLevelPackEditor, ScreenSelectLevel, ScreenSelectLevelPack, ScreenSelectLevelPackEditor, ScreenStartMenu};
-use crate::game::screen::dialog::Dialog;
-
+use crate::game::key_bindings::KeyBindings;
+use crate::game::settings::Settings;
+use crate::game::theme::Theme;
+use crate::game::level::{Level, LevelPack};
+use crate::game::screen::{Screen, ScreenId, ScreenInGame, ScreenLevelEditor, ScreenLevelPackEditor, ScreenReplay, ScreenSelectLevel, ScreenSelectLevelPack, ScreenSelectLevelPackEditor, ScreenSettings, ScreenStartMenu};
+use crate::game::screen::dialog::{Dialog, DialogOk};
+use crate::game::validation::LevelProblem;
+
+mod canvas;
 mod level;
+mod engine;
+mod deadlock;
+mod solver;
+mod validation;
 mod screen;
 mod help_page;
+mod key_bindings;
+mod settings;
+mod theme;
+
+pub mod cli;
 
 struct EditorState {
     level_packs: Vec<LevelPack>,
@@ -79,23 +94,33 @@ struct GameState {
     is_help: bool,
     dialog: Option<Box<dyn Dialog>>,
 
+    /// The size of the console in the current update
+    console_size: (usize, usize),
+
     current_level_pack_index: usize,
     level_packs: Vec<LevelPack>,
 
     current_level_index: usize,
 
     is_player_background: bool,
-    player_background_tmp: i32,
+    player_background_tmp: u32,
 
     found_secret_main_level_pack: bool,
 
+    /// Undone moves count for the best moves if set (Only for the current session)
+    is_strict_mode: bool,
+
     should_exit: bool,
 
+    settings: Settings,
+    key_bindings: KeyBindings,
+    theme: Theme,
+
     editor_state: EditorState,
 }
 
 impl GameState {
-    fn new(level_packs: Vec<LevelPack>, editor_level_packs: Vec<LevelPack>) -> Self {
+    fn new(level_packs: Vec<LevelPack>, editor_level_packs: Vec<LevelPack>, settings: Settings, key_bindings: KeyBindings, theme: Theme) -> Self {
         Self {
             current_screen_id: ScreenId::StartMenu,
             should_call_on_set_screen: Default::default(),
@@ -103,6 +128,8 @@ impl GameState {
             is_help: Default::default(),
             dialog: Default::default(),
 
+            console_size: (Game::CONSOLE_MIN_WIDTH, Game::CONSOLE_MIN_HEIGHT),
+
             current_level_pack_index: Default::default(),
             level_packs,
 
@@ -113,8 +140,14 @@ impl GameState {
 
             found_secret_main_level_pack: Default::default(),
 
+            is_strict_mode: Default::default(),
+
             should_exit: Default::default(),
 
+            settings,
+            key_bindings,
+            theme,
+
             editor_state: EditorState::new(editor_level_packs),
         }
     }
@@ -160,6 +193,43 @@ impl GameState {
         self.is_player_background
     }
 
+    /// The size of the console (Screens which are drawn in full size can use the whole console)
+    pub fn console_size(&self) -> (usize, usize) {
+        self.console_size
+    }
+
+    pub fn is_strict_mode(&self) -> bool {
+        self.is_strict_mode
+    }
+
+    pub fn set_strict_mode(&mut self, is_strict_mode: bool) {
+        self.is_strict_mode = is_strict_mode;
+    }
+
+    pub fn settings(&self) -> &Settings {
+        &self.settings
+    }
+
+    pub fn settings_mut(&mut self) -> &mut Settings {
+        &mut self.settings
+    }
+
+    pub fn key_bindings(&self) -> &KeyBindings {
+        &self.key_bindings
+    }
+
+    pub fn key_bindings_mut(&mut self) -> &mut KeyBindings {
+        &mut self.key_bindings
+    }
+
+    pub fn theme(&self) -> &Theme {
+        &self.theme
+    }
+
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.theme = theme;
+    }
+
     pub fn open_help_page(&mut self) {
         self.is_help = true;
     }
@@ -211,6 +281,12 @@ pub struct Game<'a> {
     help_page: HelpPage,
 
     game_state: GameState,
+
+    /// The game is paused while the console is smaller than the minimal console size
+    is_console_too_small: bool,
+
+    /// Errors of configuration files which were replaced with their defaults (Printed after the game was closed)
+    warnings: Vec<String>,
 }
 
 impl <'a> Game<'a> {
@@ -219,12 +295,17 @@ impl <'a> Game<'a> {
     pub const CONSOLE_MIN_WIDTH: usize = 74;
     pub const CONSOLE_MIN_HEIGHT: usize = 23;
 
-    pub const LEVEL_MAX_WIDTH: usize = Self::CONSOLE_MIN_WIDTH;
-    pub const LEVEL_MAX_HEIGHT: usize = Self::CONSOLE_MIN_HEIGHT - 1;
+    /// Levels which are larger than the console are scrolled
+    pub const LEVEL_MAX_WIDTH: usize = 255;
+    pub const LEVEL_MAX_HEIGHT: usize = 255;
+
+    /// Minimal size of levels which are created or resized in the level editor
+    pub const LEVEL_MIN_WIDTH: usize = 3;
+    pub const LEVEL_MIN_HEIGHT: usize = 3;
 
     pub const MAX_LEVEL_PACK_ID_LEN: usize = 16;
 
-    const PLAYER_BACKGROUND_DELAY: i32 = 12;
+    const PLAYER_BACKGROUND_DELAY: u32 = 12;
 
     const SAVE_GAME_FOLDER: &'static str = "ConsoleSokoban";
 
@@ -257,22 +338,15 @@ impl <'a> Game<'a> {
     }
 
     pub fn new(console: &'a Console) -> Result<Self, Box<dyn Error>> {
-        let (width, height) = console.get_console_size();
-        if width < Self::CONSOLE_MIN_WIDTH || height < Self::CONSOLE_MIN_HEIGHT {
-            return Err(Box::new(GameError::new(format!(
-                "Console is to small (Min: {} x {})!",
-                Self::CONSOLE_MIN_WIDTH,
-                Self::CONSOLE_MIN_HEIGHT
-            ))));
-        }
-
         let screens = HashMap::from_iter([
             (ScreenId::StartMenu, Box::new(ScreenStartMenu::new()) as Box<dyn Screen>),
+            (ScreenId::Settings, Box::new(ScreenSettings::new()) as Box<dyn Screen>),
 
             (ScreenId::SelectLevelPack, Box::new(ScreenSelectLevelPack::new()) as Box<dyn Screen>),
             (ScreenId::SelectLevel, Box::new(ScreenSelectLevel::new()) as Box<dyn Screen>),
 
             (ScreenId::InGame, Box::new(ScreenInGame::new()) as Box<dyn Screen>),
+            (ScreenId::Replay, Box::new(ScreenReplay::new()) as Box<dyn Screen>),
 
             (ScreenId::SelectLevelPackEditor, Box::new(ScreenSelectLevelPackEditor::new()) as Box<dyn Screen>),
             (ScreenId::LevelPackEditor, Box::new(ScreenLevelPackEditor::new()) as Box<dyn Screen>),
@@ -289,9 +363,10 @@ impl <'a> Game<'a> {
 
         for arg in std::env::args().
                 skip(1) {
-            if !arg.ends_with(".lvl") {
+            let is_xsb = arg.ends_with(".xsb") || arg.ends_with(".sok");
+            if !arg.ends_with(".lvl") && !is_xsb {
                 return Err(Box::new(GameError::new(format!(
-                    "Invalid level pack \"{}\": The file extension of level pack must be \".lvl\"",
+                    "Invalid level pack \"{}\": The file extension of level pack must be \".lvl\", \".xsb\" or \".sok\"",
                     arg
                 ))));
             }
@@ -349,7 +424,11 @@ impl <'a> Game<'a> {
                 }
             }
 
-            level_packs.push(LevelPack::read_from_save_game(level_pack_id, &arg, level_pack_data)?);
+            if is_xsb {
+                level_packs.push(LevelPack::read_xsb_from_save_game(level_pack_id, &arg, level_pack_data)?);
+            }else {
+                level_packs.push(LevelPack::read_from_save_game(level_pack_id, &arg, level_pack_data)?);
+            }
         }
 
         if level_packs.len() > LevelPack::MAX_LEVEL_PACK_COUNT {
@@ -377,34 +456,7 @@ impl <'a> Game<'a> {
                 ))));
             }
 
-            for (i, level) in level_pack.levels().iter().
-                    map(|level| level.level()).
-                    enumerate() {
-                if level.width() > Self::LEVEL_MAX_WIDTH || level.height() > Self::LEVEL_MAX_HEIGHT {
-                    return Err(Box::new(GameError::new(format!(
-                        "Error while loading level pack \"{}\": Level {} is too large (Max: {}x{})",
-                        level_pack.id(),
-                        i + 1,
-                        Self::LEVEL_MAX_WIDTH,
-                        Self::LEVEL_MAX_HEIGHT,
-                    ))));
-                }
-
-                let player_tile_count = level.tiles().iter().filter(|tile| **tile == Tile::Player).count();
-                if player_tile_count == 0 {
-                    return Err(Box::new(GameError::new(format!(
-                        "Error while loading level pack \"{}\": Level {} does not contain a player tile",
-                        level_pack.id(),
-                        i + 1,
-                    ))));
-                }else if player_tile_count > 1 {
-                    return Err(Box::new(GameError::new(format!(
-                        "Error while loading level pack \"{}\": Level {} contains too many player tiles",
-                        level_pack.id(),
-                        i + 1,
-                    ))));
-                }
-            }
+            Self::check_levels(level_pack, "level pack", LevelProblem::prevents_playing)?;
         }
 
         let mut editor_level_packs = Vec::with_capacity(LevelPack::MAX_LEVEL_PACK_COUNT);
@@ -459,24 +511,42 @@ impl <'a> Game<'a> {
                 ))));
             }
 
-            for (i, level) in level_pack.levels().iter().
-                    map(|level| level.level()).
-                    enumerate() {
-                if level.width() > Self::LEVEL_MAX_WIDTH || level.height() > Self::LEVEL_MAX_HEIGHT {
-                    return Err(Box::new(GameError::new(format!(
-                        "Error while loading editor level pack \"{}\": Level {} is too large (Max: {}x{})",
-                        level_pack.id(),
-                        i + 1,
-                        Self::LEVEL_MAX_WIDTH,
-                        Self::LEVEL_MAX_HEIGHT,
-                    ))));
-                }
-            }
+            Self::check_levels(level_pack, "editor level pack", |problem| matches!(problem, LevelProblem::TooLarge { .. }))?;
         }
         
         editor_level_packs.sort_by_key(|level_pack| level_pack.id().to_string());
 
-        let mut game_state = GameState::new(level_packs, editor_level_packs);
+        //Invalid configuration files are replaced with their defaults
+        let mut warnings = Vec::new();
+        let mut failed_configs = Vec::new();
+
+        let mut settings = Settings::read_or_create().unwrap_or_else(|err| {
+            warnings.push(err.to_string());
+            failed_configs.push("settings");
+
+            Settings::default()
+        });
+        let key_bindings = KeyBindings::read_or_create().unwrap_or_else(|err| {
+            warnings.push(err.to_string());
+            failed_configs.push("key bindings");
+
+            KeyBindings::default()
+        });
+        let theme = Theme::load(settings.theme()).unwrap_or_else(|err| {
+            warnings.push(err.to_string());
+            failed_configs.push("theme");
+
+            Theme::built_in(Theme::DEFAULT_NAME).unwrap()
+        });
+        settings.set_theme(theme.name());
+        Theme::create_example_file()?;
+
+        let mut game_state = GameState::new(level_packs, editor_level_packs, settings, key_bindings, theme);
+        if !failed_configs.is_empty() {
+            game_state.open_dialog(Box::new(DialogOk::new_error(format!(
+                "Cannot load {} (Defaults are used)", failed_configs.join(", "),
+            ))));
+        }
 
         let mut save_game_file = Game::get_or_create_save_game_folder()?;
         save_game_file.push("secret.lvl.sav");
@@ -491,15 +561,76 @@ impl <'a> Game<'a> {
             help_page: HelpPage::new(),
 
             game_state,
+
+            is_console_too_small: false,
+
+            warnings,
         })
     }
 
+    /// Errors of configuration files which could not be loaded at startup
+    pub fn warnings(&self) -> &[String] {
+        &self.warnings
+    }
+
+    /// Returns an error for the first problem of a level in the level pack for which `is_error` returns true
+    fn check_levels(level_pack: &LevelPack, kind: &str, is_error: impl Fn(&LevelProblem) -> bool) -> Result<(), GameError> {
+        for (i, level) in level_pack.levels().iter().
+                map(|level| level.level()).
+                enumerate() {
+            let Some(problem) = validation::validate_level(level).into_iter().find(&is_error) else {
+                continue;
+            };
+
+            let pos = problem.pos().map(|(x, y)| format!(" at ({:02}:{:02})", x + 1, y + 1)).unwrap_or_default();
+            return Err(GameError::new(format!(
+                "Error while loading {kind} \"{}\": Level {}: {problem}{pos}",
+                level_pack.id(),
+                i + 1,
+            )));
+        }
+
+        Ok(())
+    }
+
     #[must_use]
     pub fn update(&mut self) -> bool {
         if self.game_state.should_exit {
             return true;
         }
 
+        let (width, height) = self.console.get_console_size();
+        self.game_state.console_size = (width, height);
+
+        let is_console_too_small = width < Self::CONSOLE_MIN_WIDTH || height < Self::CONSOLE_MIN_HEIGHT;
+        if is_console_too_small != self.is_console_too_small {
+            self.is_console_too_small = is_console_too_small;
+
+            //Dialogs and the help page already pause the current screen
+            if !self.game_state.is_help && !self.game_state.is_dialog_opened() {
+                if let Some(screen) = self.screens.get_mut(&self.game_state.current_screen_id) {
+                    if is_console_too_small {
+                        screen.on_pause(&mut self.game_state);
+                    }else {
+                        screen.on_continue(&mut self.game_state);
+                    }
+                }
+            }
+        }
+
+        if is_console_too_small {
+            //Discard keys and clicks (The game can not be exited here, because screens could not confirm losing unsaved
+            //changes)
+            if self.console.has_input() {
+                let _ = self.console.get_key();
+            }
+            let _ = self.console.get_mouse_pos_clicked();
+
+            self.draw_console_too_small(width, height);
+
+            return false;
+        }
+
         if self.console.has_input() {
             if let Some(key) = self.console.get_key() {
                 self.update_key(key);
@@ -520,10 +651,14 @@ impl <'a> Game<'a> {
         }
 
         //Player background
+        let player_background_delay = self.game_state.settings.player_blink_delay();
         self.game_state.player_background_tmp += 1;
-        if self.game_state.player_background_tmp >= Self::PLAYER_BACKGROUND_DELAY + self.game_state.is_player_background as i32 {
-            //If isPlayerBackground: wait an additional update (25 updates per second, every half
-            //second: switch background/foreground colors [12 updates, 13 updates])
+        if player_background_delay == 0 {
+            self.game_state.player_background_tmp = 0;
+            self.game_state.is_player_background = false;
+        }else if self.game_state.player_background_tmp >= player_background_delay + self.game_state.is_player_background as u32 {
+            //If isPlayerBackground: wait an additional update (25 updates per second, with the default delay every
+            //half second: switch background/foreground colors [12 updates, 13 updates])
             self.game_state.player_background_tmp = 0;
             self.game_state.is_player_background = !self.game_state.is_player_background;
         }
@@ -550,7 +685,8 @@ impl <'a> Game<'a> {
         }
 
         if let Some(dialog) = self.game_state.dialog.as_ref() {
-            if let Some(dialog_selection) = dialog.on_key_pressed(Self::CONSOLE_MIN_WIDTH, Self::CONSOLE_MIN_HEIGHT, key) {
+            let (width, height) = self.game_state.console_size;
+            if let Some(dialog_selection) = dialog.on_key_pressed(width, height, key) {
                 self.game_state.close_dialog();
 
                 let screen = self.screens.get_mut(&self.game_state.current_screen_id);
@@ -572,14 +708,17 @@ impl <'a> Game<'a> {
             return;
         };
 
+        //The help page and dialogs use the whole console
+        let (width, height) = self.game_state.console_size;
+
         if self.game_state.is_help {
-            self.help_page.on_mouse_pressed(Self::CONSOLE_MIN_WIDTH, Self::CONSOLE_MIN_HEIGHT, column, row);
+            self.help_page.on_mouse_pressed(width, height, column, row);
 
             return;
         }
 
         if let Some(dialog) = self.game_state.dialog.as_ref() {
-            if let Some(dialog_selection) = dialog.on_mouse_pressed(Self::CONSOLE_MIN_WIDTH, Self::CONSOLE_MIN_HEIGHT, column, row) {
+            if let Some(dialog_selection) = dialog.on_mouse_pressed(width, height, column, row) {
                 self.game_state.close_dialog();
 
                 let screen = self.screens.get_mut(&self.game_state.current_screen_id);
@@ -591,28 +730,80 @@ impl <'a> Game<'a> {
             return;
         }
 
+        let Some((column, row)) = self.screen_canvas().canvas_pos(column, row) else {
+            return;
+        };
+
         let screen = self.screens.get_mut(&self.game_state.current_screen_id);
         if let Some(screen) = screen {
             screen.on_mouse_pressed(&mut self.game_state, column, row);
         }
     }
 
+    /// Returns the canvas of the whole console
+    fn console_canvas(&self) -> Canvas<'a> {
+        let (width, height) = self.game_state.console_size;
+
+        Canvas::centered(self.console, (width, height), width, height)
+    }
+
+    /// Returns the canvas of the current screen (Screens which are not drawn in full size are drawn on a canvas of the
+    /// minimal console size which is centered in the console)
+    fn screen_canvas(&self) -> Canvas<'a> {
+        if self.screens.get(&self.game_state.current_screen_id).is_some_and(|screen| screen.is_full_size()) {
+            return self.console_canvas();
+        }
+
+        Canvas::centered(self.console, self.game_state.console_size, Self::CONSOLE_MIN_WIDTH, Self::CONSOLE_MIN_HEIGHT)
+    }
+
     fn draw(&self) {
         self.console.repaint();
 
         if self.game_state.is_help {
-            self.help_page.draw(self.console, Self::CONSOLE_MIN_WIDTH, Self::CONSOLE_MIN_HEIGHT);
+            let canvas = self.console_canvas();
+            canvas.set_cursor_pos(0, 0);
+
+            self.help_page.draw(&canvas, canvas.width(), canvas.height(), &self.game_state.key_bindings, &self.game_state.theme);
 
             return;
         }
 
         let screen = self.screens.get(&self.game_state.current_screen_id);
         if let Some(screen) = screen {
-            screen.draw(&self.game_state, self.console);
+            let canvas = self.screen_canvas();
+            canvas.set_cursor_pos(0, 0);
+
+            screen.draw(&self.game_state, &canvas);
         }
 
         if let Some(dialog) = self.game_state.dialog.as_ref() {
-            dialog.draw(self.console, Self::CONSOLE_MIN_WIDTH, Self::CONSOLE_MIN_HEIGHT);
+            let canvas = self.console_canvas();
+
+            dialog.draw(&canvas, canvas.width(), canvas.height());
+        }
+    }
+
+    fn draw_console_too_small(&self, width: usize, height: usize) {
+        self.console.repaint();
+
+        let lines = [
+            "Console is too small".to_string(),
+            format!("Min: {} x {}", Self::CONSOLE_MIN_WIDTH, Self::CONSOLE_MIN_HEIGHT),
+            format!("Current: {width} x {height}"),
+            "Resize the console to continue".to_string(),
+        ];
+
+        let y = height.saturating_sub(lines.len()) / 2;
+        for (i, line) in lines.iter().
+                enumerate().
+                take(height) {
+            //Lines are cut off at the right border
+            let line = &line[..line.len().min(width)];
+
+            self.console.reset_color();
+            self.console.set_cursor_pos(width.saturating_sub(line.len()) / 2, y + i);
+            self.console.draw_text(line);
         }
     }
 }
@@ -0,0 +1,105 @@
+use console_lib::{Color, Console};
+use std::cell::Cell;
+
+/// A rectangular area of the console with its own origin
+///
+/// Dialogs, the help page and the level screens are drawn on a canvas of the console size, all other screens are drawn on
+/// a canvas of the minimal console size which is centered in the console. Text which contains line breaks continues at
+/// the left border of the canvas.
+pub struct Canvas<'a> {
+    console: &'a Console<'a>,
+
+    x_offset: usize,
+    y_offset: usize,
+    width: usize,
+    height: usize,
+
+    /// The cursor position relative to the origin of the canvas
+    cursor_pos: Cell<(usize, usize)>,
+}
+
+impl<'a> Canvas<'a> {
+    /// Centers a canvas of `width` x `height` in a console of `console_width` x `console_height`
+    pub fn centered(console: &'a Console<'a>, (console_width, console_height): (usize, usize), width: usize, height: usize) -> Self {
+        Self {
+            console,
+
+            x_offset: console_width.saturating_sub(width) / 2,
+            y_offset: console_height.saturating_sub(height) / 2,
+            width,
+            height,
+
+            cursor_pos: Default::default(),
+        }
+    }
+
+    /// Centers a canvas of `width` x `height` in this canvas (e.g. for texts which are laid out for the minimal console size)
+    pub fn centered_area(&self, width: usize, height: usize) -> Canvas<'a> {
+        Self {
+            console: self.console,
+
+            x_offset: self.x_offset + self.width.saturating_sub(width) / 2,
+            y_offset: self.y_offset + self.height.saturating_sub(height) / 2,
+            width,
+            height,
+
+            cursor_pos: Default::default(),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Converts a console position (e.g. of a mouse click) to a position on the canvas
+    pub fn canvas_pos(&self, column: usize, row: usize) -> Option<(usize, usize)> {
+        let x = column.checked_sub(self.x_offset)?;
+        let y = row.checked_sub(self.y_offset)?;
+
+        (x < self.width && y < self.height).then_some((x, y))
+    }
+
+    pub fn set_cursor_pos(&self, x: usize, y: usize) {
+        self.cursor_pos.set((x, y));
+        self.console.set_cursor_pos(self.x_offset + x, self.y_offset + y);
+    }
+
+    pub fn draw_text(&self, text: impl Into<String>) {
+        let text = text.into();
+
+        for (i, line) in text.split('\n').
+                enumerate() {
+            if i > 0 {
+                let (_, y) = self.cursor_pos.get();
+                self.set_cursor_pos(0, y + 1);
+            }
+
+            if !line.is_empty() {
+                self.console.draw_text(line);
+
+                let (x, y) = self.cursor_pos.get();
+                self.cursor_pos.set((x + line.chars().count(), y));
+            }
+        }
+    }
+
+    pub fn set_color(&self, fg: Color, bg: Color) {
+        self.console.set_color(fg, bg);
+    }
+
+    pub fn set_color_invertible(&self, fg: Color, bg: Color, inverted: bool) {
+        self.console.set_color_invertible(fg, bg, inverted);
+    }
+
+    pub fn reset_color(&self) {
+        self.console.reset_color();
+    }
+
+    pub fn set_underline(&self, underline: bool) {
+        self.console.set_underline(underline);
+    }
+}
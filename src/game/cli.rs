@@ -0,0 +1,243 @@
+use std::error::Error;
+use std::path::Path;
+use std::process::ExitCode;
+use std::str::FromStr;
+use std::time::Duration;
+use crate::game::{Game, GameError};
+use crate::game::engine::GameplayState;
+use crate::game::level::{LevelLoadingError, LevelPack, LevelWithStats};
+use crate::game::solver::{Solver, SolverResult};
+use crate::game::validation;
+
+/// Runs a command which does not need a console, returns None if the arguments do not start with a command
+pub fn run(args: &[String]) -> Option<ExitCode> {
+    match args.first().map(|arg| arg.as_str()) {
+        Some("--solve") => Some(solve(&args[1..])),
+        Some("--check") => Some(check(&args[1..])),
+
+        _ => None,
+    }
+}
+
+fn read_level_pack(path: &str) -> Result<LevelPack, Box<dyn Error>> {
+    let level_pack_data = match std::fs::read_to_string(path) {
+        Ok(level_pack_data) => level_pack_data,
+        Err(err) => return Err(Box::new(GameError::new(format!(
+            "Error while loading level pack \"{}\": {}",
+            path, err
+        )))),
+    };
+
+    let path_ref = Path::new(path);
+    let level_pack_id = path_ref.file_stem().
+            and_then(|file_stem| file_stem.to_str()).
+            unwrap_or(path);
+
+    if path_ref.extension().is_some_and(|extension| extension == "xsb" || extension == "sok") {
+        Ok(LevelPack::read_from_xsb_str(level_pack_id, path, level_pack_data)?)
+    }else {
+        Ok(LevelPack::read_from_str(level_pack_id, path, level_pack_data)?)
+    }
+}
+
+/// Prints an error to stderr, level loading errors are followed by a snippet of the invalid line
+pub fn print_error(err: &(dyn Error + 'static)) {
+    eprintln!("{err}");
+
+    if let Some(snippet) = error_snippet(err) {
+        eprintln!("{snippet}");
+    }
+}
+
+fn error_snippet(err: &(dyn Error + 'static)) -> Option<String> {
+    err.downcast_ref::<LevelLoadingError>().and_then(|err| err.snippet())
+}
+
+/// Parses the solver options and the level pack paths, prints an error and returns None if the arguments are invalid
+fn parse_solver_args<'a>(args: &'a [String], command: &str) -> Option<(Solver, Vec<&'a str>)> {
+    let mut max_nodes = Solver::DEFAULT_MAX_NODES;
+    let mut time_limit = Solver::DEFAULT_TIME_LIMIT;
+    let mut paths = Vec::new();
+
+    for arg in args {
+        if let Some(value) = arg.strip_prefix("--max-nodes=") {
+            let Ok(value) = usize::from_str(value) else {
+                eprintln!("Invalid node count \"{}\"", value);
+
+                return None;
+            };
+
+            max_nodes = value;
+        }else if let Some(value) = arg.strip_prefix("--time-limit=") {
+            let Ok(value) = u64::from_str(value) else {
+                eprintln!("Invalid time limit \"{}\"", value);
+
+                return None;
+            };
+
+            time_limit = Duration::from_secs(value);
+        }else {
+            paths.push(arg.as_str());
+        }
+    }
+
+    if paths.is_empty() {
+        eprintln!("Usage: {command} [--max-nodes=<count>] [--time-limit=<seconds>] <level pack 1> <level pack 2> ...");
+
+        return None;
+    }
+
+    Some((Solver::new(max_nodes, time_limit), paths))
+}
+
+fn solve(args: &[String]) -> ExitCode {
+    let Some((solver, paths)) = parse_solver_args(args, "--solve") else {
+        return ExitCode::FAILURE;
+    };
+
+    let mut are_all_levels_solvable = true;
+    for path in paths {
+        let level_pack = match read_level_pack(path) {
+            Ok(level_pack) => level_pack,
+            Err(err) => {
+                print_error(err.as_ref());
+                are_all_levels_solvable = false;
+
+                continue;
+            },
+        };
+
+        println!("Level pack \"{}\":", level_pack.id());
+
+        for (i, level) in level_pack.levels().iter().
+                map(|level| level.level()).
+                enumerate() {
+            let player_tile_count = level.tiles().iter().filter(|tile| tile.is_player()).count();
+            let state = GameplayState::new(level.clone());
+            let Some(state) = state.filter(|_| player_tile_count == 1) else {
+                println!("    Level {}: Invalid (Level must contain exactly one player tile)", i + 1);
+                are_all_levels_solvable = false;
+
+                continue;
+            };
+
+            match solver.solve(level, &state) {
+                SolverResult::Solved(solution) => {
+                    println!("    Level {}: Solvable ({} moves, {} pushes)", i + 1, solution.moves().len(), solution.pushes());
+                },
+                SolverResult::Unsolvable => {
+                    println!("    Level {}: Unsolvable", i + 1);
+                    are_all_levels_solvable = false;
+                },
+                SolverResult::GaveUp => {
+                    println!("    Level {}: Gave up (Node or time limit reached)", i + 1);
+                    are_all_levels_solvable = false;
+                },
+            }
+        }
+    }
+
+    if are_all_levels_solvable {
+        ExitCode::SUCCESS
+    }else {
+        ExitCode::FAILURE
+    }
+}
+
+/// Validates level packs without starting the game and prints all problems of all levels
+fn check(args: &[String]) -> ExitCode {
+    let Some((solver, paths)) = parse_solver_args(args, "--check") else {
+        return ExitCode::FAILURE;
+    };
+
+    let mut problem_count = 0;
+    for path in paths {
+        println!("Level pack \"{path}\":");
+
+        let level_pack = match read_level_pack(path) {
+            Ok(level_pack) => level_pack,
+            Err(err) => {
+                println!("    {err}");
+                if let Some(snippet) = error_snippet(err.as_ref()) {
+                    for line in snippet.lines() {
+                        println!("    {line}");
+                    }
+                }
+                problem_count += 1;
+
+                continue;
+            },
+        };
+
+        let extension = Path::new(path).extension().and_then(|extension| extension.to_str());
+        if !matches!(extension, Some("lvl" | "xsb" | "sok")) {
+            println!("    The file extension of level pack must be \".lvl\", \".xsb\" or \".sok\"");
+            problem_count += 1;
+        }
+
+        if level_pack.id().len() > Game::MAX_LEVEL_PACK_ID_LEN {
+            println!("    Level pack ID is too long (Max: {})", Game::MAX_LEVEL_PACK_ID_LEN);
+            problem_count += 1;
+        }
+
+        if level_pack.level_count() == 0 {
+            println!("    Level pack contains no levels");
+            problem_count += 1;
+        }
+
+        for (i, level) in level_pack.levels().iter().
+                enumerate() {
+            problem_count += check_level(&solver, i, level);
+        }
+    }
+
+    if problem_count == 0 {
+        println!("No problems were found");
+
+        ExitCode::SUCCESS
+    }else {
+        println!("{problem_count} problem(s) were found");
+
+        ExitCode::FAILURE
+    }
+}
+
+/// Prints all problems of a level and returns the count of problems
+fn check_level(solver: &Solver, index: usize, level_with_stats: &LevelWithStats) -> usize {
+    let level = level_with_stats.level();
+    let line_numbers = level_with_stats.line_numbers();
+
+    let mut problems = validation::validate_level(level).into_iter().
+            map(|problem| match problem.pos().and_then(|(x, y)| line_numbers.get(y).map(|line_number| (x, line_number))) {
+                Some((x, line_number)) => format!("Line {line_number}, column {}: {problem}", x + 1),
+                None => problem.to_string(),
+            }).collect::<Vec<_>>();
+
+    //The solver is only used for levels without any other problems
+    let mut solver_result = None;
+    if problems.is_empty() {
+        let state = GameplayState::new(level.clone()).unwrap();
+
+        match solver.solve(level, &state) {
+            SolverResult::Solved(solution) => {
+                solver_result = Some(format!("Solvable ({} moves, {} pushes)", solution.moves().len(), solution.pushes()));
+            },
+            SolverResult::Unsolvable => problems.push("Level is unsolvable".to_string()),
+            SolverResult::GaveUp => {
+                solver_result = Some("Solvability is unknown (Node or time limit reached)".to_string());
+            },
+        }
+    }
+
+    let line = line_numbers.first().map(|line_number| format!(" (Line {line_number})")).unwrap_or_default();
+    if problems.is_empty() {
+        println!("    Level {}{line}: OK, {}", index + 1, solver_result.unwrap_or_default());
+    }else {
+        println!("    Level {}{line}:", index + 1);
+        for problem in problems.iter() {
+            println!("        {problem}");
+        }
+    }
+
+    problems.len()
+}
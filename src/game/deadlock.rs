@@ -0,0 +1,170 @@
+use std::collections::VecDeque;
+use crate::game::engine::Direction;
+use crate::game::level::{Level, Tile};
+
+#[cfg(test)]
+mod tests;
+
+/// Detects positions in which not enough boxes can reach a goal anymore
+///
+/// Dead squares are squares from which a box can never be pushed to any goal (Even if all other boxes and keys are
+/// removed), they are calculated once per level by pulling boxes backwards from all goals.
+/// Frozen boxes are boxes which can never be moved again, because they are blocked by walls and other frozen boxes.
+pub struct DeadlockAnalyzer {
+    width: usize,
+    height: usize,
+
+    /// The level without any movable tiles (Player, boxes and keys)
+    static_tiles: Vec<Tile>,
+    dead_squares: Vec<bool>,
+    goal_count: usize,
+}
+
+impl DeadlockAnalyzer {
+    /// `level_original` must be the level in the state in which it was started
+    pub fn new(level_original: &Level) -> Self {
+        let width = level_original.width();
+        let height = level_original.height();
+
+        let static_tiles = level_original.tiles().iter().
+                map(|tile| match tile {
+                    Tile::Player | Tile::Box | Tile::Key => Tile::Empty,
+                    Tile::PlayerInGoal | Tile::BoxInGoal | Tile::KeyInGoal => Tile::Goal,
+                    tile => tile.clone(),
+                }).collect::<Vec<_>>();
+
+        let mut live_squares = vec![false; width * height];
+        let mut queue = VecDeque::new();
+        for (index, tile) in static_tiles.iter().
+                enumerate() {
+            if *tile == Tile::Goal {
+                live_squares[index] = true;
+                queue.push_back((index % width, index / width));
+            }
+        }
+
+        //Pull boxes backwards: A box at "pos" could have been pushed in "direction" from "pos_from" by a player at "pos_player"
+        while let Some(pos) = queue.pop_front() {
+            for direction in Direction::ALL {
+                let pos_from = direction.opposite().next_pos(width, height, pos);
+                let pos_player = direction.opposite().next_pos(width, height, pos_from);
+                if pos_from == pos || pos_player == pos_from || pos_player == pos {
+                    continue;
+                }
+
+                let index_from = pos_from.0 + pos_from.1 * width;
+                let index_player = pos_player.0 + pos_player.1 * width;
+                if live_squares[index_from] || !Self::can_box_enter(&static_tiles[index_from]) ||
+                        !Self::can_player_stand(&static_tiles[index_player]) {
+                    continue;
+                }
+
+                live_squares[index_from] = true;
+                queue.push_back(pos_from);
+            }
+        }
+
+        let dead_squares = live_squares.iter().
+                zip(static_tiles.iter()).
+                map(|(is_live, tile)| !is_live && Self::can_box_enter(tile)).
+                collect::<Vec<_>>();
+
+        let goal_count = static_tiles.iter().
+                filter(|tile| **tile == Tile::Goal).
+                count();
+
+        Self {
+            width,
+            height,
+
+            static_tiles,
+            dead_squares,
+            goal_count,
+        }
+    }
+
+    /// Returns true if a box at this position can never be pushed to a goal
+    pub fn is_dead_square(&self, x: usize, y: usize) -> bool {
+        self.dead_squares.get(x + y * self.width).copied().unwrap_or_default()
+    }
+
+    /// Returns the positions of all boxes in `level` which can never reach a goal
+    pub fn dead_boxes(&self, level: &Level) -> Vec<(usize, usize)> {
+        let mut dead_boxes = Vec::new();
+        for (index, tile) in level.tiles().iter().
+                enumerate() {
+            let pos = (index % self.width, index / self.width);
+
+            let is_dead = match tile {
+                Tile::Box => self.is_dead_square(pos.0, pos.1) || self.is_frozen(level, pos, &mut Vec::new()),
+                _ => false,
+            };
+
+            if is_dead {
+                dead_boxes.push(pos);
+            }
+        }
+
+        dead_boxes
+    }
+
+    /// Returns true if there are fewer boxes which can still reach a goal than goals in `level`
+    pub fn is_deadlocked(&self, level: &Level) -> bool {
+        let box_count = level.tiles().iter().
+                filter(|tile| matches!(tile, Tile::Box | Tile::BoxInGoal)).
+                count();
+
+        box_count - self.dead_boxes(level).len() < self.goal_count
+    }
+
+    /// Returns true if the box at `pos` can neither be moved horizontally nor vertically
+    ///
+    /// Boxes in `checking` are treated as walls (Frozen boxes which block each other)
+    fn is_frozen(&self, level: &Level, pos: (usize, usize), checking: &mut Vec<(usize, usize)>) -> bool {
+        checking.push(pos);
+        let is_frozen = self.is_axis_blocked(level, pos, Direction::Left, checking) &&
+                self.is_axis_blocked(level, pos, Direction::Up, checking);
+        checking.pop();
+
+        is_frozen
+    }
+
+    fn is_axis_blocked(&self, level: &Level, pos: (usize, usize), direction: Direction, checking: &mut Vec<(usize, usize)>) -> bool {
+        let pos_a = direction.next_pos(self.width, self.height, pos);
+        let pos_b = direction.opposite().next_pos(self.width, self.height, pos);
+
+        let (can_box_enter_a, can_player_stand_a) = self.neighbor_state(level, pos, pos_a, checking);
+        let (can_box_enter_b, can_player_stand_b) = self.neighbor_state(level, pos, pos_b, checking);
+
+        //The box can be pushed from a to b or from b to a
+        let can_be_pushed = (can_player_stand_a && can_box_enter_b) || (can_player_stand_b && can_box_enter_a);
+
+        !can_be_pushed
+    }
+
+    /// Returns if a box could be pushed to `pos_neighbor` and if the player could stand at `pos_neighbor`
+    fn neighbor_state(&self, level: &Level, pos: (usize, usize), pos_neighbor: (usize, usize), checking: &mut Vec<(usize, usize)>) -> (bool, bool) {
+        if pos_neighbor == pos || checking.contains(&pos_neighbor) {
+            return (false, false);
+        }
+
+        let is_box = matches!(level.get_tile(pos_neighbor.0, pos_neighbor.1), Some(Tile::Box | Tile::BoxInGoal));
+        if is_box && self.is_frozen(level, pos_neighbor, checking) {
+            return (false, false);
+        }
+
+        let tile = &self.static_tiles[pos_neighbor.0 + pos_neighbor.1 * self.width];
+
+        (Self::can_box_enter(tile), Self::can_player_stand(tile))
+    }
+
+    /// Returns true if a box could be on this static tile at some point (Holes could be filled and doors could be opened)
+    fn can_box_enter(tile: &Tile) -> bool {
+        matches!(tile, Tile::Empty | Tile::Goal | Tile::Hole | Tile::BoxInHole | Tile::LockedDoor)
+    }
+
+    /// Returns true if the player could stand on this static tile at some point without ending the level
+    fn can_player_stand(tile: &Tile) -> bool {
+        Self::can_box_enter(tile) || matches!(tile, Tile::OneWayLeft | Tile::OneWayUp | Tile::OneWayRight | Tile::OneWayDown)
+    }
+}
@@ -0,0 +1,103 @@
+use std::str::FromStr;
+use crate::game::deadlock::*;
+
+fn analyze(level: &str) -> (Level, DeadlockAnalyzer) {
+    let level = Level::from_str(level).unwrap();
+    let deadlock_analyzer = DeadlockAnalyzer::new(&level);
+
+    (level, deadlock_analyzer)
+}
+
+#[test]
+fn dead_squares() {
+    let (_, deadlock_analyzer) = analyze("w: 6, h: 5\n######\n#----#\n#-P@x#\n#----#\n######");
+
+    //Corners
+    assert!(deadlock_analyzer.is_dead_square(1, 1));
+    assert!(deadlock_analyzer.is_dead_square(4, 1));
+    assert!(deadlock_analyzer.is_dead_square(1, 3));
+    assert!(deadlock_analyzer.is_dead_square(4, 3));
+
+    //Walls without goals
+    assert!(deadlock_analyzer.is_dead_square(2, 1));
+    assert!(deadlock_analyzer.is_dead_square(1, 2));
+
+    assert!(!deadlock_analyzer.is_dead_square(2, 2));
+    assert!(!deadlock_analyzer.is_dead_square(3, 2));
+    assert!(!deadlock_analyzer.is_dead_square(4, 2));
+
+    //Walls are not dead squares
+    assert!(!deadlock_analyzer.is_dead_square(0, 0));
+}
+
+#[test]
+fn dead_squares_wrap_around() {
+    let (level, deadlock_analyzer) = analyze("w: 5, h: 1\n@Px--");
+
+    assert!(!deadlock_analyzer.is_dead_square(0, 0));
+    assert!(!deadlock_analyzer.is_deadlocked(&level));
+}
+
+#[test]
+fn corner() {
+    let (level, deadlock_analyzer) = analyze("w: 6, h: 4\n######\n#@---#\n#-P-x#\n######");
+
+    assert_eq!(deadlock_analyzer.dead_boxes(&level), vec![(1, 1)]);
+    assert!(deadlock_analyzer.is_deadlocked(&level));
+}
+
+#[test]
+fn frozen_pair() {
+    let (level, deadlock_analyzer) = analyze("w: 7, h: 4\n#######\n#x@@-x#\n#--P--#\n#######");
+
+    assert!(!deadlock_analyzer.is_dead_square(2, 1));
+    assert!(!deadlock_analyzer.is_dead_square(3, 1));
+
+    assert_eq!(deadlock_analyzer.dead_boxes(&level), vec![(2, 1), (3, 1)]);
+    assert!(deadlock_analyzer.is_deadlocked(&level));
+
+    let (level, deadlock_analyzer) = analyze("w: 7, h: 4\n#######\n#x@-@x#\n#--P--#\n#######");
+
+    assert_eq!(deadlock_analyzer.dead_boxes(&level), vec![]);
+    assert!(!deadlock_analyzer.is_deadlocked(&level));
+}
+
+#[test]
+fn frozen_box_in_goal() {
+    let (level, deadlock_analyzer) = analyze("w: 6, h: 4\n######\n#+@x-#\n#-P--#\n######");
+
+    //The box in the goal blocks the other box, but it is already in a goal
+    assert_eq!(deadlock_analyzer.dead_boxes(&level), vec![(2, 1)]);
+    assert!(deadlock_analyzer.is_deadlocked(&level));
+}
+
+#[test]
+fn more_boxes_than_goals() {
+    let (level, deadlock_analyzer) = analyze("w: 6, h: 4\n######\n#@---#\n#-P@x#\n######");
+
+    assert_eq!(deadlock_analyzer.dead_boxes(&level), vec![(1, 1)]);
+    assert!(!deadlock_analyzer.is_deadlocked(&level));
+}
+
+#[test]
+fn one_way_door() {
+    //The player can stand on one-way doors to push boxes, but boxes cannot be pushed into them
+    let (level, deadlock_analyzer) = analyze("w: 7, h: 3\n#######\nP>@--x#\n#######");
+
+    assert!(!deadlock_analyzer.is_dead_square(2, 1));
+    assert!(!deadlock_analyzer.is_deadlocked(&level));
+
+    let (level, deadlock_analyzer) = analyze("w: 7, h: 3\n#######\nP#@--x#\n#######");
+
+    assert!(deadlock_analyzer.is_dead_square(2, 1));
+    assert!(deadlock_analyzer.is_deadlocked(&level));
+}
+
+#[test]
+fn hole() {
+    //The hole can be filled with the first box, afterward the second box can be pushed over it
+    let (level, deadlock_analyzer) = analyze("w: 9, h: 3\n#########\nP@-@o--x#\n#########");
+
+    assert!(!deadlock_analyzer.is_dead_square(1, 1));
+    assert!(!deadlock_analyzer.is_deadlocked(&level));
+}
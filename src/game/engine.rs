@@ -0,0 +1,336 @@
+use crate::game::level::{Level, Tile};
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Left,
+    Up,
+    Right,
+    Down,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [Direction::Left, Direction::Up, Direction::Right, Direction::Down];
+
+    pub fn one_way_door_tile(&self) -> Tile {
+        match self {
+            Direction::Left => Tile::OneWayLeft,
+            Direction::Up => Tile::OneWayUp,
+            Direction::Right => Tile::OneWayRight,
+            Direction::Down => Tile::OneWayDown,
+        }
+    }
+
+    pub fn opposite(&self) -> Self {
+        match self {
+            Direction::Left => Direction::Right,
+            Direction::Up => Direction::Down,
+            Direction::Right => Direction::Left,
+            Direction::Down => Direction::Up,
+        }
+    }
+
+    /// Returns the LURD character of this direction (Uppercase for pushes, lowercase for walks)
+    pub fn to_lurd(self, is_push: bool) -> char {
+        let c = match self {
+            Direction::Left => 'l',
+            Direction::Up => 'u',
+            Direction::Right => 'r',
+            Direction::Down => 'd',
+        };
+
+        if is_push {
+            c.to_ascii_uppercase()
+        }else {
+            c
+        }
+    }
+
+    /// Parses a LURD character, returns the direction and if the move is a push
+    pub fn from_lurd(c: char) -> Option<(Self, bool)> {
+        let direction = match c.to_ascii_lowercase() {
+            'l' => Direction::Left,
+            'u' => Direction::Up,
+            'r' => Direction::Right,
+            'd' => Direction::Down,
+            _ => return None,
+        };
+
+        Some((direction, c.is_ascii_uppercase()))
+    }
+
+    /// Returns the position next to `pos` in this direction (Level edges wrap around)
+    pub fn next_pos(&self, width: usize, height: usize, pos: (usize, usize)) -> (usize, usize) {
+        let (x, y) = pos;
+
+        match self {
+            Direction::Left => (if x == 0 { width - 1 } else { x - 1 }, y),
+            Direction::Up => (x, if y == 0 { height - 1 } else { y - 1 }),
+            Direction::Right => (if x == width - 1 { 0 } else { x + 1 }, y),
+            Direction::Down => (x, if y == height - 1 { 0 } else { y + 1 }),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GameplayState {
+    level: Level,
+    player_pos: (usize, usize),
+}
+
+impl GameplayState {
+    /// Creates a new gameplay state for a level, returns None if the level does not contain a player tile
+    pub fn new(level: Level) -> Option<Self> {
+        let mut player_pos = None;
+
+        'outer:
+        for i in 0..level.width() {
+            for j in 0..level.height() {
+                if let Some(tile) = level.get_tile(i, j) {
+                    if tile.is_player() {
+                        player_pos = Some((i, j));
+
+                        break 'outer;
+                    }
+                }
+            }
+        }
+
+        player_pos.map(|player_pos| {
+            let mut level = level;
+            level.set_tile(player_pos.0, player_pos.1, Tile::Player);
+
+            Self { level, player_pos }
+        })
+    }
+
+    pub fn level(&self) -> &Level {
+        &self.level
+    }
+
+    pub fn player_pos(&self) -> (usize, usize) {
+        self.player_pos
+    }
+
+    /// Returns a copy of this state with the player placed at `pos` (The tiles are not checked)
+    ///
+    /// `level_original` must be the level in the state in which it was started
+    pub fn with_player_pos(&self, level_original: &Level, pos: (usize, usize)) -> Self {
+        let mut level = self.level.clone();
+        level.set_tile(self.player_pos.0, self.player_pos.1, Self::tile_under_player(level_original, self.player_pos));
+        level.set_tile(pos.0, pos.1, Tile::Player);
+
+        Self { level, player_pos: pos }
+    }
+
+    /// Returns true if the player can enter `tile` in `direction` without pushing anything
+    pub fn is_walkable(tile: &Tile, direction: Direction) -> bool {
+        matches!(tile, Tile::Empty | Tile::Goal | Tile::Secret | Tile::BoxInHole) || *tile == direction.one_way_door_tile()
+    }
+
+    /// Returns the tile which will be at `pos` after the player has left it
+    ///
+    /// `level_original` must be the level in the state in which it was started
+    pub fn tile_under_player(level_original: &Level, pos: (usize, usize)) -> Tile {
+        match level_original.get_tile(pos.0, pos.1).unwrap() {
+            Tile::Player | Tile::Box | Tile::Key | Tile::LockedDoor => Tile::Empty,
+            Tile::PlayerInGoal | Tile::BoxInGoal | Tile::KeyInGoal => Tile::Goal,
+            Tile::Hole | Tile::BoxInHole => Tile::BoxInHole,
+            tile => tile.clone(),
+        }
+    }
+
+    /// Moves the player one tile in `direction` and pushes boxes or keys if possible
+    ///
+    /// `level_original` must be the level in the state in which it was started
+    pub fn apply_move(&self, level_original: &Level, direction: Direction) -> MoveOutcome {
+        if self.level.width() != level_original.width() || self.level.height() != level_original.height() {
+            panic!("Original level must have the same width and height as the modified level!");
+        }
+
+        let mut level = self.level.clone();
+
+        let pos_from = self.player_pos;
+        let pos_to = direction.next_pos(level.width(), level.height(), pos_from);
+
+        //Set players old position to old level data
+        level.set_tile(pos_from.0, pos_from.1, Self::tile_under_player(level_original, pos_from));
+
+        let tile = level.get_tile(pos_to.0, pos_to.1).unwrap().clone();
+        let (can_move, push) = if Self::is_walkable(&tile, direction) {
+            (true, None)
+        }else if matches!(tile, Tile::Box | Tile::BoxInGoal | Tile::Key | Tile::KeyInGoal) {
+            let push = Self::move_box_or_key(&mut level, level_original, pos_to, direction);
+
+            (push.is_some(), push)
+        }else {
+            (false, None)
+        };
+
+        let player_pos = if can_move {
+            pos_to
+        }else {
+            pos_from
+        };
+
+        //Set player to new position
+        level.set_tile(player_pos.0, player_pos.1, Tile::Player);
+
+        MoveOutcome {
+            state: GameplayState { level, player_pos },
+            has_moved: player_pos != pos_from,
+
+            pushed: push.as_ref().map(|push| push.pushed),
+            opened_door: push.as_ref().and_then(|push| push.opened_door),
+            destroyed_key: push.as_ref().and_then(|push| push.destroyed_key),
+
+            found_secret: can_move && tile == Tile::Secret,
+            has_won: push.is_some_and(|push| push.has_won),
+        }
+    }
+
+    /// Pushes the box or key at `pos_from` in `direction`, returns None if it can not be pushed
+    fn move_box_or_key(level: &mut Level, level_original: &Level, pos_from: (usize, usize), direction: Direction) -> Option<Push> {
+        let pos_to = direction.next_pos(level.width(), level.height(), pos_from);
+
+        let index_from = pos_from.0 + pos_from.1 * level.width();
+        let index_to = pos_to.0 + pos_to.1 * level.width();
+
+        let tile_from = level.get_tile(pos_from.0, pos_from.1).unwrap().clone();
+        let tile_to = level.get_tile(pos_to.0, pos_to.1).unwrap().clone();
+
+        let is_box = tile_from == Tile::Box || tile_from == Tile::BoxInGoal;
+
+        if !(matches!(tile_to, Tile::Empty | Tile::Goal | Tile::BoxInHole | Tile::Hole) || (!is_box && tile_to == Tile::LockedDoor)) {
+            return None;
+        }
+
+        let mut push = Push {
+            pushed: (pos_from, pos_to),
+            opened_door: None,
+            destroyed_key: None,
+            has_won: false,
+        };
+
+        let tile_to_new_value = if is_box && tile_to == Tile::Goal {
+            push.has_won = true;
+            for (index, tile) in level.tiles().iter().
+                    enumerate() {
+                if index == index_to {
+                    continue;
+                }
+
+                if *tile == Tile::Goal || *tile == Tile::KeyInGoal {
+                    push.has_won = false;
+
+                    break;
+                }
+
+                let tile_original = &level_original.tiles()[index];
+
+                //If player is on GOAL -> check level field
+                if index == index_from && matches!(tile_original, Tile::Goal | Tile::PlayerInGoal | Tile::BoxInGoal | Tile::KeyInGoal) {
+                    push.has_won = false;
+
+                    break;
+                }
+            }
+
+            Tile::BoxInGoal
+        }else if !is_box && tile_to == Tile::Goal {
+            Tile::KeyInGoal
+        }else if tile_to == Tile::Hole {
+            if is_box {
+                Tile::BoxInHole
+            }else {
+                //Key will be destroyed, only boxes can fill holes
+                push.destroyed_key = Some(pos_to);
+
+                Tile::Hole
+            }
+        }else if is_box {
+            Tile::Box
+        }else if tile_to == Tile::LockedDoor {
+            //Open door and destroy key
+            push.opened_door = Some(pos_to);
+            push.destroyed_key = Some(pos_to);
+
+            Tile::Empty
+        }else {
+            Tile::Key
+        };
+
+        let tile_from_new_value = if tile_from == Tile::Box || tile_from == Tile::Key {
+            Tile::Empty
+        }else {
+            Tile::Goal
+        };
+
+        level.set_tile(pos_from.0, pos_from.1, tile_from_new_value);
+        level.set_tile(pos_to.0, pos_to.1, tile_to_new_value);
+
+        Some(push)
+    }
+}
+
+/// Changes caused by pushing a box or a key
+struct Push {
+    pushed: ((usize, usize), (usize, usize)),
+    opened_door: Option<(usize, usize)>,
+    destroyed_key: Option<(usize, usize)>,
+
+    has_won: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct MoveOutcome {
+    state: GameplayState,
+    has_moved: bool,
+
+    pushed: Option<((usize, usize), (usize, usize))>,
+    opened_door: Option<(usize, usize)>,
+    destroyed_key: Option<(usize, usize)>,
+
+    found_secret: bool,
+    has_won: bool,
+}
+
+impl MoveOutcome {
+    pub fn state(&self) -> &GameplayState {
+        &self.state
+    }
+
+    pub fn into_state(self) -> GameplayState {
+        self.state
+    }
+
+    /// Returns true if the player position has changed
+    pub fn has_moved(&self) -> bool {
+        self.has_moved
+    }
+
+    /// Returns the start and end position of a pushed box or key
+    pub fn pushed(&self) -> Option<((usize, usize), (usize, usize))> {
+        self.pushed
+    }
+
+    /// Returns the position of a locked door which was opened by a key
+    pub fn opened_door(&self) -> Option<(usize, usize)> {
+        self.opened_door
+    }
+
+    /// Returns the position where a key was destroyed (In a hole or in a locked door)
+    pub fn destroyed_key(&self) -> Option<(usize, usize)> {
+        self.destroyed_key
+    }
+
+    pub fn found_secret(&self) -> bool {
+        self.found_secret
+    }
+
+    pub fn has_won(&self) -> bool {
+        self.has_won
+    }
+}
@@ -0,0 +1,200 @@
+use std::str::FromStr;
+use crate::game::engine::*;
+
+fn state_from_str(level: &str) -> (Level, GameplayState) {
+    let level = Level::from_str(level).unwrap();
+    let state = GameplayState::new(level.clone()).unwrap();
+
+    (level, state)
+}
+
+#[test]
+fn no_player_tile() {
+    assert!(GameplayState::new(Level::from_str("w: 3, h: 1\n-@x").unwrap()).is_none());
+}
+
+#[test]
+fn walk() {
+    let (level, state) = state_from_str("w: 3, h: 1\nP--");
+
+    let outcome = state.apply_move(&level, Direction::Right);
+    assert!(outcome.has_moved());
+    assert!(outcome.pushed().is_none());
+    assert!(!outcome.has_won());
+
+    let state = outcome.into_state();
+    assert_eq!(state.player_pos(), (1, 0));
+    assert_eq!(state.level().to_str(), "w: 3, h: 1\n-P-\n");
+}
+
+#[test]
+fn walk_into_wall() {
+    let (level, state) = state_from_str("w: 3, h: 1\nP#-");
+
+    let outcome = state.apply_move(&level, Direction::Right);
+    assert!(!outcome.has_moved());
+    assert_eq!(outcome.into_state().player_pos(), (0, 0));
+}
+
+#[test]
+fn wrap_around() {
+    let (level, state) = state_from_str("w: 3, h: 2\nP--\n---");
+
+    let state = state.apply_move(&level, Direction::Left).into_state();
+    assert_eq!(state.player_pos(), (2, 0));
+
+    let state = state.apply_move(&level, Direction::Up).into_state();
+    assert_eq!(state.player_pos(), (2, 1));
+    assert_eq!(state.level().to_str(), "w: 3, h: 2\n---\n--P\n");
+}
+
+#[test]
+fn one_way_door() {
+    let (level, state) = state_from_str("w: 5, h: 1\n->P<-");
+
+    let outcome = state.apply_move(&level, Direction::Left);
+    assert!(!outcome.has_moved());
+
+    let outcome = state.apply_move(&level, Direction::Right);
+    assert!(!outcome.has_moved());
+
+    let (level, state) = state_from_str("w: 3, h: 1\nP>-");
+
+    let state = state.apply_move(&level, Direction::Right).into_state();
+    assert_eq!(state.player_pos(), (1, 0));
+
+    //One-way door must be restored after the player has left it
+    let state = state.apply_move(&level, Direction::Right).into_state();
+    assert_eq!(state.level().to_str(), "w: 3, h: 1\n->P\n");
+}
+
+#[test]
+fn push_box() {
+    let (level, state) = state_from_str("w: 4, h: 1\nP@--");
+
+    let outcome = state.apply_move(&level, Direction::Right);
+    assert!(outcome.has_moved());
+    assert_eq!(outcome.pushed(), Some(((1, 0), (2, 0))));
+
+    let state = outcome.into_state();
+    assert_eq!(state.level().to_str(), "w: 4, h: 1\n-P@-\n");
+}
+
+#[test]
+fn push_box_blocked() {
+    let (level, state) = state_from_str("w: 4, h: 1\nP@@-");
+
+    let outcome = state.apply_move(&level, Direction::Right);
+    assert!(!outcome.has_moved());
+    assert!(outcome.pushed().is_none());
+
+    let (level, state) = state_from_str("w: 4, h: 1\nP@>-");
+
+    let outcome = state.apply_move(&level, Direction::Right);
+    assert!(!outcome.has_moved());
+}
+
+#[test]
+fn push_box_into_hole() {
+    let (level, state) = state_from_str("w: 4, h: 1\nP@o-");
+
+    let state = state.apply_move(&level, Direction::Right).into_state();
+    assert_eq!(state.level().to_str(), "w: 4, h: 1\n-P.-\n");
+
+    let state = state.apply_move(&level, Direction::Right).into_state();
+    assert_eq!(state.level().to_str(), "w: 4, h: 1\n--P-\n");
+
+    let state = state.apply_move(&level, Direction::Right).into_state();
+    assert_eq!(state.level().to_str(), "w: 4, h: 1\n--.P\n");
+}
+
+#[test]
+fn push_key_into_door() {
+    let (level, state) = state_from_str("w: 4, h: 1\nP*=-");
+
+    let outcome = state.apply_move(&level, Direction::Right);
+    assert!(outcome.has_moved());
+    assert_eq!(outcome.opened_door(), Some((2, 0)));
+    assert_eq!(outcome.destroyed_key(), Some((2, 0)));
+    assert_eq!(outcome.into_state().level().to_str(), "w: 4, h: 1\n-P--\n");
+}
+
+#[test]
+fn push_key_into_hole() {
+    let (level, state) = state_from_str("w: 4, h: 1\nP*o-");
+
+    let outcome = state.apply_move(&level, Direction::Right);
+    assert!(outcome.opened_door().is_none());
+    assert_eq!(outcome.destroyed_key(), Some((2, 0)));
+    assert_eq!(outcome.into_state().level().to_str(), "w: 4, h: 1\n-Po-\n");
+}
+
+#[test]
+fn box_cannot_open_door() {
+    let (level, state) = state_from_str("w: 4, h: 1\nP@=-");
+
+    assert!(!state.apply_move(&level, Direction::Right).has_moved());
+}
+
+#[test]
+fn win() {
+    let (level, state) = state_from_str("w: 6, h: 1\nP@x+-~");
+
+    let outcome = state.apply_move(&level, Direction::Right);
+    assert!(!outcome.has_won());
+
+    let (level, state) = state_from_str("w: 5, h: 1\nP@x+-");
+
+    let outcome = state.apply_move(&level, Direction::Right);
+    assert!(outcome.has_won());
+    assert_eq!(outcome.into_state().level().to_str(), "w: 5, h: 1\n-P++-\n");
+}
+
+#[test]
+fn no_win_if_player_is_on_goal() {
+    let (level, state) = state_from_str("w: 5, h: 1\n-P+x-");
+
+    //Push box out of goal and onto the next goal: The player stands on the old goal
+    let outcome = state.apply_move(&level, Direction::Right);
+    assert!(outcome.has_moved());
+    assert!(!outcome.has_won());
+}
+
+#[test]
+fn player_in_goal() {
+    let (level, state) = state_from_str("w: 4, h: 1\n&@x-");
+    assert_eq!(state.player_pos(), (0, 0));
+    assert_eq!(state.level().to_str(), "w: 4, h: 1\nP@x-\n");
+
+    //The goal below the player is not filled
+    let outcome = state.apply_move(&level, Direction::Right);
+    assert!(!outcome.has_won());
+    assert_eq!(outcome.into_state().level().to_str(), "w: 4, h: 1\nxP+-\n");
+
+    //The goal where the player started must be filled as well
+    let (level, state) = state_from_str("w: 5, h: 1\n&-@x#");
+    let outcome = state.apply_move(&level, Direction::Right).into_state().apply_move(&level, Direction::Right);
+    assert!(outcome.pushed().is_some());
+    assert!(!outcome.has_won());
+}
+
+#[test]
+fn secret() {
+    let (level, state) = state_from_str("w: 3, h: 1\nPs-");
+
+    let outcome = state.apply_move(&level, Direction::Right);
+    assert!(outcome.has_moved());
+    assert!(outcome.found_secret());
+}
+
+#[test]
+fn lurd() {
+    for direction in Direction::ALL {
+        assert_eq!(Direction::from_lurd(direction.to_lurd(false)), Some((direction, false)));
+        assert_eq!(Direction::from_lurd(direction.to_lurd(true)), Some((direction, true)));
+    }
+
+    assert_eq!(Direction::Up.to_lurd(false), 'u');
+    assert_eq!(Direction::Left.to_lurd(true), 'L');
+    assert_eq!(Direction::from_lurd('x'), None);
+}
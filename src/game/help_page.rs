@@ -1,4 +1,8 @@
-use console_lib::{Key, Color, Console};
+use console_lib::{Key, Color};
+use crate::game::canvas::Canvas;
+use crate::game::key_bindings::{Action, BoundKey, KeyBindings};
+use crate::game::level::Tile;
+use crate::game::theme::Theme;
 
 #[derive(Debug, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
 enum SectionLayer {
@@ -29,7 +33,7 @@ impl Section {
         Self { layer, name: name.into(), page }
     }
 
-    pub fn draw(&self, console: &Console, width: usize) {
+    pub fn draw(&self, console: &Canvas, width: usize) {
         console.set_color(self.layer.get_heading_color(), Color::Default);
 
         let heading = match self.layer {
@@ -107,7 +111,7 @@ impl TableOfContents {
         ));
     }
 
-    pub fn draw(&self, console: &Console, x: usize, y: usize, width: usize, height: usize, page: u32) {
+    pub fn draw(&self, console: &Canvas, x: usize, y: usize, width: usize, height: usize, page: u32) {
         for (i, section) in self.sections.iter().
                 skip(height * page as usize).
                 take(height).
@@ -131,7 +135,7 @@ pub struct HelpPage {
 }
 
 impl HelpPage {
-    const PAGE_COUNT: u32 = 10;
+    const PAGE_COUNT: u32 = 12;
 
     pub fn new() -> Self {
         let mut table_of_contents = TableOfContents::new();
@@ -145,9 +149,11 @@ impl HelpPage {
         table_of_contents.add_sub_sub_section("Help menu", 3);
         table_of_contents.add_sub_sub_section("Exit window", 3);
         table_of_contents.add_sub_sub_section("Start menu", 4);
+        table_of_contents.add_sub_section("Settings", 3);
         table_of_contents.add_section("Console arguments", 4);
         table_of_contents.add_section("Gameplay", 5);
         table_of_contents.add_sub_section("Game screen", 5);
+        table_of_contents.add_sub_section("Replay", 5);
         table_of_contents.add_section("Editor", 6);
         table_of_contents.add_sub_section("Controls", 6);
         table_of_contents.add_sub_sub_section("Level Pack selection", 6);
@@ -156,6 +162,8 @@ impl HelpPage {
         table_of_contents.add_sub_sub_section("Level editor (Editing mode)", 7);
         table_of_contents.add_sub_sub_section("Level editor (Editing mode - Tiles)", 8);
         table_of_contents.add_sub_sub_section("Level editor (Editing mode - Decoration Tiles)", 9);
+        table_of_contents.add_sub_sub_section("Level editor (Editing mode - Selection)", 10);
+        table_of_contents.add_sub_sub_section("Level editor (Editing mode - Tools)", 11);
 
         Self {
             table_of_contents,
@@ -163,7 +171,7 @@ impl HelpPage {
         }
     }
 
-    pub fn draw(&self, console: &Console, width: usize, height: usize) {
+    pub fn draw(&self, console: &Canvas, width: usize, height: usize, key_bindings: &KeyBindings, theme: &Theme) {
         console.set_color(Color::Yellow, Color::Default);
         console.set_underline(true);
         console.draw_text("Help menu");
@@ -248,22 +256,16 @@ impl HelpPage {
                 console.draw_text("1.1.4 Game controls\n");
 
                 console.set_underline(false);
-                console.set_color(Color::LightRed, Color::Default);
-                console.draw_text("Arrow keys");
-                console.reset_color();
+                Self::draw_direction_keys(console, key_bindings, Action::MOVE);
                 console.draw_text(": Move position\n");
-                console.set_color(Color::LightRed, Color::Default);
-                console.draw_text("r");
-                console.reset_color();
+                Self::draw_keys(console, key_bindings, Action::Reset);
                 console.draw_text(": Reset level\n");
-                console.set_color(Color::LightRed, Color::Default);
-                console.draw_text("z");
-                console.reset_color();
+                Self::draw_keys(console, key_bindings, Action::Undo);
                 console.draw_text(" / ");
-                console.set_color(Color::LightRed, Color::Default);
-                console.draw_text("y");
-                console.reset_color();
-                console.draw_text(": Undo / Redo");
+                Self::draw_keys(console, key_bindings, Action::Redo);
+                console.draw_text(": Undo / Redo\n");
+                Self::draw_keys(console, key_bindings, Action::Hint);
+                console.draw_text(": Show hint (Best scores with hints are marked with \"(H)\")");
             },
             3 => {
                 console.set_color(Color::Green, Color::Default);
@@ -357,6 +359,18 @@ impl HelpPage {
                 console.draw_text("F1");
                 console.reset_color();
                 console.draw_text(")");
+
+                console.set_underline(true);
+                console.set_color(Color::Green, Color::Default);
+                console.set_cursor_pos(0, 19);
+                console.draw_text("1.3 Settings\n");
+
+                console.set_underline(false);
+                console.set_color(Color::LightRed, Color::Default);
+                console.draw_text("s");
+                console.reset_color();
+                console.draw_text(" (Start menu): Opens the settings (Theme, key bindings, ...)\n");
+                console.draw_text("Files: \"settings.cfg\", \"key_bindings.cfg\", user themes: \"<name>.theme\"");
             },
             4 => {
                 console.set_color(Color::Blue, Color::Default);
@@ -364,7 +378,16 @@ impl HelpPage {
 
                 console.set_underline(false);
                 console.reset_color();
-                console.draw_text("1) No arguments\n2) \"Path to level pack 1\" \"Path to level pack 2\" ...");
+                console.draw_text(
+                    "1) No arguments\n2) \"Path to level pack 1\" \"Path to level pack 2\" ...\n\n\
+                    Commands (The game is not started):\n3) --solve [--max-nodes=<count>] [--time-limit=<seconds>]\n   \
+                    \"Path to level pack 1\" \"Path to level pack 2\" ...\n   \
+                    Checks if all levels are solvable\n\
+                    4) --check [--max-nodes=<count>] [--time-limit=<seconds>]\n   \
+                    \"Path to level pack 1\" \"Path to level pack 2\" ...\n   \
+                    Reports all problems of all levels (Including unsolvable levels)\n\n\
+                    Level packs can be \".lvl\" files or XSB files (\".xsb\" or \".sok\")"
+                );
             },
             5 => {
                 console.set_color(Color::Blue, Color::Default);
@@ -386,46 +409,68 @@ impl HelpPage {
                 console.reset_color();
                 console.set_cursor_pos(1, 6);
                 console.draw_text(
-                    ": Empty\n       : One way doors\n : Wall\n : Player\n     : Box\n \
+                    ": Empty\n       : One way doors\n : Wall\n   : Player\n     : Box\n \
                     : Goal\n : Hole\n   : Key\n : Locked Door\n : Decoration"
                 );
 
-                console.set_color(Color::LightBlue, Color::Default);
-                console.set_cursor_pos(0, 6);
-                console.draw_text("-\n< ^ > v");
-                console.set_color(Color::LightGreen, Color::Default);
-                console.set_cursor_pos(0, 8);
-                console.draw_text("#");
-                console.set_color(Color::Yellow, Color::Default);
-                console.set_cursor_pos(0, 9);
-                console.draw_text("P");
-                console.set_color(Color::LightCyan, Color::Default);
-                console.set_cursor_pos(0, 10);
-                console.draw_text("@");
-                console.set_color(Color::Pink, Color::Default);
-                console.set_cursor_pos(2, 10);
-                console.draw_text("@");
-                console.set_color(Color::Default, Color::LightBlue);
-                console.set_cursor_pos(4, 10);
-                console.draw_text("@");
+                for (x, y, tile) in [
+                    (0, 6, Tile::Empty), (0, 8, Tile::Wall), (0, 9, Tile::Player), (2, 9, Tile::PlayerInGoal), (0, 10, Tile::Box), (2, 10, Tile::BoxInGoal),
+                    (4, 10, Tile::BoxInHole), (0, 11, Tile::Goal), (0, 12, Tile::Hole), (0, 13, Tile::Key), (2, 13, Tile::KeyInGoal),
+                    (0, 14, Tile::LockedDoor), (0, 15, Tile::DecorationBlank),
+                ] {
+                    console.set_cursor_pos(x, y);
+                    tile.draw(console, theme, false, false, false);
+                }
+                console.set_cursor_pos(0, 7);
+                Tile::draw_one_way_doors(console, theme);
+
                 console.set_color(Color::LightRed, Color::Default);
-                console.set_cursor_pos(0, 11);
-                console.draw_text("x");
-                console.set_color(Color::LightBlue, Color::Default);
-                console.set_cursor_pos(0, 12);
-                console.draw_text("O");
-                console.set_color(Color::LightCyan, Color::Default);
-                console.set_cursor_pos(0, 13);
-                console.draw_text("*");
-                console.set_color(Color::Pink, Color::Default);
-                console.set_cursor_pos(2, 13);
-                console.draw_text("*");
+                console.set_cursor_pos(24, 6);
+                console.draw_text("s");
+                console.reset_color();
+                console.draw_text(" (Level selection): Toggle strict mode");
+                console.set_cursor_pos(24, 7);
+                console.draw_text("Strict mode stores the best actions, which");
+                console.set_cursor_pos(24, 8);
+                console.draw_text("include undone moves (\"Total actions\")");
+
+                console.set_underline(true);
+
+                console.set_color(Color::Green, Color::Default);
+                console.set_cursor_pos(0, 17);
+                console.draw_text("3.2 Replay\n");
+
+                console.set_underline(false);
+
                 console.set_color(Color::LightRed, Color::Default);
-                console.set_cursor_pos(0, 14);
-                console.draw_text("=");
-                console.set_color(Color::LightBlue, Color::Default);
-                console.set_cursor_pos(0, 15);
-                console.draw_text(" ");
+                console.draw_text("r");
+                console.reset_color();
+                console.draw_text(" (Level selection): Replays the best solution of the selected level\n");
+                console.set_color(Color::LightRed, Color::Default);
+                console.draw_text("SPACE");
+                console.reset_color();
+                console.draw_text(": Pause / Resume, ");
+                console.set_color(Color::LightRed, Color::Default);
+                console.draw_text("LEFT");
+                console.reset_color();
+                console.draw_text(" / ");
+                console.set_color(Color::LightRed, Color::Default);
+                console.draw_text("RIGHT");
+                console.reset_color();
+                console.draw_text(": Single step backward / forward\n");
+                console.set_color(Color::LightRed, Color::Default);
+                console.draw_text("+");
+                console.reset_color();
+                console.draw_text(" / ");
+                console.set_color(Color::LightRed, Color::Default);
+                console.draw_text("-");
+                console.reset_color();
+                console.draw_text(": Faster / Slower, ");
+                console.set_color(Color::LightRed, Color::Default);
+                console.draw_text("r");
+                console.reset_color();
+                console.draw_text(": Restart\n");
+                console.draw_text("Solutions are saved as LURD move strings in \"<level pack>.lvl.sol\"");
             },
             6 => {
                 console.set_color(Color::Blue, Color::Default);
@@ -451,11 +496,17 @@ impl HelpPage {
 
                 console.set_cursor_pos(0, 7);
                 console.set_color(Color::LightRed, Color::Default);
+                console.draw_text("x");
+                console.reset_color();
+                console.draw_text(": Exports the selected level pack as XSB file to the current directory");
+
+                console.set_cursor_pos(0, 8);
+                console.set_color(Color::LightRed, Color::Default);
                 console.draw_text("DELETE");
                 console.reset_color();
                 console.draw_text(": Deletes the selected level pack");
 
-                console.set_cursor_pos(0, 8);
+                console.set_cursor_pos(0, 9);
                 console.set_color(Color::LightRed, Color::Default);
                 console.draw_text("ESC");
                 console.reset_color();
@@ -483,9 +534,27 @@ impl HelpPage {
 
                 console.set_cursor_pos(0, 13);
                 console.set_color(Color::LightRed, Color::Default);
+                console.draw_text("i");
+                console.reset_color();
+                console.draw_text(": Edits the name, author, description and version of the level pack");
+
+                console.set_cursor_pos(0, 14);
+                console.set_color(Color::LightRed, Color::Default);
+                console.draw_text("t");
+                console.reset_color();
+                console.draw_text(": Edits the title of the selected level");
+
+                console.set_cursor_pos(0, 15);
+                console.set_color(Color::LightRed, Color::Default);
+                console.draw_text("TAB");
+                console.reset_color();
+                console.draw_text(": Switches between the input fields");
+
+                console.set_cursor_pos(0, 16);
+                console.set_color(Color::LightRed, Color::Default);
                 console.draw_text("ESC");
                 console.reset_color();
-                console.draw_text(": Cancels the creation of a new level");
+                console.draw_text(": Cancels the creation of a new level or the editing of information");
             },
             7 => {
 
@@ -495,27 +564,19 @@ impl HelpPage {
                 console.set_underline(false);
 
                 console.set_cursor_pos(0, 3);
-                console.set_color(Color::LightRed, Color::Default);
-                console.draw_text("Arrow keys");
-                console.reset_color();
+                Self::draw_direction_keys(console, key_bindings, Action::MOVE);
                 console.draw_text(": Moves the player\n");
 
                 console.set_cursor_pos(0, 4);
-                console.set_color(Color::LightRed, Color::Default);
-                console.draw_text("z");
-                console.reset_color();
+                Self::draw_keys(console, key_bindings, Action::Undo);
                 console.draw_text(": Undo");
 
                 console.set_cursor_pos(0, 5);
-                console.set_color(Color::LightRed, Color::Default);
-                console.draw_text("y");
-                console.reset_color();
+                Self::draw_keys(console, key_bindings, Action::Redo);
                 console.draw_text(": Redo");
 
                 console.set_cursor_pos(0, 6);
-                console.set_color(Color::LightRed, Color::Default);
-                console.draw_text("r");
-                console.reset_color();
+                Self::draw_keys(console, key_bindings, Action::EditorSwitchMode);
                 console.draw_text(": Goes into the editing mode");
 
                 console.set_underline(true);
@@ -527,46 +588,44 @@ impl HelpPage {
                 console.set_underline(false);
 
                 console.set_cursor_pos(0, 9);
-                console.set_color(Color::LightRed, Color::Default);
-                console.draw_text("Arrow keys");
-                console.reset_color();
+                Self::draw_direction_keys(console, key_bindings, Action::EDITOR_CURSOR);
                 console.draw_text(": Moves cursor position\n");
 
                 console.set_cursor_pos(0, 10);
-                console.set_color(Color::LightRed, Color::Default);
-                console.draw_text("w a s d");
-                console.reset_color();
+                Self::draw_direction_keys(console, key_bindings, Action::EDITOR_DIRECTION);
                 console.draw_text(": Sets the direction of the cursor");
 
                 console.set_cursor_pos(0, 11);
-                console.set_color(Color::LightRed, Color::Default);
-                console.draw_text("i");
-                console.reset_color();
+                Self::draw_keys(console, key_bindings, Action::EditorInsert);
                 console.draw_text(": Inserts a row or column in the cursor direction");
 
                 console.set_cursor_pos(0, 12);
-                console.set_color(Color::LightRed, Color::Default);
-                console.draw_text("c");
-                console.reset_color();
+                Self::draw_keys(console, key_bindings, Action::EditorCopy);
                 console.draw_text(": Copies the current row or column in the cursor direction");
 
                 console.set_cursor_pos(0, 13);
-                console.set_color(Color::LightRed, Color::Default);
-                console.draw_text("z");
-                console.reset_color();
+                Self::draw_keys(console, key_bindings, Action::Undo);
                 console.draw_text(": Undo");
 
                 console.set_cursor_pos(0, 14);
-                console.set_color(Color::LightRed, Color::Default);
-                console.draw_text("y");
-                console.reset_color();
+                Self::draw_keys(console, key_bindings, Action::Redo);
                 console.draw_text(": Redo");
 
                 console.set_cursor_pos(0, 15);
-                console.set_color(Color::LightRed, Color::Default);
-                console.draw_text("r");
-                console.reset_color();
+                Self::draw_keys(console, key_bindings, Action::EditorSwitchMode);
                 console.draw_text(": Goes into the playing mode");
+
+                console.set_cursor_pos(0, 16);
+                Self::draw_keys(console, key_bindings, Action::EditorCheckSolvable);
+                console.draw_text(": Checks if the level is solvable");
+
+                console.set_cursor_pos(0, 18);
+                console.reset_color();
+                console.draw_text("The bottom line shows live checks of the level:");
+                console.set_cursor_pos(0, 19);
+                console.draw_text("Player tiles, Boxes/Goals (+Unfilled holes), Keys/Locked doors,");
+                console.set_cursor_pos(0, 20);
+                console.draw_text("goals which are unreachable from the player and if the level is solved.");
             },
             8 => {
                 console.set_color(Color::Cyan, Color::Default);
@@ -651,6 +710,12 @@ impl HelpPage {
                 console.draw_text(".");
                 console.reset_color();
                 console.draw_text(": Inserts a box in hole tile");
+
+                console.set_cursor_pos(0, 16);
+                console.set_color(Color::LightRed, Color::Default);
+                console.draw_text("&");
+                console.reset_color();
+                console.draw_text(": Inserts a player in goal tile");
             },
             9 => {
                 console.set_color(Color::Cyan, Color::Default);
@@ -668,6 +733,102 @@ impl HelpPage {
                 console.reset_color();
                 console.draw_text(": Inserts a blank decoration tile");
             },
+            10 => {
+                console.set_color(Color::Cyan, Color::Default);
+                console.draw_text("4.1.7 Level editor (Editing mode - Selection)\n");
+
+                console.set_underline(false);
+
+                console.set_cursor_pos(0, 3);
+                Self::draw_keys(console, key_bindings, Action::EditorSelect);
+                console.draw_text(": Starts or ends the selection of an area at the cursor");
+
+                console.set_cursor_pos(0, 4);
+                Self::draw_keys(console, key_bindings, Action::EditorCopy);
+                console.draw_text(": Copies the selected area");
+
+                console.set_cursor_pos(0, 5);
+                Self::draw_keys(console, key_bindings, Action::EditorCut);
+                console.draw_text(": Copies the selected area and clears it");
+
+                console.set_cursor_pos(0, 6);
+                Self::draw_keys(console, key_bindings, Action::EditorPaste);
+                console.draw_text(": Pastes the copied area at the cursor");
+
+                console.set_cursor_pos(0, 7);
+                Self::draw_keys(console, key_bindings, Action::EditorMove);
+                console.draw_text(": Picks up the selected area, use it again to move it to the cursor");
+
+                console.set_cursor_pos(0, 8);
+                console.set_color(Color::LightRed, Color::Default);
+                console.draw_text("DELETE");
+                console.reset_color();
+                console.draw_text(": Clears the selected area");
+
+                console.set_cursor_pos(0, 9);
+                console.reset_color();
+                console.draw_text("Tile keys fill the selected area with the tile (Except player tiles).");
+
+                console.set_cursor_pos(0, 11);
+                console.draw_text("Clicking on the tile at the cursor starts or ends the selection,");
+                console.set_cursor_pos(0, 12);
+                console.draw_text("clicking on another tile selects the opposite corner.");
+                console.set_cursor_pos(0, 13);
+                console.draw_text("Every change of a selected area can be undone as a single step.");
+            },
+            11 => {
+                console.set_color(Color::Cyan, Color::Default);
+                console.draw_text("4.1.8 Level editor (Editing mode - Tools)\n");
+
+                console.set_underline(false);
+
+                console.set_cursor_pos(0, 3);
+                Self::draw_keys(console, key_bindings, Action::EditorFloodFill);
+                console.draw_text(": Fills the connected tiles of the same kind with the last inserted tile");
+
+                console.set_cursor_pos(0, 4);
+                Self::draw_keys(console, key_bindings, Action::EditorLine);
+                console.draw_text(": Draws a line of walls from the start of the selection to the cursor");
+
+                console.set_cursor_pos(0, 5);
+                Self::draw_keys(console, key_bindings, Action::EditorRectangle);
+                console.draw_text(": Draws walls around the border of the selected area");
+
+                console.set_cursor_pos(0, 6);
+                Self::draw_keys(console, key_bindings, Action::EditorAutoWall);
+                console.draw_text(": Surrounds all tiles which the player can reach with walls");
+
+                console.set_cursor_pos(0, 8);
+                console.reset_color();
+                console.draw_text("Filled rectangles: Fill the selected area with walls (#)");
+                console.set_cursor_pos(0, 9);
+                console.draw_text("Every tool can be undone as a single step.");
+
+                for (i, (action, text)) in [
+                    (Action::EditorRotateRight, ": Rotates the level by 90 degrees clockwise"),
+                    (Action::EditorRotateLeft, ": Rotates the level by 90 degrees counterclockwise"),
+                    (Action::EditorRotate180, ": Rotates the level by 180 degrees"),
+                    (Action::EditorMirrorHorizontally, ": Mirrors the level horizontally (Left and right are swapped)"),
+                    (Action::EditorMirrorVertically, ": Mirrors the level vertically (Top and bottom are swapped)"),
+                    (Action::EditorTranspose, ": Swaps rows and columns"),
+                ].into_iter().
+                        enumerate() {
+                    console.set_cursor_pos(0, 11 + i);
+                    Self::draw_keys(console, key_bindings, action);
+                    console.draw_text(text);
+                }
+                console.set_cursor_pos(0, 17);
+                console.reset_color();
+                console.draw_text("One-way doors are turned with the level.");
+
+                console.set_cursor_pos(0, 19);
+                Self::draw_keys(console, key_bindings, Action::EditorResize);
+                console.draw_text(": Resizes the level (Arrow keys choose the side which is kept)");
+
+                console.set_cursor_pos(0, 20);
+                Self::draw_keys(console, key_bindings, Action::EditorAutoCrop);
+                console.draw_text(": Removes empty rows and columns at the border of the level");
+            },
             _ => {},
         }
 
@@ -709,4 +870,58 @@ impl HelpPage {
             self.on_key_pressed(Key::DOWN);
         }
     }
+
+    /// Draws all keys which are bound to the action separated by "/"
+    fn draw_keys(console: &Canvas, key_bindings: &KeyBindings, action: Action) {
+        let keys = key_bindings.keys(action).iter().
+                map(|key| key.to_string()).
+                collect::<Vec<_>>();
+
+        Self::draw_key_list(console, &keys);
+    }
+
+    /// Draws the keys which are bound to the four direction actions (Ordered like [Direction::ALL](crate::game::engine::Direction::ALL))
+    ///
+    /// The n-th keys of all actions are drawn as a group (e.g. "w a s d" or "Arrow keys"), groups are separated by "/".
+    fn draw_direction_keys(console: &Canvas, key_bindings: &KeyBindings, actions: [Action; 4]) {
+        let group_count = actions.iter().
+                map(|action| key_bindings.keys(*action).len()).
+                max().unwrap_or_default();
+
+        let groups = (0..group_count).map(|i| {
+            let keys = actions.map(|action| key_bindings.keys(action).get(i).copied());
+            if keys == [Key::LEFT, Key::UP, Key::RIGHT, Key::DOWN].map(|key| Some(BoundKey::Special(key))) {
+                return "Arrow keys".to_string();
+            }
+
+            //Swap the left and up keys to get the order "w a s d"
+            [keys[1], keys[0], keys[3], keys[2]].iter().
+                    map(|key| key.map_or("-".to_string(), |key| key.to_string())).
+                    collect::<Vec<_>>().
+                    join(" ")
+        }).collect::<Vec<_>>();
+
+        Self::draw_key_list(console, &groups);
+    }
+
+    fn draw_key_list(console: &Canvas, keys: &[String]) {
+        if keys.is_empty() {
+            console.draw_text("(No key)");
+
+            return;
+        }
+
+        for (i, key) in keys.iter().
+                enumerate() {
+            if i > 0 {
+                console.reset_color();
+                console.draw_text("/");
+            }
+
+            console.set_color(Color::LightRed, Color::Default);
+            console.draw_text(key);
+        }
+
+        console.reset_color();
+    }
 }
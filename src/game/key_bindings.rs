@@ -0,0 +1,504 @@
+use console_lib::Key;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{Display, Formatter, Write as _};
+use std::fs::File;
+use std::io::Write;
+use std::str::FromStr;
+use crate::game::Game;
+use crate::game::engine::Direction;
+use crate::game::level::Tile;
+
+#[cfg(test)]
+mod tests;
+
+/// A logical action which can be bound to keys
+#[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum Action {
+    MoveLeft,
+    MoveUp,
+    MoveRight,
+    MoveDown,
+    Undo,
+    Redo,
+    Reset,
+    Hint,
+
+    EditorCursorLeft,
+    EditorCursorUp,
+    EditorCursorRight,
+    EditorCursorDown,
+    EditorDirectionLeft,
+    EditorDirectionUp,
+    EditorDirectionRight,
+    EditorDirectionDown,
+    EditorInsert,
+    EditorCopy,
+    EditorSwitchMode,
+    EditorCheckSolvable,
+    EditorSelect,
+    EditorCut,
+    EditorPaste,
+    EditorMove,
+    EditorFloodFill,
+    EditorLine,
+    EditorRectangle,
+    EditorAutoWall,
+    EditorRotateRight,
+    EditorRotateLeft,
+    EditorRotate180,
+    EditorMirrorHorizontally,
+    EditorMirrorVertically,
+    EditorTranspose,
+    EditorResize,
+    EditorAutoCrop,
+}
+
+impl Action {
+    pub const ALL: [Action; 36] = [
+        Action::MoveLeft, Action::MoveUp, Action::MoveRight, Action::MoveDown,
+        Action::Undo, Action::Redo, Action::Reset, Action::Hint,
+
+        Action::EditorCursorLeft, Action::EditorCursorUp, Action::EditorCursorRight, Action::EditorCursorDown,
+        Action::EditorDirectionLeft, Action::EditorDirectionUp, Action::EditorDirectionRight, Action::EditorDirectionDown,
+        Action::EditorInsert, Action::EditorCopy, Action::EditorSwitchMode, Action::EditorCheckSolvable,
+        Action::EditorSelect, Action::EditorCut, Action::EditorPaste, Action::EditorMove,
+        Action::EditorFloodFill, Action::EditorLine, Action::EditorRectangle, Action::EditorAutoWall,
+        Action::EditorRotateRight, Action::EditorRotateLeft, Action::EditorRotate180,
+        Action::EditorMirrorHorizontally, Action::EditorMirrorVertically, Action::EditorTranspose,
+        Action::EditorResize, Action::EditorAutoCrop,
+    ];
+
+    /// Movement actions (Ordered like [Direction::ALL])
+    pub const MOVE: [Action; 4] = [Action::MoveLeft, Action::MoveUp, Action::MoveRight, Action::MoveDown];
+    /// Cursor movement actions of the level editor (Ordered like [Direction::ALL])
+    pub const EDITOR_CURSOR: [Action; 4] = [
+        Action::EditorCursorLeft, Action::EditorCursorUp, Action::EditorCursorRight, Action::EditorCursorDown,
+    ];
+    /// Cursor direction actions of the level editor (Ordered like [Direction::ALL])
+    pub const EDITOR_DIRECTION: [Action; 4] = [
+        Action::EditorDirectionLeft, Action::EditorDirectionUp, Action::EditorDirectionRight, Action::EditorDirectionDown,
+    ];
+
+    /// Actions of the game screen
+    pub const GAME_SCREEN: [Action; 8] = [
+        Action::MoveLeft, Action::MoveUp, Action::MoveRight, Action::MoveDown,
+        Action::Undo, Action::Redo, Action::Reset, Action::Hint,
+    ];
+    /// Actions of the playing mode of the level editor
+    pub const EDITOR_PLAYING: [Action; 7] = [
+        Action::MoveLeft, Action::MoveUp, Action::MoveRight, Action::MoveDown,
+        Action::Undo, Action::Redo, Action::EditorSwitchMode,
+    ];
+    /// Actions of the editing mode of the level editor
+    pub const EDITOR_EDITING: [Action; 30] = [
+        Action::EditorCursorLeft, Action::EditorCursorUp, Action::EditorCursorRight, Action::EditorCursorDown,
+        Action::EditorDirectionLeft, Action::EditorDirectionUp, Action::EditorDirectionRight, Action::EditorDirectionDown,
+        Action::EditorInsert, Action::EditorCopy, Action::Undo, Action::Redo,
+        Action::EditorSwitchMode, Action::EditorCheckSolvable,
+        Action::EditorSelect, Action::EditorCut, Action::EditorPaste, Action::EditorMove,
+        Action::EditorFloodFill, Action::EditorLine, Action::EditorRectangle, Action::EditorAutoWall,
+        Action::EditorRotateRight, Action::EditorRotateLeft, Action::EditorRotate180,
+        Action::EditorMirrorHorizontally, Action::EditorMirrorVertically, Action::EditorTranspose,
+        Action::EditorResize, Action::EditorAutoCrop,
+    ];
+
+    /// Actions which are used on the same screen and therefore must not share keys
+    const CONTEXTS: [&'static [Action]; 3] = [&Self::GAME_SCREEN, &Self::EDITOR_PLAYING, &Self::EDITOR_EDITING];
+
+    /// The name of the action in the key bindings file
+    pub fn name(&self) -> &'static str {
+        match self {
+            Action::MoveLeft => "move_left",
+            Action::MoveUp => "move_up",
+            Action::MoveRight => "move_right",
+            Action::MoveDown => "move_down",
+            Action::Undo => "undo",
+            Action::Redo => "redo",
+            Action::Reset => "reset",
+            Action::Hint => "hint",
+
+            Action::EditorCursorLeft => "editor_cursor_left",
+            Action::EditorCursorUp => "editor_cursor_up",
+            Action::EditorCursorRight => "editor_cursor_right",
+            Action::EditorCursorDown => "editor_cursor_down",
+            Action::EditorDirectionLeft => "editor_direction_left",
+            Action::EditorDirectionUp => "editor_direction_up",
+            Action::EditorDirectionRight => "editor_direction_right",
+            Action::EditorDirectionDown => "editor_direction_down",
+            Action::EditorInsert => "editor_insert",
+            Action::EditorCopy => "editor_copy",
+            Action::EditorSwitchMode => "editor_switch_mode",
+            Action::EditorCheckSolvable => "editor_check_solvable",
+            Action::EditorSelect => "editor_select",
+            Action::EditorCut => "editor_cut",
+            Action::EditorPaste => "editor_paste",
+            Action::EditorMove => "editor_move",
+            Action::EditorFloodFill => "editor_flood_fill",
+            Action::EditorLine => "editor_line",
+            Action::EditorRectangle => "editor_rectangle",
+            Action::EditorAutoWall => "editor_auto_wall",
+            Action::EditorRotateRight => "editor_rotate_right",
+            Action::EditorRotateLeft => "editor_rotate_left",
+            Action::EditorRotate180 => "editor_rotate_180",
+            Action::EditorMirrorHorizontally => "editor_mirror_horizontally",
+            Action::EditorMirrorVertically => "editor_mirror_vertically",
+            Action::EditorTranspose => "editor_transpose",
+            Action::EditorResize => "editor_resize",
+            Action::EditorAutoCrop => "editor_auto_crop",
+        }
+    }
+
+    /// The name of the action in the settings screen
+    pub fn label(&self) -> &'static str {
+        match self {
+            Action::MoveLeft => "Move left",
+            Action::MoveUp => "Move up",
+            Action::MoveRight => "Move right",
+            Action::MoveDown => "Move down",
+            Action::Undo => "Undo",
+            Action::Redo => "Redo",
+            Action::Reset => "Reset level",
+            Action::Hint => "Show hint",
+
+            Action::EditorCursorLeft => "Cursor left",
+            Action::EditorCursorUp => "Cursor up",
+            Action::EditorCursorRight => "Cursor right",
+            Action::EditorCursorDown => "Cursor down",
+            Action::EditorDirectionLeft => "Direction left",
+            Action::EditorDirectionUp => "Direction up",
+            Action::EditorDirectionRight => "Direction right",
+            Action::EditorDirectionDown => "Direction down",
+            Action::EditorInsert => "Insert row/column",
+            Action::EditorCopy => "Copy row/col/area",
+            Action::EditorSwitchMode => "Switch mode",
+            Action::EditorCheckSolvable => "Check solvability",
+            Action::EditorSelect => "Select area",
+            Action::EditorCut => "Cut area",
+            Action::EditorPaste => "Paste area",
+            Action::EditorMove => "Move area",
+            Action::EditorFloodFill => "Flood fill",
+            Action::EditorLine => "Draw wall line",
+            Action::EditorRectangle => "Draw wall rect",
+            Action::EditorAutoWall => "Auto-wall",
+            Action::EditorRotateRight => "Rotate right",
+            Action::EditorRotateLeft => "Rotate left",
+            Action::EditorRotate180 => "Rotate 180",
+            Action::EditorMirrorHorizontally => "Mirror horizontal",
+            Action::EditorMirrorVertically => "Mirror vertical",
+            Action::EditorTranspose => "Transpose",
+            Action::EditorResize => "Resize level",
+            Action::EditorAutoCrop => "Auto-crop",
+        }
+    }
+
+    fn default_keys(&self) -> Vec<BoundKey> {
+        let key = match self {
+            Action::MoveLeft | Action::EditorCursorLeft => BoundKey::Special(Key::LEFT),
+            Action::MoveUp | Action::EditorCursorUp => BoundKey::Special(Key::UP),
+            Action::MoveRight | Action::EditorCursorRight => BoundKey::Special(Key::RIGHT),
+            Action::MoveDown | Action::EditorCursorDown => BoundKey::Special(Key::DOWN),
+            Action::Undo => BoundKey::Ascii(b'z'),
+            Action::Redo => BoundKey::Ascii(b'y'),
+            Action::Reset | Action::EditorSwitchMode => BoundKey::Ascii(b'r'),
+            Action::Hint => BoundKey::Ascii(b'h'),
+
+            Action::EditorDirectionLeft => BoundKey::Ascii(b'a'),
+            Action::EditorDirectionUp => BoundKey::Ascii(b'w'),
+            Action::EditorDirectionRight => BoundKey::Ascii(b'd'),
+            Action::EditorDirectionDown => BoundKey::Ascii(b's'),
+            Action::EditorInsert => BoundKey::Ascii(b'i'),
+            Action::EditorCopy => BoundKey::Ascii(b'c'),
+            Action::EditorCheckSolvable => BoundKey::Special(Key::F2),
+            Action::EditorSelect => BoundKey::Ascii(b'm'),
+            Action::EditorCut => BoundKey::Ascii(b't'),
+            Action::EditorPaste => BoundKey::Ascii(b'g'),
+            Action::EditorMove => BoundKey::Ascii(b'n'),
+            Action::EditorFloodFill => BoundKey::Ascii(b'f'),
+            Action::EditorLine => BoundKey::Ascii(b'l'),
+            Action::EditorRectangle => BoundKey::Ascii(b'q'),
+            Action::EditorAutoWall => BoundKey::Ascii(b'u'),
+            Action::EditorRotateRight => BoundKey::Ascii(b')'),
+            Action::EditorRotateLeft => BoundKey::Ascii(b'('),
+            Action::EditorRotate180 => BoundKey::Ascii(b'%'),
+            Action::EditorMirrorHorizontally => BoundKey::Ascii(b'|'),
+            Action::EditorMirrorVertically => BoundKey::Ascii(b'_'),
+            Action::EditorTranspose => BoundKey::Ascii(b'\\'),
+            Action::EditorResize => BoundKey::Ascii(b'e'),
+            Action::EditorAutoCrop => BoundKey::Ascii(b'k'),
+        };
+
+        vec![key]
+    }
+}
+
+/// A key which can be bound to an action
+///
+/// ESC, F1, ENTER, DELETE and TAB are reserved for fixed controls and uppercase letters can not be bound,
+/// because the console only reports lowercase letters.
+/// Actions of the editing mode of the level editor can not be bound to tile characters.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum BoundKey {
+    Ascii(u8),
+    Special(Key),
+}
+
+impl BoundKey {
+    const SPECIAL_KEYS: [(&'static str, Key); 15] = [
+        ("LEFT", Key::LEFT), ("UP", Key::UP), ("RIGHT", Key::RIGHT), ("DOWN", Key::DOWN),
+        ("F2", Key::F2), ("F3", Key::F3), ("F4", Key::F4), ("F5", Key::F5), ("F6", Key::F6), ("F7", Key::F7),
+        ("F8", Key::F8), ("F9", Key::F9), ("F10", Key::F10), ("F11", Key::F11), ("F12", Key::F12),
+    ];
+
+    /// Returns the bound key for a pressed key or None if the key can not be bound
+    pub fn from_key(key: Key) -> Option<Self> {
+        if let Some(ascii) = key.to_ascii() {
+            return (ascii == b' ' || ascii.is_ascii_graphic()).then_some(BoundKey::Ascii(ascii));
+        }
+
+        Self::SPECIAL_KEYS.iter().
+                any(|(_, special_key)| *special_key == key).
+                then_some(BoundKey::Special(key))
+    }
+
+    /// Returns true if the key inserts a tile in the editing mode of the level editor
+    ///
+    /// The secret tile can not be inserted with a key ("s" is a cursor direction key by default).
+    pub fn is_editor_tile_key(&self) -> bool {
+        matches!(self, BoundKey::Ascii(ascii) if Tile::from_ascii(*ascii).is_ok_and(|tile| tile != Tile::Secret))
+    }
+
+    pub fn matches(&self, key: Key) -> bool {
+        match self {
+            BoundKey::Ascii(ascii) => key.to_ascii() == Some(*ascii),
+            BoundKey::Special(special_key) => key == *special_key,
+        }
+    }
+}
+
+impl FromStr for BoundKey {
+    type Err = KeyBindingsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "SPACE" {
+            return Ok(BoundKey::Ascii(b' '));
+        }
+
+        if let Some((_, key)) = Self::SPECIAL_KEYS.iter().find(|(name, _)| *name == s) {
+            return Ok(BoundKey::Special(*key));
+        }
+
+        if let [ascii] = s.as_bytes() {
+            if ascii.is_ascii_uppercase() {
+                return Err(KeyBindingsError::new(format!(
+                    "Uppercase key \"{s}\" can not be bound (Use \"{}\" instead)", s.to_ascii_lowercase(),
+                )));
+            }
+
+            if ascii.is_ascii_graphic() {
+                return Ok(BoundKey::Ascii(*ascii));
+            }
+        }
+
+        Err(KeyBindingsError::new(format!("\"{s}\" is not a valid key")))
+    }
+}
+
+impl Display for BoundKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BoundKey::Ascii(b' ') => f.write_str("SPACE"),
+            BoundKey::Ascii(ascii) => f.write_char(*ascii as char),
+            BoundKey::Special(key) => f.write_str(Self::SPECIAL_KEYS.iter().
+                    find(|(_, special_key)| special_key == key).
+                    map_or("?", |(name, _)| name)),
+        }
+    }
+}
+
+/// Maps actions to keys, an action can be bound to multiple keys or to no key at all
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyBindings {
+    bindings: HashMap<Action, Vec<BoundKey>>,
+}
+
+impl KeyBindings {
+    const FILE_NAME: &'static str = "key_bindings.cfg";
+
+    /// Reads the key bindings file from the save game folder, the file is created with the default key bindings
+    /// if it does not exist
+    pub fn read_or_create() -> Result<Self, Box<dyn Error>> {
+        let mut key_bindings_file = Game::get_or_create_save_game_folder()?;
+        key_bindings_file.push(Self::FILE_NAME);
+
+        if !std::fs::exists(&key_bindings_file)? {
+            let key_bindings = Self::default();
+            key_bindings.save()?;
+
+            return Ok(key_bindings);
+        }
+
+        let key_bindings_data = std::fs::read_to_string(&key_bindings_file)?;
+        Ok(Self::from_str(&key_bindings_data).map_err(|err| KeyBindingsError::new(format!(
+            "Error while loading key bindings \"{}\": {err}", key_bindings_file.to_string_lossy(),
+        )))?)
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn Error>> {
+        let mut key_bindings_file = Game::get_or_create_save_game_folder()?;
+        key_bindings_file.push(Self::FILE_NAME);
+
+        let mut file = File::create(key_bindings_file)?;
+        file.write_all(self.to_str().as_bytes())?;
+        file.flush()?;
+
+        Ok(())
+    }
+
+    pub fn keys(&self, action: Action) -> &[BoundKey] {
+        self.bindings.get(&action).map_or(&[], |keys| keys)
+    }
+
+    pub fn set_keys(&mut self, action: Action, keys: Vec<BoundKey>) {
+        self.bindings.insert(action, keys);
+    }
+
+    pub fn is_bound(&self, action: Action, key: Key) -> bool {
+        self.keys(action).iter().any(|bound_key| bound_key.matches(key))
+    }
+
+    /// Returns the first action of `actions` which is bound to the key
+    pub fn action(&self, actions: &[Action], key: Key) -> Option<Action> {
+        actions.iter().
+                find(|action| self.is_bound(**action, key)).
+                copied()
+    }
+
+    /// Returns the direction of the first of the four direction actions (Ordered like [Direction::ALL]) which
+    /// is bound to the key
+    pub fn direction(&self, actions: [Action; 4], key: Key) -> Option<Direction> {
+        actions.into_iter().
+                zip(Direction::ALL).
+                find(|(action, _)| self.is_bound(*action, key)).
+                map(|(_, direction)| direction)
+    }
+
+    /// Returns an error for the first key which is bound to two actions which are used on the same screen or
+    /// which is reserved on the screen of an action
+    pub fn check_conflicts(&self) -> Result<(), KeyBindingsError> {
+        for action in Action::EDITOR_EDITING {
+            if let Some(key) = self.keys(action).iter().find(|key| key.is_editor_tile_key()) {
+                return Err(KeyBindingsError::new(format!(
+                    "Key \"{key}\" of \"{}\" is reserved for inserting tiles in the level editor", action.name(),
+                )));
+            }
+        }
+
+        for context in Action::CONTEXTS {
+            for (i, action) in context.iter().
+                    enumerate() {
+                for other_action in &context[i + 1..] {
+                    if let Some(key) = self.keys(*action).iter().find(|key| self.keys(*other_action).contains(key)) {
+                        return Err(KeyBindingsError::new(format!(
+                            "Key \"{key}\" is bound to \"{}\" and \"{}\"", action.name(), other_action.name(),
+                        )));
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn to_str(&self) -> String {
+        let mut out = String::new();
+
+        out += "# Key bindings: <action> = <keys separated by spaces>\n";
+        out += "# Keys: Single characters (No uppercase letters), SPACE, LEFT, UP, RIGHT, DOWN and F2 - F12\n";
+        out += "# Tile characters can not be bound to editor actions\n";
+        for action in Action::ALL {
+            let _ = write!(out, "{} =", action.name());
+            for key in self.keys(action) {
+                let _ = write!(out, " {key}");
+            }
+            out += "\n";
+        }
+
+        out
+    }
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            bindings: HashMap::from_iter(Action::ALL.map(|action| (action, action.default_keys()))),
+        }
+    }
+}
+
+impl FromStr for KeyBindings {
+    type Err = KeyBindingsError;
+
+    /// Parses key bindings, actions which are not listed keep their default keys
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut key_bindings = Self::default();
+
+        let mut actions_defined = Vec::new();
+        for (i, line) in s.lines().
+                enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with("#") {
+                continue;
+            }
+
+            let error = |err: KeyBindingsError| KeyBindingsError::new(format!("Line {}: {err}", i + 1));
+
+            let Some((name, keys)) = line.split_once("=") else {
+                return Err(error(KeyBindingsError::new("\"=\" is missing")));
+            };
+            let name = name.trim();
+
+            let Some(action) = Action::ALL.into_iter().find(|action| action.name() == name) else {
+                return Err(error(KeyBindingsError::new(format!("\"{name}\" is not a valid action"))));
+            };
+
+            if actions_defined.contains(&action) {
+                return Err(error(KeyBindingsError::new(format!("Action \"{name}\" is defined multiple times"))));
+            }
+            actions_defined.push(action);
+
+            let mut bound_keys = Vec::new();
+            for key in keys.split_whitespace() {
+                let key = BoundKey::from_str(key).map_err(error)?;
+                if !bound_keys.contains(&key) {
+                    bound_keys.push(key);
+                }
+            }
+
+            key_bindings.set_keys(action, bound_keys);
+        }
+
+        key_bindings.check_conflicts()?;
+
+        Ok(key_bindings)
+    }
+}
+
+#[derive(Debug)]
+pub struct KeyBindingsError {
+    message: String
+}
+
+impl KeyBindingsError {
+    fn new(message: impl Into<String>) -> Self {
+        Self { message: message.into() }
+    }
+}
+
+impl Display for KeyBindingsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl Error for KeyBindingsError {}
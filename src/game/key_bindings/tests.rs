@@ -0,0 +1,92 @@
+use std::str::FromStr;
+use crate::game::key_bindings::*;
+
+#[test]
+fn default_key_bindings() {
+    let key_bindings = KeyBindings::default();
+    assert!(key_bindings.is_bound(Action::Undo, Key::Z));
+    assert!(!key_bindings.is_bound(Action::Undo, Key::Y));
+    assert_eq!(key_bindings.direction(Action::MOVE, Key::UP), Some(Direction::Up));
+    assert_eq!(key_bindings.direction(Action::EDITOR_DIRECTION, Key::A), Some(Direction::Left));
+    assert_eq!(key_bindings.direction(Action::MOVE, Key::W), None);
+    assert!(key_bindings.is_bound(Action::EditorSelect, Key::M));
+
+    assert!(key_bindings.check_conflicts().is_ok());
+}
+
+#[test]
+fn read_multiple_keys() {
+    let key_bindings = KeyBindings::from_str("# Comment\n\nmove_left = LEFT a\nmove_up = UP w\nhint = F5 SPACE\n").unwrap();
+    assert_eq!(key_bindings.direction(Action::MOVE, Key::A), Some(Direction::Left));
+    assert_eq!(key_bindings.direction(Action::MOVE, Key::LEFT), Some(Direction::Left));
+    assert_eq!(key_bindings.direction(Action::MOVE, Key::W), Some(Direction::Up));
+    assert!(key_bindings.is_bound(Action::Hint, Key::F5));
+    assert!(key_bindings.is_bound(Action::Hint, Key::SPACE));
+    assert!(!key_bindings.is_bound(Action::Hint, Key::H));
+
+    //Actions which are not listed keep their default keys
+    assert!(key_bindings.is_bound(Action::Undo, Key::Z));
+
+    //Actions without keys are unbound
+    let key_bindings = KeyBindings::from_str("hint =").unwrap();
+    assert!(key_bindings.keys(Action::Hint).is_empty());
+}
+
+#[test]
+fn read_errors() {
+    let err = KeyBindingsError::to_string;
+
+    assert_eq!(err(&KeyBindings::from_str("undo z").unwrap_err()), "Line 1: \"=\" is missing");
+    assert_eq!(err(&KeyBindings::from_str("\njump = SPACE").unwrap_err()), "Line 2: \"jump\" is not a valid action");
+    assert_eq!(err(&KeyBindings::from_str("undo = z\nundo = u").unwrap_err()), "Line 2: Action \"undo\" is defined multiple times");
+    assert_eq!(err(&KeyBindings::from_str("undo = ESC").unwrap_err()), "Line 1: \"ESC\" is not a valid key");
+    assert_eq!(err(&KeyBindings::from_str("undo = Z").unwrap_err()), "Line 1: Uppercase key \"Z\" can not be bound (Use \"z\" instead)");
+
+    //Hint is bound to "h" by default
+    assert_eq!(
+        err(&KeyBindings::from_str("move_left = LEFT h").unwrap_err()),
+        "Key \"h\" is bound to \"move_left\" and \"hint\"",
+    );
+
+    //Actions of different screens may share keys
+    assert!(KeyBindings::from_str("move_left = LEFT a").is_ok());
+
+    //Tile characters are reserved in the editing mode of the level editor
+    assert_eq!(
+        err(&KeyBindings::from_str("editor_copy = x").unwrap_err()),
+        "Key \"x\" of \"editor_copy\" is reserved for inserting tiles in the level editor",
+    );
+    assert_eq!(
+        err(&KeyBindings::from_str("editor_line = #").unwrap_err()),
+        "Key \"#\" of \"editor_line\" is reserved for inserting tiles in the level editor",
+    );
+    assert!(KeyBindings::from_str("move_left = LEFT x").is_ok());
+    assert!(KeyBindings::from_str("editor_direction_down = s").is_ok());
+}
+
+#[test]
+fn write_key_bindings() {
+    let mut key_bindings = KeyBindings::default();
+    key_bindings.set_keys(Action::MoveLeft, vec![BoundKey::Special(Key::LEFT), BoundKey::Ascii(b'a')]);
+    key_bindings.set_keys(Action::Hint, vec![BoundKey::Ascii(b' ')]);
+
+    let key_bindings_str = key_bindings.to_str();
+    assert!(key_bindings_str.contains("\nmove_left = LEFT a\n"), "{key_bindings_str}");
+    assert!(key_bindings_str.contains("\nhint = SPACE\n"), "{key_bindings_str}");
+    assert!(key_bindings_str.contains("\neditor_check_solvable = F2\n"), "{key_bindings_str}");
+
+    //Round trip
+    assert_eq!(KeyBindings::from_str(&key_bindings_str).unwrap(), key_bindings);
+}
+
+#[test]
+fn bound_key_from_key() {
+    assert_eq!(BoundKey::from_key(Key::A), Some(BoundKey::Ascii(b'a')));
+    assert_eq!(BoundKey::from_key(Key::SPACE), Some(BoundKey::Ascii(b' ')));
+    assert_eq!(BoundKey::from_key(Key::F12), Some(BoundKey::Special(Key::F12)));
+
+    //Reserved keys
+    assert_eq!(BoundKey::from_key(Key::ESC), None);
+    assert_eq!(BoundKey::from_key(Key::F1), None);
+    assert_eq!(BoundKey::from_key(Key::ENTER), None);
+}
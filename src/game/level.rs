@@ -1,11 +1,20 @@
 use crate::game::Game;
-use console_lib::{Color, Console};
+use crate::game::engine::Direction;
+use crate::game::settings::Ranking;
+use crate::game::theme::{Style, Theme, ThemeElement};
+use crate::game::canvas::Canvas;
+use crate::game::validation;
 use std::error::Error;
 use std::fmt::{Debug, Display, Formatter, Write as _};
 use std::fs::File;
 use std::io::Write;
 use std::str::FromStr;
 
+mod xsb;
+
+#[cfg(test)]
+mod tests;
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Tile {
     Empty,
@@ -18,6 +27,8 @@ pub enum Tile {
     Wall,
 
     Player,
+    /// The player on a goal in the start state of a level (The gameplay state only contains player tiles)
+    PlayerInGoal,
 
     Key,
     KeyInGoal,
@@ -48,6 +59,7 @@ impl Tile {
             b'#' => Ok(Tile::Wall),
 
             b'p' | b'P' => Ok(Tile::Player),
+            b'&' => Ok(Tile::PlayerInGoal),
 
             b'*' => Ok(Tile::Key),
             b'~' => Ok(Tile::KeyInGoal),
@@ -64,7 +76,14 @@ impl Tile {
 
             b's' | b'S' => Ok(Tile::Secret),
 
-            _ => Err(LevelLoadingError::new("Invalid tile")),
+            _ => Err(LevelLoadingError::new(
+                LevelLoadingErrorKind::InvalidTile,
+                if a.is_ascii_graphic() {
+                    format!("\"{}\" is not a valid tile", a as char)
+                }else {
+                    format!("The byte 0x{a:02X} is not a valid tile")
+                },
+            )),
         }
     }
 
@@ -80,6 +99,7 @@ impl Tile {
             Tile::Wall => b'#',
 
             Tile::Player => b'P',
+            Tile::PlayerInGoal => b'&',
 
             Tile::Key => b'*',
             Tile::KeyInGoal => b'~',
@@ -98,81 +118,150 @@ impl Tile {
         }
     }
 
-    pub fn draw(&self, console: &Console, is_player_background: bool, inverted: bool) {
-        match self {
-            Tile::Empty => {
-                console.set_color_invertible(Color::LightBlue, Color::Default, inverted);
-                console.draw_text("-");
-            },
-            Tile::OneWayLeft => {
-                console.set_color_invertible(Color::LightBlue, Color::Default, inverted);
-                console.draw_text("<");
-            },
-            Tile::OneWayUp => {
-                console.set_color_invertible(Color::LightBlue, Color::Default, inverted);
-                console.draw_text("^");
-            },
-            Tile::OneWayRight => {
-                console.set_color_invertible(Color::LightBlue, Color::Default, inverted);
-                console.draw_text(">");
-            },
-            Tile::OneWayDown => {
-                console.set_color_invertible(Color::LightBlue, Color::Default, inverted);
-                console.draw_text("v");
-            },
-            Tile::Wall => {
-                console.set_color_invertible(Color::LightGreen, Color::Default, inverted);
-                console.draw_text("#");
-            },
-            Tile::Player => {
-                if is_player_background {
-                    console.set_color_invertible(Color::Default, Color::Yellow, inverted);
-                }else {
-                    console.set_color_invertible(Color::Yellow, Color::Default, inverted);
-                }
-                console.draw_text("P");
-            },
-            Tile::Key => {
-                console.set_color_invertible(Color::LightCyan, Color::Default, inverted);
-                console.draw_text("*");
-            },
-            Tile::KeyInGoal => {
-                console.set_color_invertible(Color::LightPink, Color::Default, inverted);
-                console.draw_text("*");
-            },
-            Tile::LockedDoor => {
-                console.set_color_invertible(Color::LightRed, Color::Default, inverted);
-                console.draw_text("=");
-            },
-            Tile::Box => {
-                console.set_color_invertible(Color::LightCyan, Color::Default, inverted);
-                console.draw_text("@");
-            },
-            Tile::BoxInGoal => {
-                console.set_color_invertible(Color::LightPink, Color::Default, inverted);
-                console.draw_text("@");
-            },
-            Tile::Goal => {
-                console.set_color_invertible(Color::LightRed, Color::Default, inverted);
-                console.draw_text("x");
-            },
-            Tile::Hole => {
-                console.set_color_invertible(Color::LightBlue, Color::Default, inverted);
-                console.draw_text("O");
-            },
-            Tile::BoxInHole => {
-                console.set_color_invertible(Color::Default, Color::LightBlue, inverted);
-                console.draw_text("@");
-            },
-            Tile::DecorationBlank => {
-                console.set_color_invertible(Color::LightBlue, Color::Default, inverted);
+    pub fn is_player(&self) -> bool {
+        matches!(self, Tile::Player | Tile::PlayerInGoal)
+    }
+
+    pub fn style(&self, theme: &Theme, is_player_background: bool, is_deadlocked: bool) -> Style {
+        theme.style(match self {
+            Tile::Player if is_player_background => ThemeElement::PlayerBlink,
+            Tile::Box if is_deadlocked => ThemeElement::BoxDeadlocked,
+
+            tile => ThemeElement::from_tile(tile),
+        })
+    }
+
+    pub fn draw(&self, console: &Canvas, theme: &Theme, is_player_background: bool, inverted: bool, is_deadlocked: bool) {
+        self.style(theme, is_player_background, is_deadlocked).draw(console, inverted);
+    }
+
+    /// Draws all one-way doors separated by spaces ("< ^ > v") for help texts
+    pub fn draw_one_way_doors(console: &Canvas, theme: &Theme) {
+        for (i, tile) in [Tile::OneWayLeft, Tile::OneWayUp, Tile::OneWayRight, Tile::OneWayDown].iter().enumerate() {
+            if i > 0 {
+                console.reset_color();
                 console.draw_text(" ");
-            },
-            Tile::Secret => {
-                console.set_color_invertible(Color::LightBlue, Color::Default, inverted);
-                console.draw_text("+");
-            },
+            }
+
+            tile.draw(console, theme, false, false, false);
+        }
+    }
+}
+
+/// The position of a level on the console, the width of its tiles and the visible part of the level
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LevelView {
+    x_offset: usize,
+    y_offset: usize,
+    tile_width: usize,
+
+    /// The position of the first visible tile
+    scroll_pos: (usize, usize),
+    /// The number of visible columns and rows of tiles
+    visible_size: (usize, usize),
+}
+
+impl LevelView {
+    /// Centers the level horizontally in an area of `width` x `height` console cells which starts at row `y_offset`
+    /// (The area depends on the current console size)
+    ///
+    /// If `wide_tiles` is true, each tile is drawn with two columns if the level fits (Terminal cells are about twice
+    /// as tall as wide). Levels which do not fit are scrolled so that `focus_pos` (e.g. the player or the editor cursor)
+    /// is in the center of the view.
+    pub fn new(level: &Level, (width, height): (usize, usize), y_offset: usize, wide_tiles: bool, focus_pos: (usize, usize)) -> Self {
+        let tile_width = if wide_tiles && level.width() * 2 <= width {
+            2
+        }else {
+            1
         };
+
+        let visible_size = (level.width().min(width / tile_width), level.height().min(height));
+        let scroll = |focus: usize, visible: usize, size: usize| focus.saturating_sub(visible / 2).min(size - visible);
+
+        Self {
+            x_offset: (width - visible_size.0 * tile_width) / 2,
+            y_offset,
+            tile_width,
+
+            scroll_pos: (
+                scroll(focus_pos.0, visible_size.0, level.width()),
+                scroll(focus_pos.1, visible_size.1, level.height()),
+            ),
+            visible_size,
+        }
+    }
+
+    pub fn y_offset(&self) -> usize {
+        self.y_offset
+    }
+
+    /// The number of console rows which are used by the level
+    pub fn visible_height(&self) -> usize {
+        self.visible_size.1
+    }
+
+    /// The number of console columns which are used by each tile
+    pub fn tile_width(&self) -> usize {
+        self.tile_width
+    }
+
+    /// Returns the console position at which the tile of the level is drawn if it is visible
+    pub fn console_pos(&self, (x, y): (usize, usize)) -> Option<(usize, usize)> {
+        let column = x.checked_sub(self.scroll_pos.0).filter(|column| *column < self.visible_size.0)?;
+        let row = y.checked_sub(self.scroll_pos.1).filter(|row| *row < self.visible_size.1)?;
+
+        Some((self.x_offset + column * self.tile_width, self.y_offset + row))
+    }
+
+    /// Returns the position of the tile of the level which is drawn at the console position
+    pub fn tile_pos(&self, column: usize, row: usize) -> Option<(usize, usize)> {
+        let x = column.checked_sub(self.x_offset)? / self.tile_width;
+        let y = row.checked_sub(self.y_offset)?;
+
+        (x < self.visible_size.0 && y < self.visible_size.1).then_some((x + self.scroll_pos.0, y + self.scroll_pos.1))
+    }
+}
+
+/// A rectangular area of tiles in a level
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TileArea {
+    pos: (usize, usize),
+    width: usize,
+    height: usize,
+}
+
+impl TileArea {
+    /// Creates the smallest area which contains both corners
+    pub fn from_corners(corner_a: (usize, usize), corner_b: (usize, usize)) -> Self {
+        Self {
+            pos: (corner_a.0.min(corner_b.0), corner_a.1.min(corner_b.1)),
+            width: corner_a.0.abs_diff(corner_b.0) + 1,
+            height: corner_a.1.abs_diff(corner_b.1) + 1,
+        }
+    }
+
+    /// Creates an area which only contains the tile at `pos`
+    pub fn from_pos(pos: (usize, usize)) -> Self {
+        Self::from_corners(pos, pos)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (x, y): (usize, usize)) -> bool {
+        (self.pos.0..self.pos.0 + self.width).contains(&x) && (self.pos.1..self.pos.1 + self.height).contains(&y)
+    }
+
+    /// Returns all positions of the area row by row
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let TileArea { pos: (x, y), width, height } = *self;
+
+        (y..y + height).flat_map(move |y| (x..x + width).map(move |x| (x, y)))
     }
 }
 
@@ -222,105 +311,222 @@ impl Level {
         self.tiles[x + y * self.width] = tile;
     }
 
-    pub fn move_box_or_key(&mut self, level_original: &Level, has_won: &mut bool, from_pos_x: usize, from_pos_y: usize, to_pos_x: usize, to_pos_y: usize) -> bool {
-        if self.width != level_original.width || self.height != level_original.height {
-            panic!("Original level must have the same width and height as the modified level!");
+    /// Returns a level which contains a copy of the tiles in the area, the area must be inside the level
+    pub fn copy_area(&self, area: TileArea) -> Level {
+        let mut level = Level::new(area.width, area.height);
+        for (x, y) in area.positions() {
+            level.set_tile(x - area.pos.0, y - area.pos.1, self.get_tile(x, y).unwrap().clone());
         }
 
-        let move_x = to_pos_x as isize - from_pos_x as isize;
-        let move_y = to_pos_y as isize - from_pos_y as isize;
-        let index_from = to_pos_x + to_pos_y * self.width;
-        let index_to = ((to_pos_x as isize + move_x + self.width as isize) % self.width as isize) as usize +
-                ((to_pos_y as isize + move_y + self.height as isize) % self.height as isize) as usize * self.width;
+        level
+    }
 
-        let Some(tile_from) = self.tiles.get(index_from) else {
-            return false;
-        };
-        let Some(tile_to) = self.tiles.get(index_to) else {
-            return false;
-        };
+    /// Sets all tiles in the area to `tile`, positions outside the level are ignored
+    pub fn fill_area(&mut self, area: TileArea, tile: Tile) {
+        let (width, height) = (self.width, self.height);
+        for (x, y) in area.positions().
+                filter(|(x, y)| *x < width && *y < height) {
+            self.set_tile(x, y, tile.clone());
+        }
+    }
+
+    /// Replaces the tile at `pos` and all connected tiles of the same kind with `tile` (Without wrapping around the borders)
+    pub fn flood_fill(&mut self, pos: (usize, usize), tile: Tile) {
+        let tile_replaced = self.get_tile(pos.0, pos.1).unwrap().clone();
+        if tile_replaced == tile {
+            return;
+        }
 
-        let is_box = *tile_from == Tile::Box || *tile_from == Tile::BoxInGoal;
+        self.set_tile(pos.0, pos.1, tile.clone());
 
-        let tile_from_new_value;
-        let tile_to_new_value;
+        let mut stack = vec![pos];
+        while let Some((x, y)) = stack.pop() {
+            for (x, y) in [(x.wrapping_sub(1), y), (x + 1, y), (x, y.wrapping_sub(1)), (x, y + 1)] {
+                if x < self.width && y < self.height && *self.get_tile(x, y).unwrap() == tile_replaced {
+                    self.set_tile(x, y, tile.clone());
+                    stack.push((x, y));
+                }
+            }
+        }
+    }
 
-        if *tile_to == Tile::Empty ||*tile_to == Tile::Goal ||  *tile_to == Tile::BoxInHole ||
-                *tile_to == Tile::Hole || (!is_box && *tile_to == Tile::LockedDoor) {
-            if is_box && *tile_to == Tile::Goal {
-                tile_to_new_value = Tile::BoxInGoal;
+    /// Sets the tiles of the straight line between both positions to `tile`
+    pub fn draw_line(&mut self, from: (usize, usize), to: (usize, usize), tile: Tile) {
+        let steps = from.0.abs_diff(to.0).max(from.1.abs_diff(to.1));
+        for i in 0..=steps {
+            let interpolate = |from: usize, to: usize| if steps == 0 {
+                from
+            }else {
+                (from as f64 + (to as f64 - from as f64) * i as f64 / steps as f64).round() as usize
+            };
 
-                *has_won = true;
-                for (index, tile) in self.tiles.iter().
-                        enumerate() {
-                    if index == index_to {
-                        continue;
-                    }
+            self.set_tile(interpolate(from.0, to.0), interpolate(from.1, to.1), tile.clone());
+        }
+    }
 
-                    if *tile == Tile::Goal || *tile == Tile::KeyInGoal {
-                        *has_won = false;
+    /// Sets the tiles at the border of the area to `tile`, the area must be inside the level
+    pub fn draw_rectangle(&mut self, area: TileArea, tile: Tile) {
+        let TileArea { pos: (left, top), width, height } = area;
+        let (right, bottom) = (left + width - 1, top + height - 1);
 
-                        break;
-                    }
+        for (x, y) in area.positions().
+                filter(|(x, y)| *x == left || *x == right || *y == top || *y == bottom) {
+            self.set_tile(x, y, tile.clone());
+        }
+    }
 
-                    let tile_original = &level_original.tiles[index];
+    /// Replaces all empty and decoration tiles next to the tiles which the player can reach (Including diagonally) with walls
+    ///
+    /// Neighbours wrap around the borders of the level like moves.
+    pub fn surround_with_walls(&mut self, player_pos: (usize, usize)) {
+        let (width, height) = (self.width, self.height);
+        let reachable = validation::reachable_positions(self, player_pos);
 
-                    //If player is on GOAL -> check level field
-                    if index == index_from && (*tile_original == Tile::Goal ||
-                            *tile_original == Tile::BoxInGoal || *tile_original == Tile::KeyInGoal) {
-                        *has_won = false;
+        for index in (0..width * height).filter(|index| reachable[*index]) {
+            let (x, y) = (index % width, index / width);
 
-                        break;
+            for y in [(y + height - 1) % height, y, (y + 1) % height] {
+                for x in [(x + width - 1) % width, x, (x + 1) % width] {
+                    if !reachable[x + y * width] && matches!(self.get_tile(x, y).unwrap(), Tile::Empty | Tile::DecorationBlank) {
+                        self.set_tile(x, y, Tile::Wall);
                     }
                 }
-            }else if !is_box && *tile_to == Tile::Goal {
-                tile_to_new_value = Tile::KeyInGoal;
-            }else if *tile_to == Tile::Hole {
-                if is_box {
-                    tile_to_new_value = Tile::BoxInHole;
-                }else {
-                    //Key will be destroyed, only boxes can fill holes
-                    tile_to_new_value = Tile::Hole;
-                }
-            }else if is_box {
-                tile_to_new_value = Tile::Box;
-            }else if *tile_to == Tile::LockedDoor {
-                //Open door and destroy key
-                tile_to_new_value = Tile::Empty;
-            }else {
-                tile_to_new_value = Tile::Key;
             }
+        }
+    }
 
-            if *tile_from == Tile::Box || *tile_from == Tile::Key {
-                tile_from_new_value = Tile::Empty;
-            }else if *tile_from == Tile::BoxInHole {
-                tile_from_new_value = Tile::BoxInHole;
-            }else {
-                tile_from_new_value = Tile::Goal;
+    /// Returns a level of the given size which contains this level with its top left corner at `offset`
+    ///
+    /// New tiles are empty and tiles which do not fit are cut off (Negative offsets cut off the left or top side).
+    pub fn resized(&self, width: usize, height: usize, offset: (isize, isize)) -> Level {
+        let mut level = Level::new(width, height);
+        for (x, y) in TileArea::from_corners((0, 0), (width - 1, height - 1)).positions() {
+            let source_x = x as isize - offset.0;
+            let source_y = y as isize - offset.1;
+            if (0..self.width as isize).contains(&source_x) && (0..self.height as isize).contains(&source_y) {
+                level.set_tile(x, y, self.get_tile(source_x as usize, source_y as usize).unwrap().clone());
             }
+        }
+
+        level
+    }
+
+    /// Returns the smallest area which contains all tiles except empty and decoration tiles or None if there are none
+    pub fn content_area(&self) -> Option<TileArea> {
+        let mut positions = TileArea::from_corners((0, 0), (self.width - 1, self.height - 1)).positions().
+                filter(|(x, y)| !matches!(self.get_tile(*x, *y).unwrap(), Tile::Empty | Tile::DecorationBlank));
+
+        let first = positions.next()?;
+        Some(positions.fold(TileArea::from_pos(first), |area, (x, y)| {
+            let TileArea { pos: (left, top), width, height } = area;
 
-            self.tiles[index_from] = tile_from_new_value;
-            self.tiles[index_to] = tile_to_new_value;
+            TileArea::from_corners(
+                (left.min(x), top.min(y)),
+                ((left + width - 1).max(x), (top + height - 1).max(y)),
+            )
+        }))
+    }
+
+    /// Returns the level rotated by 90 degrees clockwise
+    pub fn rotated_clockwise(&self) -> Level {
+        self.transposed().mirrored_horizontally()
+    }
 
-            return true;
+    /// Returns the level rotated by 90 degrees counterclockwise
+    pub fn rotated_counterclockwise(&self) -> Level {
+        self.transposed().mirrored_vertically()
+    }
+
+    pub fn rotated_180(&self) -> Level {
+        self.mirrored_horizontally().mirrored_vertically()
+    }
+
+    /// Returns the level with the left and the right side swapped
+    pub fn mirrored_horizontally(&self) -> Level {
+        self.transformed(
+            (self.width, self.height), |(x, y)| (self.width - 1 - x, y),
+            [Tile::OneWayRight, Tile::OneWayUp, Tile::OneWayLeft, Tile::OneWayDown],
+        )
+    }
+
+    /// Returns the level with the top and the bottom side swapped
+    pub fn mirrored_vertically(&self) -> Level {
+        self.transformed(
+            (self.width, self.height), |(x, y)| (x, self.height - 1 - y),
+            [Tile::OneWayLeft, Tile::OneWayDown, Tile::OneWayRight, Tile::OneWayUp],
+        )
+    }
+
+    /// Returns the level mirrored at the diagonal from the top left to the bottom right corner (Rows become columns)
+    pub fn transposed(&self) -> Level {
+        self.transformed(
+            (self.height, self.width), |(x, y)| (y, x),
+            [Tile::OneWayUp, Tile::OneWayLeft, Tile::OneWayDown, Tile::OneWayRight],
+        )
+    }
+
+    /// Creates a level of the given size in which every tile is copied from the position returned by `source_pos`
+    ///
+    /// One-way doors are replaced by the tiles in `one_way_doors` (Ordered like [Direction::ALL]).
+    fn transformed(&self, (width, height): (usize, usize), source_pos: impl Fn((usize, usize)) -> (usize, usize), one_way_doors: [Tile; 4]) -> Level {
+        let mut level = Level::new(width, height);
+        for (x, y) in TileArea::from_corners((0, 0), (width - 1, height - 1)).positions() {
+            let (source_x, source_y) = source_pos((x, y));
+
+            let tile = match self.get_tile(source_x, source_y).unwrap() {
+                Tile::OneWayLeft => one_way_doors[0].clone(),
+                Tile::OneWayUp => one_way_doors[1].clone(),
+                Tile::OneWayRight => one_way_doors[2].clone(),
+                Tile::OneWayDown => one_way_doors[3].clone(),
+
+                tile => tile.clone(),
+            };
+            level.set_tile(x, y, tile);
         }
 
-        false
+        level
+    }
+
+    /// Copies all tiles of `level` to this level with the top left corner at `pos`, tiles which do not fit are cut off
+    pub fn paste(&mut self, pos: (usize, usize), level: &Level) {
+        let (width, height) = (self.width, self.height);
+        let area = TileArea { pos, width: level.width, height: level.height };
+        for (x, y) in area.positions().
+                filter(|(x, y)| *x < width && *y < height) {
+            self.set_tile(x, y, level.get_tile(x - pos.0, y - pos.1).unwrap().clone());
+        }
     }
 
-    pub fn draw(&self, console: &Console, x_offset: usize, y_offset: usize, is_player_background: bool, cursor_pos: Option<(usize, usize)>) {
-        let mut tile_iter = self.tiles.iter();
+    /// Draws the level, tiles in `marked_area` are drawn inverted and boxes at the positions in `deadlocked_boxes` are drawn
+    /// in a different color
+    ///
+    /// Only the visible part of the level in the view is drawn.
+    pub fn draw(&self, console: &Canvas, theme: &Theme, view: LevelView, is_player_background: bool, marked_area: Option<TileArea>, deadlocked_boxes: &[(usize, usize)]) {
+        let (scroll_x, scroll_y) = view.scroll_pos;
+        let (visible_width, visible_height) = view.visible_size;
+
+        for i in scroll_y..scroll_y + visible_height {
+            console.set_cursor_pos(view.x_offset, i - scroll_y + view.y_offset);
+
+            for j in scroll_x..scroll_x + visible_width {
+                if let Some(tile) = self.get_tile(j, i) {
+                    let inverted = marked_area.is_some_and(|area| area.contains((j, i)));
+
+                    let mut style = tile.style(theme, is_player_background, deadlocked_boxes.contains(&(j, i)));
+                    style.draw(console, inverted);
+
+                    if view.tile_width == 2 {
+                        //The second column continues walls and floors, all other tiles are followed by a space
+                        style.glyph = match tile {
+                            Tile::Wall | Tile::Empty | Tile::DecorationBlank => style.glyph,
 
-        for i in 0..self.height {
-            console.set_cursor_pos(x_offset, i + y_offset);
+                            _ => ' ',
+                        };
 
-            for j in 0..self.width {
-                if let Some(tile) = tile_iter.next() {
-                    tile.draw(console, is_player_background, cursor_pos.is_some_and(|(x, y)| x == j && y == i));
+                        style.draw(console, inverted);
+                    }
                 }
             }
-
-            console.draw_text("\n");
         }
     }
 
@@ -337,70 +543,137 @@ impl Level {
     }
 }
 
-impl FromStr for Level {
-    type Err = LevelLoadingError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let lines = s.lines().collect::<Vec<_>>();
-        if lines.is_empty() {
-            return Err(LevelLoadingError::new("Level is invalid!"));
-        }
-
-        let line = lines.first().unwrap().trim();
-        if !line.starts_with("w: ") || !line.contains(", h: ") {
-            return Err(LevelLoadingError::new("Level is invalid!"));
-        }
-
-        let index = line.to_string().find(", h: ").unwrap();
-
-        let (width, height) = (&line[3..index], &line[index + 5..]);
-        let height = if let Ok(height) = usize::from_str(height) {
-            height
-        }else {
-            return Err(LevelLoadingError::new("Level is invalid!"));
+impl Level {
+    /// Parses a level from its size header ("w: <width>, h: <height>") and its rows
+    ///
+    /// `lines` contains the 1-based line number and the content of each line, the first line is the size header.
+    fn from_lines(lines: &[(usize, &str)]) -> Result<Self, LevelLoadingError> {
+        let Some(&(header_line_number, header)) = lines.first() else {
+            return Err(LevelLoadingError::new(LevelLoadingErrorKind::PrematureEof, "The level is missing"));
         };
-        let width = if let Ok(width) = usize::from_str(width) {
-            width
-        }else {
-            return Err(LevelLoadingError::new("Level is invalid!"));
+
+        let Some((width, height)) = Self::parse_size_header(header) else {
+            return Err(LevelLoadingError::new(
+                LevelLoadingErrorKind::BadHeader,
+                "The level size is invalid (Expected: \"w: <width>, h: <height>\")",
+            ).at(header_line_number, None, header));
         };
 
         if width == 0 || height == 0 {
-            return Err(LevelLoadingError::new("Level is invalid!"));
+            return Err(LevelLoadingError::new(
+                LevelLoadingErrorKind::BadHeader,
+                "The level width and height must be > 0",
+            ).at(header_line_number, None, header));
         }
 
         let mut tiles = Vec::with_capacity(width * height);
 
-        for line in lines.into_iter().
+        for (y, &(line_number, line)) in lines.iter().
                 skip(1).
-                map(|line| line.trim()) {
-            if line.len() != width {
-                return Err(LevelLoadingError::new("Level is invalid!"));
+                enumerate() {
+            if y == height {
+                return Err(LevelLoadingError::new(
+                    LevelLoadingErrorKind::TrailingData,
+                    format!("The level has more rows than its height ({height})"),
+                ).at(line_number, None, line));
+            }
+
+            //Columns are counted in the untrimmed line
+            let indentation = line.len() - line.trim_start().len();
+            let row = line.trim();
+
+            for (x, tile) in row.bytes().
+                    enumerate() {
+                if x == width {
+                    return Err(LevelLoadingError::new(
+                        LevelLoadingErrorKind::WidthMismatch,
+                        format!("The row is longer than the level width ({width})"),
+                    ).at(line_number, Some(indentation + x + 1), line));
+                }
+
+                tiles.push(Tile::from_ascii(tile).map_err(|err| err.at(line_number, Some(indentation + x + 1), line))?);
             }
 
-            for tile in line.bytes() {
-                tiles.push(Tile::from_ascii(tile)?);
+            if row.len() < width {
+                return Err(LevelLoadingError::new(
+                    LevelLoadingErrorKind::WidthMismatch,
+                    format!("The row is shorter than the level width ({width})"),
+                ).at(line_number, Some(indentation + row.len() + 1), line));
             }
         }
 
         if tiles.len() != width * height {
-            return Err(LevelLoadingError::new("Level is invalid!"));
+            let &(line_number, line) = lines.last().unwrap();
+
+            return Err(LevelLoadingError::new(
+                LevelLoadingErrorKind::PrematureEof,
+                format!("The level has fewer rows than its height ({height})"),
+            ).at(line_number, None, line));
         }
 
         Ok(Self { width, height, tiles })
     }
+
+    /// Parses "w: <width>, h: <height>"
+    fn parse_size_header(line: &str) -> Option<(usize, usize)> {
+        let (width, height) = line.trim().
+                strip_prefix("w: ")?.
+                split_once(", h: ")?;
+
+        Some((usize::from_str(width).ok()?, usize::from_str(height).ok()?))
+    }
+}
+
+impl FromStr for Level {
+    type Err = LevelLoadingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lines = s.lines().
+                enumerate().
+                map(|(i, line)| (i + 1, line)).
+                collect::<Vec<_>>();
+
+        Self::from_lines(&lines)
+    }
 }
 
 #[derive(Debug)]
 pub struct LevelWithStats {
     level: Level,
     best_time: Option<u64>,
-    best_moves: Option<u32>
+    best_moves: Option<u32>,
+    best_pushes: Option<u32>,
+    /// Fewest actions (Moves including undone and redone moves) of a completion in strict mode
+    best_actions: Option<u32>,
+
+    /// Count of hints which were used to achieve the best time
+    best_time_hints: u32,
+    /// Count of hints which were used to achieve the best moves
+    best_moves_hints: u32,
+    /// Count of hints which were used to achieve the best pushes
+    best_pushes_hints: u32,
+    /// Count of hints which were used to achieve the best actions
+    best_actions_hints: u32,
+
+    /// LURD move string of the solution with the fewest moves
+    best_solution: Option<String>,
+
+    title: Option<String>,
+
+    /// 1-based line numbers of the rows in the level pack file (Empty if the level was not loaded from a file)
+    ///
+    /// The line numbers are not updated if the level is changed in the editor.
+    line_numbers: Vec<usize>,
 }
 
 impl LevelWithStats {
+    pub const MAX_TITLE_LEN: usize = 32;
+
     pub fn new(level: Level, best_time: Option<u64>, best_moves: Option<u32>) -> Self {
-        Self { level, best_time, best_moves }
+        Self {
+            level, best_time, best_moves, best_pushes: None, best_actions: None,
+            best_time_hints: 0, best_moves_hints: 0, best_pushes_hints: 0, best_actions_hints: 0, best_solution: None, title: None, line_numbers: Vec::new(),
+        }
     }
 
     pub fn level(&self) -> &Level {
@@ -418,6 +691,167 @@ impl LevelWithStats {
     pub fn best_moves(&self) -> Option<u32> {
         self.best_moves
     }
+
+    pub fn best_pushes(&self) -> Option<u32> {
+        self.best_pushes
+    }
+
+    pub fn best_actions(&self) -> Option<u32> {
+        self.best_actions
+    }
+
+    pub fn best_time_hints(&self) -> u32 {
+        self.best_time_hints
+    }
+
+    pub fn best_moves_hints(&self) -> u32 {
+        self.best_moves_hints
+    }
+
+    pub fn best_pushes_hints(&self) -> u32 {
+        self.best_pushes_hints
+    }
+
+    pub fn best_actions_hints(&self) -> u32 {
+        self.best_actions_hints
+    }
+
+    pub fn best_solution(&self) -> Option<&str> {
+        self.best_solution.as_deref()
+    }
+
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    pub fn set_title(&mut self, title: Option<String>) {
+        self.title = title;
+    }
+
+    pub fn line_numbers(&self) -> &[usize] {
+        &self.line_numbers
+    }
+
+    /// Reads the best time and moves from a line of a save game, all scores are cleared first
+    ///
+    /// Format: "ms<time>,<moves>" (-1 = not completed), the time is in seconds if the "ms" prefix is missing (Old format)
+    fn read_save_game_stats(&mut self, mut line: &str) {
+        self.best_time = None;
+        self.best_moves = None;
+        self.best_pushes = None;
+        self.best_actions = None;
+        self.best_time_hints = 0;
+        self.best_moves_hints = 0;
+        self.best_pushes_hints = 0;
+        self.best_actions_hints = 0;
+
+        let is_new_format = line.starts_with("ms");
+        if is_new_format {
+            line = &line[2..];
+        }
+
+        let tokens = line.split(",").collect::<Vec<_>>();
+        if tokens.len() != 2 {
+            return;
+        }
+
+        self.best_time = u64::from_str(tokens[0]).ok().map(|best_time| {
+            if is_new_format {
+                best_time
+            }else {
+                best_time * 1000 + 999
+            }
+        });
+        self.best_moves = u32::from_str(tokens[1]).ok();
+    }
+
+    /// Returns the line of the best time and moves in the save game (See [LevelWithStats::read_save_game_stats])
+    fn save_game_stats(&self) -> String {
+        format!(
+            "ms{},{}",
+            self.best_time.map_or(-1, |best_time| best_time as i64),
+            self.best_moves.map_or(-1, |best_moves| best_moves as i32),
+        )
+    }
+
+    /// Reads the hints, the best pushes and the best actions from a line of the stats file, invalid lines are ignored
+    ///
+    /// Format: "<time hints>,<moves hints>,<pushes>,<pushes hints>,<actions>,<actions hints>" (-1 = not completed)
+    fn read_save_game_extra_stats(&mut self, line: &str) {
+        let tokens = line.split(",").collect::<Vec<_>>();
+        if tokens.len() != 6 {
+            return;
+        }
+
+        self.best_time_hints = u32::from_str(tokens[0]).unwrap_or_default();
+        self.best_moves_hints = u32::from_str(tokens[1]).unwrap_or_default();
+        self.best_pushes = u32::from_str(tokens[2]).ok();
+        self.best_pushes_hints = u32::from_str(tokens[3]).unwrap_or_default();
+        self.best_actions = u32::from_str(tokens[4]).ok();
+        self.best_actions_hints = u32::from_str(tokens[5]).unwrap_or_default();
+    }
+
+    /// Returns the line of the stats file (See [LevelWithStats::read_save_game_extra_stats]) or None if there are no
+    /// hints, pushes and actions
+    fn save_game_extra_stats(&self) -> Option<String> {
+        if self.best_time_hints == 0 && self.best_moves_hints == 0 && self.best_pushes.is_none() && self.best_actions.is_none() {
+            return None;
+        }
+
+        Some(format!(
+            "{},{},{},{},{},{}",
+            self.best_time_hints,
+            self.best_moves_hints,
+            self.best_pushes.map_or(-1, |best_pushes| best_pushes as i32),
+            self.best_pushes_hints,
+            self.best_actions.map_or(-1, |best_actions| best_actions as i32),
+            self.best_actions_hints,
+        ))
+    }
+}
+
+/// Optional header fields of level pack files
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LevelPackMetadataField {
+    Name,
+    Author,
+    Description,
+    Version,
+}
+
+impl LevelPackMetadataField {
+    pub const ALL: [LevelPackMetadataField; 4] = [
+        LevelPackMetadataField::Name,
+        LevelPackMetadataField::Author,
+        LevelPackMetadataField::Description,
+        LevelPackMetadataField::Version,
+    ];
+
+    pub fn key(self) -> &'static str {
+        match self {
+            LevelPackMetadataField::Name => "Name",
+            LevelPackMetadataField::Author => "Author",
+            LevelPackMetadataField::Description => "Description",
+            LevelPackMetadataField::Version => "Version",
+        }
+    }
+
+    /// Max count of characters (The values must fit into the level pack selection screen)
+    pub fn max_len(self) -> usize {
+        match self {
+            LevelPackMetadataField::Name | LevelPackMetadataField::Author => 24,
+            LevelPackMetadataField::Description => 58,
+            LevelPackMetadataField::Version => 16,
+        }
+    }
+
+    /// Parses a header line (e.g. "Name: Level pack"), returns None if the line is not a header line of this field
+    fn parse_line(self, line: &str) -> Option<&str> {
+        line.trim().
+                strip_prefix(self.key()).
+                and_then(|value| value.strip_prefix(":")).
+                map(|value| value.trim())
+    }
 }
 
 #[derive(Debug)]
@@ -426,10 +860,16 @@ pub struct LevelPack {
     path: String,
     levels: Vec<LevelWithStats>,
 
+    name: Option<String>,
+    author: Option<String>,
+    description: Option<String>,
+    version: Option<String>,
+
     min_level_not_completed: usize,
 
     level_pack_best_time_sum: Option<u64>,
     level_pack_best_moves_sum: Option<u32>,
+    level_pack_best_pushes_sum: Option<u32>,
 }
 
 impl LevelPack {
@@ -442,117 +882,184 @@ impl LevelPack {
             path: path.into(),
             levels: vec![],
 
+            name: Default::default(),
+            author: Default::default(),
+            description: Default::default(),
+            version: Default::default(),
+
             min_level_not_completed: Default::default(),
             level_pack_best_time_sum: Default::default(),
             level_pack_best_moves_sum: Default::default(),
+            level_pack_best_pushes_sum: Default::default(),
         }
     }
 
-    pub fn read_from_save_game(id: impl Into<String>, path: impl Into<String>, lvl_data: impl Into<String>) -> Result<Self, Box<dyn Error>> {
+    /// Parses a level pack without reading its save game
+    ///
+    /// The level count can be followed by optional metadata lines (e.g. "Name: Level pack") and each level can be
+    /// preceded by an optional "Title: " line.
+    pub fn read_from_str(id: impl Into<String>, path: impl Into<String>, lvl_data: impl Into<String>) -> Result<Self, LevelLoadingError> {
         let id = id.into();
         let path = path.into();
         let lvl_data = lvl_data.into();
 
+        let mut level_pack = Self::new(id, path);
+        level_pack.parse_lvl_data(&lvl_data).map_err(|err| err.with_path(&level_pack.path))?;
+
+        Ok(level_pack)
+    }
+
+    /// Parses the levels and the metadata of the level pack file (The path is not added to errors)
+    fn parse_lvl_data(&mut self, lvl_data: &str) -> Result<(), LevelLoadingError> {
         let mut levels = Vec::with_capacity(Self::MAX_LEVEL_COUNT_PER_PACK);
-        {
-            let lines = lvl_data.lines().collect::<Vec<_>>();
-            if lines.is_empty() {
-                return Err(Box::new(LevelLoadingError::new(format!(
-                    "The level pack file \"{path}\" is empty!"
-                ))));
+        let mut metadata: Vec<(LevelPackMetadataField, String)> = Vec::new();
+
+        //Items: (1-based line number, line)
+        let mut line_iter = lvl_data.lines().
+                enumerate().
+                map(|(i, line)| (i + 1, line)).
+                filter(|(_, line)| !line.trim().is_empty()).
+                peekable();
+
+        let Some((line_number, line)) = line_iter.next() else {
+            return Err(LevelLoadingError::new(LevelLoadingErrorKind::PrematureEof, "The level pack file is empty"));
+        };
+
+        let Some(level_count) = line.trim().strip_prefix("Levels: ") else {
+            return Err(LevelLoadingError::new(
+                LevelLoadingErrorKind::BadHeader,
+                "The level count is missing (Expected: \"Levels: <count>\")",
+            ).at(line_number, None, line));
+        };
+
+        let Ok(level_count) = usize::from_str(level_count) else {
+            return Err(LevelLoadingError::new(
+                LevelLoadingErrorKind::BadHeader,
+                format!("The level count \"{level_count}\" is invalid"),
+            ).at(line_number, None, line));
+        };
+
+        if level_count > Self::MAX_LEVEL_COUNT_PER_PACK {
+            return Err(LevelLoadingError::new(
+                LevelLoadingErrorKind::TooManyLevels,
+                format!("There are too many levels (Count: {level_count}, Max: {})", Self::MAX_LEVEL_COUNT_PER_PACK),
+            ).at(line_number, None, line));
+        }
+
+        while let Some((line_number, line, field, value)) = line_iter.peek().and_then(|&(line_number, line)| LevelPackMetadataField::ALL.into_iter().
+                find_map(|field| field.parse_line(line).map(|value| (line_number, line, field, value)))) {
+            line_iter.next();
+
+            if metadata.iter().any(|(field_defined, _)| *field_defined == field) {
+                return Err(LevelLoadingError::new(
+                    LevelLoadingErrorKind::BadHeader,
+                    format!("The metadata \"{}\" is defined multiple times", field.key()),
+                ).at(line_number, None, line));
             }
 
-            let line = lines.first().unwrap().trim();
-            if !line.starts_with("Levels: ") {
-                return Err(Box::new(LevelLoadingError::new(format!(
-                    "The level count is missing in the level pack file \"{path}\"!"
-                ))));
+            if value.chars().count() > field.max_len() {
+                return Err(LevelLoadingError::new(
+                    LevelLoadingErrorKind::BadHeader,
+                    format!("The metadata \"{}\" is too long (Max: {} characters)", field.key(), field.max_len()),
+                ).at(line_number, None, line));
             }
 
-            let line = &line[8..];
+            if !value.is_empty() {
+                metadata.push((field, value.to_string()));
+            }
+        }
 
-            let level_count = if let Ok(level_count) = usize::from_str(line) {
-                if level_count > Self::MAX_LEVEL_COUNT_PER_PACK {
-                    return Err(Box::new(LevelLoadingError::new(format!(
-                        "There are too many levels in the level pack file \"{path}\" (Count: {line}, Max: {})!",
-                        Self::MAX_LEVEL_COUNT_PER_PACK
-                    ))));
-                }else {
-                    level_count
-                }
-            }else {
-                return Err(Box::new(LevelLoadingError::new(format!(
-                    "The level count \"{line}\" is invalid in the level pack file \"{path}\"!"
-                ))));
+        for i in 0..level_count {
+            let Some((mut line_number, mut line)) = line_iter.next() else {
+                return Err(LevelLoadingError::new(
+                    LevelLoadingErrorKind::PrematureEof,
+                    format!("The level is missing (Read: {i} levels, Expected: {level_count} levels)"),
+                ).with_level(i + 1));
             };
 
-            let mut line_iter = lines.into_iter().
-                    skip(1).
-                    filter(|line| !line.trim().is_empty());
-            for i in 0..level_count {
-                let line = line_iter.next();
-                let Some(line) = line else {
-                    return Err(Box::new(LevelLoadingError::new(format!(
-                        "EOF was reached early in the level pack file \"{path}\" (Read: {} levels, Expected: {level_count} levels)!",
-                        i + 1
-                    ))));
-                };
+            let mut title = None;
+            if let Some(value) = line.trim().strip_prefix("Title:").map(|value| value.trim()) {
+                if value.chars().count() > LevelWithStats::MAX_TITLE_LEN {
+                    return Err(LevelLoadingError::new(
+                        LevelLoadingErrorKind::BadHeader,
+                        format!("The title is too long (Max: {} characters)", LevelWithStats::MAX_TITLE_LEN),
+                    ).at(line_number, None, line).with_level(i + 1));
+                }
 
-                if !line.starts_with("w: ") || !line.contains(", h: ") {
-                    return Err(Box::new(LevelLoadingError::new(format!(
-                        "Level {} is invalid in the level pack file \"{path}\"!",
-                        i + 1
-                    ))));
+                if !value.is_empty() {
+                    title = Some(value.to_string());
                 }
 
-                let index = line.to_string().find(", h: ").unwrap() + 5;
-                let height = if let Ok(height) = usize::from_str(&line[index..]) {
-                    height
-                }else {
-                    return Err(Box::new(LevelLoadingError::new(format!(
-                        "Level {} is invalid in the level pack file \"{path}\"!",
-                        i + 1
-                    ))));
+                let Some(next_line) = line_iter.next() else {
+                    return Err(LevelLoadingError::new(
+                        LevelLoadingErrorKind::PrematureEof,
+                        "The level size is missing",
+                    ).at(line_number, None, line).with_level(i + 1));
                 };
+                (line_number, line) = next_line;
+            }
 
-                let mut level_str = Vec::with_capacity(1 + height);
-                level_str.push(line);
-                for _ in 0..height {
-                    if let Some(line) = line_iter.next() {
-                        level_str.push(line);
-                    }else {
-                        return Err(Box::new(LevelLoadingError::new(format!(
-                            "EOF was reached early during parsing of level {} is invalid in the level pack file \"{path}\"!",
-                            i + 1
-                        ))));
-                    }
-                }
+            let Some((_, height)) = Level::parse_size_header(line) else {
+                return Err(LevelLoadingError::new(
+                    LevelLoadingErrorKind::BadHeader,
+                    "The level size is invalid (Expected: \"w: <width>, h: <height>\")",
+                ).at(line_number, None, line).with_level(i + 1));
+            };
 
-                let level = Level::from_str(&level_str.join("\n"));
-                match level {
-                    Ok(level) => levels.push(level),
-                    Err(err) => {
-                        return Err(Box::new(LevelLoadingError::new(format!(
-                            "\"{}\" occurred during parsing of level {} is invalid in the level pack file \"{path}\"!",
-                            err, i + 1
-                        ))));
-                    },
-                }
-            }
+            let mut level_lines = Vec::with_capacity(1 + height);
+            level_lines.push((line_number, line));
+            level_lines.extend(line_iter.by_ref().take(height));
 
-            if line_iter.next().is_some() {
-                return Err(Box::new(LevelLoadingError::new(format!(
-                    "Additional data was found after last level was parsed in the level pack file \"{path}\"!"
-                ))));
-            }
+            let level = Level::from_lines(&level_lines).map_err(|err| err.with_level(i + 1))?;
+
+            let mut level = LevelWithStats::new(level, None, None);
+            level.title = title;
+            level.line_numbers = level_lines.iter().
+                    skip(1).
+                    map(|(line_number, _)| *line_number).
+                    collect();
+
+            levels.push(level);
+        }
+
+        if let Some((line_number, line)) = line_iter.next() {
+            return Err(LevelLoadingError::new(
+                LevelLoadingErrorKind::TrailingData,
+                "Additional data was found after the last level",
+            ).at(line_number, None, line));
         }
 
+        self.levels = levels;
+        for (field, value) in metadata {
+            self.set_metadata(field, Some(value));
+        }
+
+        Ok(())
+    }
+
+    pub fn read_from_save_game(id: impl Into<String>, path: impl Into<String>, lvl_data: impl Into<String>) -> Result<Self, Box<dyn Error>> {
+        let mut level_pack = Self::read_from_str(id, path, lvl_data)?;
+        level_pack.read_save_game()?;
+
+        Ok(level_pack)
+    }
+
+    pub fn read_xsb_from_save_game(id: impl Into<String>, path: impl Into<String>, xsb_data: impl Into<String>) -> Result<Self, Box<dyn Error>> {
+        let mut level_pack = Self::read_from_xsb_str(id, path, xsb_data)?;
+        level_pack.read_save_game()?;
+
+        Ok(level_pack)
+    }
+
+    fn read_save_game(&mut self) -> Result<(), Box<dyn Error>> {
+        let level_pack = self;
+
         let mut save_game_file = Game::get_or_create_save_game_folder()?;
-        save_game_file.push(&id);
+        save_game_file.push(&level_pack.id);
         save_game_file.push(".lvl.sav");
 
         let mut min_level_not_completed= Default::default();
-        let mut level_stats: Vec<(Option<u64>, Option<u32>)> = vec![Default::default(); Self::MAX_LEVEL_COUNT_PER_PACK];
+        let mut level_stats_lines = Vec::new();
         'read_save_game: {
             if std::fs::exists(&save_game_file)? {
                 let save_game_data = std::fs::read_to_string(&save_game_file)?;
@@ -574,53 +1081,73 @@ impl LevelPack {
                     break 'read_save_game;
                 };
 
-                for (i, mut line) in lines.iter().
+                level_stats_lines = lines.iter().
                         skip(1).
                         take(Self::MAX_LEVEL_COUNT_PER_PACK).
-                        map(|line| line.trim()).
-                        enumerate() {
-                    let is_new_format = line.starts_with("ms");
-                    if is_new_format {
-                        line = &line[2..];
-                    }
+                        map(|line| line.trim().to_string()).
+                        collect();
+            }
+        }
 
-                    let tokens = line.split(",").collect::<Vec<_>>();
-                    if tokens.len() != 2 {
-                        continue;
-                    }
+        for (i, level) in level_pack.levels.iter_mut().
+                enumerate() {
+            level.read_save_game_stats(level_stats_lines.get(i).map_or("", |line| line));
+        }
+
+        //The hints and pushes are stored in a separate file, because older versions ignore lines of the save game
+        //which contain more than the time and the moves
+        let mut stats_file = Game::get_or_create_save_game_folder()?;
+        stats_file.push(&level_pack.id);
+        stats_file.push(".lvl.stat");
 
-                    let best_time = u64::from_str(tokens[0]).ok().map(|best_time| {
-                        if is_new_format {
-                            best_time
-                        }else {
-                            best_time * 1000 + 999
-                        }
-                    });
-                    let best_moves = u32::from_str(tokens[1]).ok();
+        if std::fs::exists(&stats_file)? {
+            let stats_data = std::fs::read_to_string(&stats_file)?;
 
-                    level_stats[i] = (best_time, best_moves);
+            //Format: "<level number>,<stats>"
+            for line in stats_data.lines().
+                    map(|line| line.trim()) {
+                let Some((level_number, stats)) = line.split_once(",") else {
+                    continue;
+                };
+
+                if let Some(level) = usize::from_str(level_number).ok().
+                        and_then(|level_number| level_number.checked_sub(1)).
+                        and_then(|index| level_pack.levels.get_mut(index)) {
+                    level.read_save_game_extra_stats(stats);
                 }
             }
         }
 
-        let levels = levels.into_iter().
-                zip(level_stats).
-                map(|(level, (best_time, best_moves))| {
-                    LevelWithStats::new(level, best_time, best_moves)
-                }).collect::<Vec<_>>();
+        let mut solution_file = Game::get_or_create_save_game_folder()?;
+        solution_file.push(&level_pack.id);
+        solution_file.push(".lvl.sol");
 
-        let mut level_pack = Self {
-            id,
-            path,
-            levels,
+        if std::fs::exists(&solution_file)? {
+            let solution_data = std::fs::read_to_string(&solution_file)?;
 
-            min_level_not_completed,
-            level_pack_best_time_sum: Default::default(),
-            level_pack_best_moves_sum: Default::default(),
-        };
+            //Format: "<level number>,<LURD move string>"
+            for line in solution_data.lines().
+                    map(|line| line.trim()) {
+                let Some((level_number, solution)) = line.split_once(",") else {
+                    continue;
+                };
+
+                if solution.is_empty() || !solution.chars().all(|c| Direction::from_lurd(c).is_some()) {
+                    continue;
+                }
+
+                if let Some(level) = usize::from_str(level_number).ok().
+                        and_then(|level_number| level_number.checked_sub(1)).
+                        and_then(|index| level_pack.levels.get_mut(index)) {
+                    level.best_solution = Some(solution.to_string());
+                }
+            }
+        }
+
+        level_pack.min_level_not_completed = min_level_not_completed;
         level_pack.calculate_stats_sum();
 
-        Ok(level_pack)
+        Ok(())
     }
 
     pub fn save_editor_level_pack(&self) -> Result<(), Box<dyn Error>> {
@@ -630,17 +1157,35 @@ impl LevelPack {
     pub fn save_editor_level_pack_to_path(&self, path: impl Into<String>) -> Result<(), Box<dyn Error>> {
         let mut file = File::create(path.into())?;
 
-        writeln!(file, "Levels: {}", self.levels.len())?;
-
-        for level in self.levels.iter().
-                map(|level| level.level()) {
-            write!(file, "\n{}", level.to_str())?;
-        }
+        file.write_all(self.to_str().as_bytes())?;
         file.flush()?;
 
         Ok(())
     }
 
+    pub fn to_str(&self) -> String {
+        let mut out = String::new();
+
+        let _ = writeln!(out, "Levels: {}", self.levels.len());
+        for field in LevelPackMetadataField::ALL {
+            if let Some(value) = self.metadata(field) {
+                let _ = writeln!(out, "{}: {}", field.key(), value);
+            }
+        }
+
+        for level in self.levels.iter() {
+            out += "\n";
+
+            if let Some(title) = &level.title {
+                let _ = writeln!(out, "Title: {}", title);
+            }
+
+            out += &level.level().to_str();
+        }
+
+        out
+    }
+
     pub fn save_save_game(&self) -> Result<(), Box<dyn Error>> {
         let mut save_game_file = Game::get_or_create_save_game_folder()?;
         save_game_file.push(&self.id);
@@ -652,14 +1197,48 @@ impl LevelPack {
 
         for level in self.levels.iter().
                 take(self.min_level_not_completed) {
-            writeln!(
-                file, "ms{},{}",
-                level.best_time.map_or(-1, |best_time| best_time as i64),
-                level.best_moves.map_or(-1, |best_moves| best_moves as i32)
-            )?;
+            writeln!(file, "{}", level.save_game_stats())?;
         }
         file.flush()?;
 
+        let extra_stats = self.levels.iter().
+                map(LevelWithStats::save_game_extra_stats).
+                collect::<Vec<_>>();
+        let mut stats_file = Game::get_or_create_save_game_folder()?;
+        stats_file.push(&self.id);
+        stats_file.push(".lvl.stat");
+        if extra_stats.iter().any(Option::is_some) {
+            let mut file = File::create(stats_file)?;
+
+            for (i, stats) in extra_stats.iter().
+                    enumerate() {
+                if let Some(stats) = stats {
+                    writeln!(file, "{},{}", i + 1, stats)?;
+                }
+            }
+            file.flush()?;
+        }else if std::fs::exists(&stats_file)? {
+            //Remove stats of an older save game which would be read again otherwise
+            std::fs::remove_file(stats_file)?;
+        }
+
+        let mut solution_file = Game::get_or_create_save_game_folder()?;
+        solution_file.push(&self.id);
+        solution_file.push(".lvl.sol");
+        if self.levels.iter().any(|level| level.best_solution.is_some()) {
+            let mut file = File::create(solution_file)?;
+
+            for (i, level) in self.levels.iter().
+                    enumerate() {
+                if let Some(best_solution) = &level.best_solution {
+                    writeln!(file, "{},{}", i + 1, best_solution)?;
+                }
+            }
+            file.flush()?;
+        }else if std::fs::exists(&solution_file)? {
+            std::fs::remove_file(solution_file)?;
+        }
+
         Ok(())
     }
 
@@ -679,6 +1258,31 @@ impl LevelPack {
         &mut self.levels
     }
 
+    pub fn metadata(&self, field: LevelPackMetadataField) -> Option<&str> {
+        match field {
+            LevelPackMetadataField::Name => self.name.as_deref(),
+            LevelPackMetadataField::Author => self.author.as_deref(),
+            LevelPackMetadataField::Description => self.description.as_deref(),
+            LevelPackMetadataField::Version => self.version.as_deref(),
+        }
+    }
+
+    pub fn set_metadata(&mut self, field: LevelPackMetadataField, value: Option<String>) {
+        let metadata = match field {
+            LevelPackMetadataField::Name => &mut self.name,
+            LevelPackMetadataField::Author => &mut self.author,
+            LevelPackMetadataField::Description => &mut self.description,
+            LevelPackMetadataField::Version => &mut self.version,
+        };
+
+        *metadata = value;
+    }
+
+    /// Returns the name of the level pack or the ID if no name is set
+    pub fn display_name(&self) -> &str {
+        self.name.as_deref().unwrap_or(&self.id)
+    }
+
     pub fn min_level_not_completed(&self) -> usize {
         self.min_level_not_completed
     }
@@ -691,6 +1295,10 @@ impl LevelPack {
         self.level_pack_best_moves_sum
     }
 
+    pub fn level_pack_best_pushes_sum(&self) -> Option<u32> {
+        self.level_pack_best_pushes_sum
+    }
+
     pub fn set_min_level_not_completed(&mut self, min_level_not_completed: usize) {
         self.min_level_not_completed = min_level_not_completed;
     }
@@ -699,26 +1307,69 @@ impl LevelPack {
         self.levels.len()
     }
 
-    pub fn update_stats(&mut self, index: usize, best_time: u64, best_moves: u32) -> Option<()> {
+    /// Updates the best scores of a level, an equal score replaces the best score if fewer hints were used
+    ///
+    /// `best_actions` is only set if the level was completed in strict mode.
+    pub fn update_stats(
+        &mut self, index: usize, best_time: u64, best_moves: u32, best_pushes: u32, best_actions: Option<u32>, hints_used: u32,
+    ) -> Option<()> {
         let level = self.levels.get_mut(index)?;
 
-        level.best_time = if level.best_time.is_none_or(|level_best_time| best_time < level_best_time) {
-            Some(best_time)
-        }else {
-            level.best_time
-        };
+        if level.best_time.is_none_or(|level_best_time| best_time < level_best_time ||
+                (best_time == level_best_time && hints_used < level.best_time_hints)) {
+            level.best_time = Some(best_time);
+            level.best_time_hints = hints_used;
+        }
 
-        level.best_moves = if level.best_moves.is_none_or(|level_best_moves| best_moves < level_best_moves) {
-            Some(best_moves)
-        }else {
-            level.best_moves
-        };
+        if level.best_moves.is_none_or(|level_best_moves| best_moves < level_best_moves ||
+                (best_moves == level_best_moves && hints_used < level.best_moves_hints)) {
+            level.best_moves = Some(best_moves);
+            level.best_moves_hints = hints_used;
+        }
+
+        if level.best_pushes.is_none_or(|level_best_pushes| best_pushes < level_best_pushes ||
+                (best_pushes == level_best_pushes && hints_used < level.best_pushes_hints)) {
+            level.best_pushes = Some(best_pushes);
+            level.best_pushes_hints = hints_used;
+        }
+
+        if let Some(best_actions) = best_actions {
+            if level.best_actions.is_none_or(|level_best_actions| best_actions < level_best_actions ||
+                    (best_actions == level_best_actions && hints_used < level.best_actions_hints)) {
+                level.best_actions = Some(best_actions);
+                level.best_actions_hints = hints_used;
+            }
+        }
 
         self.calculate_stats_sum();
 
         Some(())
     }
 
+    /// Stores the LURD move string if it is better than the best solution
+    ///
+    /// Solutions are compared by the count of moves and then by the count of pushes (Or the other way around if `ranking`
+    /// is [Ranking::Pushes]).
+    pub fn update_best_solution(&mut self, index: usize, solution: &str, ranking: Ranking) -> Option<()> {
+        let level = self.levels.get_mut(index)?;
+
+        let score = |solution: &str| {
+            let moves = solution.len();
+            let pushes = solution.chars().filter(|c| c.is_ascii_uppercase()).count();
+
+            match ranking {
+                Ranking::Moves => (moves, pushes),
+                Ranking::Pushes => (pushes, moves),
+            }
+        };
+
+        if level.best_solution.as_deref().is_none_or(|best_solution| score(solution) < score(best_solution)) {
+            level.best_solution = Some(solution.to_string());
+        }
+
+        Some(())
+    }
+
     pub fn add_level(&mut self, level: Level) {
         self.levels.push(LevelWithStats::new(level, None, None));
 
@@ -729,12 +1380,13 @@ impl LevelPack {
         if self.levels.is_empty() {
             self.level_pack_best_time_sum = None;
             self.level_pack_best_moves_sum = None;
+            self.level_pack_best_pushes_sum = None;
 
             return;
         }
 
         let stats_sum = self.levels.iter().
-                fold((Some(0), Some(0)), |mut sum, current| {
+                fold((Some(0), Some(0), Some(0)), |mut sum, current| {
                     sum.0 = if let Some(best_time) = current.best_time {
                         sum.0.map(|sum| sum + best_time)
                     }else {
@@ -747,28 +1399,165 @@ impl LevelPack {
                         None
                     };
 
+                    sum.2 = if let Some(best_pushes) = current.best_pushes {
+                        sum.2.map(|sum| sum + best_pushes)
+                    }else {
+                        None
+                    };
+
                     sum
                 });
 
         self.level_pack_best_time_sum = stats_sum.0;
         self.level_pack_best_moves_sum = stats_sum.1;
+        self.level_pack_best_pushes_sum = stats_sum.2;
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LevelLoadingErrorKind {
+    /// The level count, the metadata, a level title or a level size is invalid
+    BadHeader,
+    WidthMismatch,
+    InvalidTile,
+    InvalidRunLength,
+    PrematureEof,
+    TrailingData,
+    TooManyLevels,
+}
+
+impl Display for LevelLoadingErrorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            LevelLoadingErrorKind::BadHeader => "Bad header",
+            LevelLoadingErrorKind::WidthMismatch => "Width mismatch",
+            LevelLoadingErrorKind::InvalidTile => "Invalid tile",
+            LevelLoadingErrorKind::InvalidRunLength => "Invalid run length",
+            LevelLoadingErrorKind::PrematureEof => "Premature end of file",
+            LevelLoadingErrorKind::TrailingData => "Trailing data",
+            LevelLoadingErrorKind::TooManyLevels => "Too many levels",
+        })
     }
 }
 
+#[derive(Debug)]
+struct SourceLocation {
+    /// 1-based line number
+    line: usize,
+    /// 1-based column (In bytes)
+    column: Option<usize>,
+    /// The byte at the column
+    byte: Option<u8>,
+    /// The content of the line (Used for the snippet)
+    source_line: String,
+}
+
 #[derive(Debug)]
 pub struct LevelLoadingError {
-    message: String
+    kind: LevelLoadingErrorKind,
+    message: String,
+
+    path: Option<String>,
+    /// 1-based level number
+    level: Option<usize>,
+
+    location: Option<Box<SourceLocation>>,
 }
 
 impl LevelLoadingError {
-    fn new(message: impl Into<String>) -> Self {
-        Self { message: message.into() }
+    fn new(kind: LevelLoadingErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+
+            path: None,
+            level: None,
+
+            location: None,
+        }
+    }
+
+    fn at(mut self, line: usize, column: Option<usize>, source_line: &str) -> Self {
+        self.location = Some(Box::new(SourceLocation {
+            line,
+            column,
+            byte: column.and_then(|column| source_line.as_bytes().get(column - 1).copied()),
+            source_line: source_line.to_string(),
+        }));
+
+        self
+    }
+
+    fn with_path(mut self, path: impl Into<String>) -> Self {
+        self.path = Some(path.into());
+
+        self
+    }
+
+    fn with_level(mut self, level: usize) -> Self {
+        self.level = Some(level);
+
+        self
+    }
+
+    /// Returns the invalid line with a caret below the invalid column or None if the line is unknown
+    ///
+    /// Format:
+    /// ```text
+    ///  --> main.lvl:5:3
+    ///   |
+    /// 5 | #P%x#
+    ///   |   ^
+    /// ```
+    pub fn snippet(&self) -> Option<String> {
+        let SourceLocation { line, column, source_line, .. } = self.location.as_deref()?;
+
+        let indentation = " ".repeat(line.to_string().len());
+
+        let mut out = String::new();
+        let _ = write!(out, "{indentation}--> {}:{line}", self.path.as_deref().unwrap_or("<unknown>"));
+        if let Some(column) = column {
+            let _ = write!(out, ":{column}");
+        }
+        let _ = write!(out, "\n{indentation} |\n{line} | {source_line}");
+        if let Some(column) = column {
+            //The column is in bytes, but the caret must be below the character
+            let prefix = source_line.as_bytes().get(..column - 1).unwrap_or(source_line.as_bytes());
+            let _ = write!(out, "\n{indentation} | {}^", " ".repeat(String::from_utf8_lossy(prefix).chars().count()));
+        }
+
+        Some(out)
     }
 }
 
 impl Display for LevelLoadingError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        f.write_str(&self.message)
+        if let Some(level) = self.level {
+            write!(f, "Level {level}: ")?;
+        }
+
+        write!(f, "{}: {}", self.kind, self.message)?;
+
+        let mut location = Vec::new();
+        if let Some(path) = &self.path {
+            location.push(format!("File \"{path}\""));
+        }
+        if let Some(source_location) = &self.location {
+            location.push(format!("line {}", source_location.line));
+
+            if let Some(column) = source_location.column {
+                location.push(format!("column {column}"));
+            }
+            if let Some(byte) = source_location.byte {
+                location.push(format!("byte 0x{byte:02X}"));
+            }
+        }
+
+        if !location.is_empty() {
+            write!(f, " ({})", location.join(", "))?;
+        }
+
+        Ok(())
     }
 }
 
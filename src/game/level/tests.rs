@@ -0,0 +1,365 @@
+use std::str::FromStr;
+use crate::game::level::*;
+
+#[test]
+fn read_without_metadata() {
+    let level_pack = LevelPack::read_from_str("test", "test.lvl", "Levels: 1\n\nw: 3, h: 1\nP@x\n").unwrap();
+
+    assert_eq!(level_pack.level_count(), 1);
+    assert_eq!(level_pack.display_name(), "test");
+    for field in LevelPackMetadataField::ALL {
+        assert_eq!(level_pack.metadata(field), None);
+    }
+    assert_eq!(level_pack.levels()[0].title(), None);
+}
+
+#[test]
+fn read_metadata() {
+    let lvl = "Levels: 2\n\
+               Name: Test pack\n\
+               Author: Someone\n\
+               Version: 1.0\n\
+               \n\
+               Title: First level\n\
+               w: 3, h: 1\n\
+               P@x\n\
+               \n\
+               w: 3, h: 1\n\
+               x@P\n";
+
+    let level_pack = LevelPack::read_from_str("test", "test.lvl", lvl).unwrap();
+    assert_eq!(level_pack.level_count(), 2);
+    assert_eq!(level_pack.display_name(), "Test pack");
+    assert_eq!(level_pack.metadata(LevelPackMetadataField::Author), Some("Someone"));
+    assert_eq!(level_pack.metadata(LevelPackMetadataField::Description), None);
+    assert_eq!(level_pack.metadata(LevelPackMetadataField::Version), Some("1.0"));
+    assert_eq!(level_pack.levels()[0].title(), Some("First level"));
+    assert_eq!(level_pack.levels()[1].title(), None);
+}
+
+#[test]
+fn read_metadata_errors() {
+    assert!(LevelPack::read_from_str("test", "test.lvl", "Levels: 1\nName: A\nName: B\n\nw: 3, h: 1\nP@x\n").is_err());
+
+    let name = "n".repeat(LevelPackMetadataField::Name.max_len() + 1);
+    assert!(LevelPack::read_from_str("test", "test.lvl", format!("Levels: 1\nName: {name}\n\nw: 3, h: 1\nP@x\n")).is_err());
+
+    let title = "t".repeat(LevelWithStats::MAX_TITLE_LEN + 1);
+    assert!(LevelPack::read_from_str("test", "test.lvl", format!("Levels: 1\n\nTitle: {title}\nw: 3, h: 1\nP@x\n")).is_err());
+
+    //Metadata is only allowed before the first level
+    assert!(LevelPack::read_from_str("test", "test.lvl", "Levels: 1\n\nw: 3, h: 1\nP@x\nName: A\n").is_err());
+}
+
+#[test]
+fn write_metadata() {
+    let mut level_pack = LevelPack::new("test", "test.lvl.edit");
+    level_pack.add_level(Level::from_str("w: 3, h: 1\nP@x").unwrap());
+    level_pack.add_level(Level::from_str("w: 3, h: 1\nx@P").unwrap());
+
+    assert_eq!(level_pack.to_str(), "Levels: 2\n\nw: 3, h: 1\nP@x\n\nw: 3, h: 1\nx@P\n");
+
+    level_pack.set_metadata(LevelPackMetadataField::Description, Some("Two levels".to_string()));
+    level_pack.set_metadata(LevelPackMetadataField::Name, Some("Test pack".to_string()));
+    level_pack.levels_mut()[1].set_title(Some("Second level".to_string()));
+
+    let lvl = level_pack.to_str();
+    assert_eq!(lvl, "Levels: 2\nName: Test pack\nDescription: Two levels\n\nw: 3, h: 1\nP@x\n\nTitle: Second level\nw: 3, h: 1\nx@P\n");
+
+    let level_pack_read = LevelPack::read_from_str("test", "test.lvl", lvl).unwrap();
+    assert_eq!(level_pack_read.to_str(), level_pack.to_str());
+}
+
+fn read_error(lvl: &str) -> LevelLoadingError {
+    LevelPack::read_from_str("test", "test.lvl", lvl).unwrap_err()
+}
+
+fn error_location(err: &LevelLoadingError) -> Option<(usize, Option<usize>, Option<u8>)> {
+    err.location.as_ref().map(|location| (location.line, location.column, location.byte))
+}
+
+#[test]
+fn loading_error_kinds() {
+    let err = read_error("Level: 1\n\nw: 3, h: 1\nP@x\n");
+    assert_eq!(err.kind, LevelLoadingErrorKind::BadHeader);
+    assert_eq!(error_location(&err), Some((1, None, None)));
+
+    let err = read_error("Levels: 1\n\nw: 3, h: 1\nP@x-\n");
+    assert_eq!(err.kind, LevelLoadingErrorKind::WidthMismatch);
+    assert_eq!(err.level, Some(1));
+    assert_eq!(error_location(&err), Some((4, Some(4), Some(b'-'))));
+
+    let err = read_error("Levels: 1\n\nw: 3, h: 1\n  P%x\n");
+    assert_eq!(err.kind, LevelLoadingErrorKind::InvalidTile);
+    assert_eq!(error_location(&err), Some((4, Some(4), Some(b'%'))));
+
+    let err = read_error("Levels: 2\n\nw: 3, h: 2\nP@x\n");
+    assert_eq!(err.kind, LevelLoadingErrorKind::PrematureEof);
+    assert_eq!(error_location(&err), Some((4, None, None)));
+
+    let err = read_error("Levels: 2\n\nw: 3, h: 1\nP@x\n");
+    assert_eq!(err.kind, LevelLoadingErrorKind::PrematureEof);
+    assert_eq!(err.level, Some(2));
+
+    let err = read_error("Levels: 1\n\nw: 3, h: 1\nP@x\n\nP@x\n");
+    assert_eq!(err.kind, LevelLoadingErrorKind::TrailingData);
+    assert_eq!(error_location(&err), Some((6, None, None)));
+
+    let err = read_error(&format!("Levels: {}\n", LevelPack::MAX_LEVEL_COUNT_PER_PACK + 1));
+    assert_eq!(err.kind, LevelLoadingErrorKind::TooManyLevels);
+}
+
+#[test]
+fn loading_error_display() {
+    let err = read_error("Levels: 1\n\nw: 3, h: 1\nP%x\n");
+
+    assert_eq!(
+        err.to_string(),
+        "Level 1: Invalid tile: \"%\" is not a valid tile (File \"test.lvl\", line 4, column 2, byte 0x25)",
+    );
+    assert_eq!(err.snippet().unwrap(), " --> test.lvl:4:2\n  |\n4 | P%x\n  |  ^");
+
+    let err = read_error("");
+    assert_eq!(err.to_string(), "Premature end of file: The level pack file is empty (File \"test.lvl\")");
+    assert_eq!(err.snippet(), None);
+}
+
+#[test]
+fn loading_error_snippet_non_ascii() {
+    let err = LevelLoadingError::new(LevelLoadingErrorKind::BadHeader, "Invalid").
+            with_path("test.lvl").
+            at(2, Some(7), "Ä: ß!");
+
+    assert_eq!(err.snippet().unwrap(), " --> test.lvl:2:7\n  |\n2 | Ä: ß!\n  |     ^");
+}
+
+#[test]
+fn level_view() {
+    let level = Level::new(10, 3);
+
+    //Wide tiles are used if the level fits
+    let view = LevelView::new(&level, (30, 20), 1, true, (0, 0));
+    assert_eq!(view.tile_pos(5, 1), Some((0, 0)));
+    assert_eq!(view.tile_pos(6, 2), Some((0, 1)));
+    assert_eq!(view.tile_pos(24, 3), Some((9, 2)));
+    assert_eq!(view.tile_pos(4, 1), None);
+    assert_eq!(view.tile_pos(25, 1), None);
+    assert_eq!(view.tile_pos(5, 0), None);
+    assert_eq!(view.tile_pos(5, 4), None);
+
+    let view = LevelView::new(&level, (19, 20), 1, true, (0, 0));
+    assert_eq!(view.tile_pos(4, 1), Some((0, 0)));
+    assert_eq!(view.tile_pos(5, 1), Some((1, 0)));
+
+    let view = LevelView::new(&level, (30, 20), 1, false, (0, 0));
+    assert_eq!(view.tile_pos(10, 1), Some((0, 0)));
+    assert_eq!(view.tile_pos(19, 1), Some((9, 0)));
+    assert_eq!(view.console_pos((9, 0)), Some((19, 1)));
+    assert_eq!(view.console_pos((10, 0)), None);
+
+    //Wide tiles depend on the console width and not on the minimal console width
+    let level = Level::new(40, 10);
+    let view = LevelView::new(&level, (74, 22), 1, true, (0, 0));
+    assert_eq!(view.tile_width(), 1);
+    let view = LevelView::new(&level, (120, 34), 1, true, (0, 0));
+    assert_eq!(view.tile_width(), 2);
+    assert_eq!(view.tile_pos(20, 1), Some((0, 0)));
+    assert_eq!(view.tile_pos(99, 1), Some((39, 0)));
+
+    //Levels which do not fit are scrolled to the focus position
+    let level = Level::new(100, 50);
+    let view = LevelView::new(&level, (20, 10), 1, true, (50, 25));
+    assert_eq!(view.visible_height(), 10);
+    assert_eq!(view.tile_pos(0, 1), Some((40, 20)));
+    assert_eq!(view.tile_pos(19, 10), Some((59, 29)));
+    assert_eq!(view.tile_pos(20, 1), None);
+    assert_eq!(view.console_pos((41, 20)), Some((1, 1)));
+    assert_eq!(view.console_pos((39, 20)), None);
+
+    //The view does not scroll past the borders of the level
+    let view = LevelView::new(&level, (20, 10), 1, true, (2, 48));
+    assert_eq!(view.tile_pos(0, 1), Some((0, 40)));
+    let view = LevelView::new(&level, (20, 10), 1, true, (99, 0));
+    assert_eq!(view.tile_pos(19, 1), Some((99, 0)));
+}
+
+#[test]
+fn save_game_stats() {
+    let mut level = LevelWithStats::new(Level::new(1, 1), None, None);
+
+    fn round_trip(level: &mut LevelWithStats, line: &str) -> String {
+        level.read_save_game_stats(line);
+        level.save_game_stats()
+    }
+
+    assert_eq!(round_trip(&mut level, "ms-1,-1"), "ms-1,-1");
+    assert_eq!(round_trip(&mut level, "ms1234,56"), "ms1234,56");
+
+    //Old format in seconds
+    assert_eq!(round_trip(&mut level, "12,56"), "ms12999,56");
+
+    //Invalid lines clear the scores
+    assert_eq!(round_trip(&mut level, "ms1234,56,0"), "ms-1,-1");
+}
+
+#[test]
+fn save_game_extra_stats() {
+    let mut level = LevelWithStats::new(Level::new(1, 1), None, None);
+    assert_eq!(level.save_game_extra_stats(), None);
+
+    level.read_save_game_extra_stats("1,0,-1,0,-1,0");
+    assert_eq!(level.best_time_hints(), 1);
+    assert_eq!(level.best_pushes(), None);
+    assert_eq!(level.save_game_extra_stats().as_deref(), Some("1,0,-1,0,-1,0"));
+
+    level.read_save_game_extra_stats("0,0,12,2,40,1");
+    assert_eq!(level.best_pushes(), Some(12));
+    assert_eq!(level.best_pushes_hints(), 2);
+    assert_eq!(level.best_actions(), Some(40));
+    assert_eq!(level.best_actions_hints(), 1);
+    assert_eq!(level.save_game_extra_stats().as_deref(), Some("0,0,12,2,40,1"));
+
+    //Invalid lines are ignored
+    level.read_save_game_extra_stats("0,0,3,0");
+    assert_eq!(level.best_pushes(), Some(12));
+
+    //The save game line does not contain the extra stats, so that older versions can read it
+    level.read_save_game_stats("ms1234,56");
+    assert_eq!(level.best_pushes(), None);
+    assert_eq!(level.save_game_stats(), "ms1234,56");
+}
+
+#[test]
+fn best_solution_ranking() {
+    let mut level_pack = LevelPack::new("test", "test.lvl");
+    level_pack.add_level(Level::new(1, 1));
+
+    level_pack.update_best_solution(0, "rrRR", Ranking::Moves);
+    level_pack.update_best_solution(0, "RRR", Ranking::Moves);
+    assert_eq!(level_pack.levels()[0].best_solution(), Some("RRR"));
+
+    level_pack.update_best_solution(0, "rrrrR", Ranking::Pushes);
+    assert_eq!(level_pack.levels()[0].best_solution(), Some("rrrrR"));
+
+    //Equal primary scores are compared by the secondary score
+    level_pack.update_best_solution(0, "rrR", Ranking::Pushes);
+    assert_eq!(level_pack.levels()[0].best_solution(), Some("rrR"));
+
+    level_pack.update_stats(0, 1000, 3, 1, None, 0);
+    level_pack.update_stats(0, 2000, 2, 2, None, 0);
+    assert_eq!(level_pack.levels()[0].best_moves(), Some(2));
+    assert_eq!(level_pack.levels()[0].best_pushes(), Some(1));
+    assert_eq!(level_pack.levels()[0].best_actions(), None);
+    assert_eq!(level_pack.level_pack_best_pushes_sum(), Some(1));
+
+    //Strict mode scores do not replace the best moves
+    level_pack.update_stats(0, 3000, 3, 1, Some(7), 0);
+    level_pack.update_stats(0, 3000, 4, 1, Some(5), 0);
+    assert_eq!(level_pack.levels()[0].best_moves(), Some(2));
+    assert_eq!(level_pack.levels()[0].best_actions(), Some(5));
+}
+
+#[test]
+fn tile_area() {
+    let area = TileArea::from_corners((3, 1), (1, 2));
+    assert_eq!(area, TileArea::from_corners((1, 1), (3, 2)));
+    assert_eq!((area.width(), area.height()), (3, 2));
+    assert!(area.contains((1, 1)));
+    assert!(area.contains((3, 2)));
+    assert!(!area.contains((0, 1)));
+    assert!(!area.contains((1, 3)));
+    assert_eq!(area.positions().collect::<Vec<_>>(), [(1, 1), (2, 1), (3, 1), (1, 2), (2, 2), (3, 2)]);
+
+    assert_eq!(TileArea::from_pos((2, 2)).positions().collect::<Vec<_>>(), [(2, 2)]);
+}
+
+#[test]
+fn copy_fill_and_paste_area() {
+    let mut level = Level::from_str("w: 4, h: 3\n####\n#P@#\n#x-#").unwrap();
+
+    let copy = level.copy_area(TileArea::from_corners((1, 1), (2, 2)));
+    assert_eq!(copy.to_str(), "w: 2, h: 2\nP@\nx-\n");
+
+    level.fill_area(TileArea::from_corners((1, 1), (2, 1)), Tile::Empty);
+    assert_eq!(level.to_str(), "w: 4, h: 3\n####\n#--#\n#x-#\n");
+
+    //Tiles outside the level are cut off
+    level.paste((2, 1), &copy);
+    assert_eq!(level.to_str(), "w: 4, h: 3\n####\n#-P@\n#xx-\n");
+
+    level.fill_area(TileArea::from_corners((3, 2), (5, 5)), Tile::Wall);
+    assert_eq!(level.to_str(), "w: 4, h: 3\n####\n#-P@\n#xx#\n");
+}
+
+#[test]
+fn flood_fill() {
+    let mut level = Level::from_str("w: 5, h: 3\n--#--\n--#@-\n-----").unwrap();
+
+    level.flood_fill((0, 0), Tile::Goal);
+    assert_eq!(level.to_str(), "w: 5, h: 3\nxx#xx\nxx#@x\nxxxxx\n");
+
+    //Connections do not wrap around the borders
+    let mut level = Level::from_str("w: 3, h: 1\n-#-").unwrap();
+    level.flood_fill((0, 0), Tile::Wall);
+    assert_eq!(level.to_str(), "w: 3, h: 1\n##-\n");
+}
+
+#[test]
+fn draw_line_and_rectangle() {
+    let mut level = Level::new(5, 4);
+
+    level.draw_line((0, 0), (4, 2), Tile::Wall);
+    assert_eq!(level.to_str(), "w: 5, h: 4\n#----\n-##--\n---##\n-----\n");
+
+    let mut level = Level::new(5, 4);
+    level.draw_line((1, 3), (1, 1), Tile::Wall);
+    assert_eq!(level.to_str(), "w: 5, h: 4\n-----\n-#---\n-#---\n-#---\n");
+
+    let mut level = Level::new(5, 4);
+    level.draw_rectangle(TileArea::from_corners((1, 0), (4, 3)), Tile::Wall);
+    assert_eq!(level.to_str(), "w: 5, h: 4\n-####\n-#--#\n-#--#\n-####\n");
+}
+
+#[test]
+fn surround_with_walls() {
+    let mut level = Level::from_str("w: 6, h: 5\nbbbbbb\nbb-bbb\nb-P@xb\nbb-bbb\nbbbbbb").unwrap();
+
+    level.surround_with_walls((2, 2));
+    assert_eq!(level.to_str(), "w: 6, h: 5\nb###bb\n##-###\n#-P@x#\n##-###\nb###bb\n");
+
+    //Open levels are not changed
+    let mut level = Level::from_str("w: 4, h: 3\n----\n-P@x\n----").unwrap();
+    level.surround_with_walls((1, 1));
+    assert_eq!(level.to_str(), "w: 4, h: 3\n----\n-P@x\n----\n");
+}
+
+#[test]
+fn transform() {
+    let level = Level::from_str("w: 3, h: 2\n<P^\n>@v").unwrap();
+
+    assert_eq!(level.rotated_clockwise().to_str(), "w: 2, h: 3\nv^\n@P\n<>\n");
+    assert_eq!(level.rotated_counterclockwise().to_str(), "w: 2, h: 3\n<>\nP@\nv^\n");
+    assert_eq!(level.rotated_180().to_str(), "w: 3, h: 2\n^@<\nvP>\n");
+    assert_eq!(level.mirrored_horizontally().to_str(), "w: 3, h: 2\n^P>\nv@<\n");
+    assert_eq!(level.mirrored_vertically().to_str(), "w: 3, h: 2\n>@^\n<Pv\n");
+    assert_eq!(level.transposed().to_str(), "w: 2, h: 3\n^v\nP@\n<>\n");
+
+    assert_eq!(level.rotated_clockwise().rotated_counterclockwise().to_str(), level.to_str());
+    assert_eq!(level.transposed().transposed().to_str(), level.to_str());
+}
+
+#[test]
+fn resize_and_content_area() {
+    let level = Level::from_str("w: 3, h: 2\n#P#\n@x.").unwrap();
+
+    assert_eq!(level.resized(4, 3, (0, 0)).to_str(), "w: 4, h: 3\n#P#-\n@x.-\n----\n");
+    assert_eq!(level.resized(5, 4, (1, 1)).to_str(), "w: 5, h: 4\n-----\n-#P#-\n-@x.-\n-----\n");
+    assert_eq!(level.resized(2, 1, (-1, -1)).to_str(), "w: 2, h: 1\nx.\n");
+
+    let level = Level::from_str("w: 5, h: 4\n-----\n--#P-\n--@x-\n-----").unwrap();
+    let area = level.content_area().unwrap();
+    assert_eq!(area.positions().next(), Some((2, 1)));
+    assert_eq!((area.width(), area.height()), (2, 2));
+
+    assert!(Level::new(3, 3).content_area().is_none());
+}
@@ -0,0 +1,363 @@
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt::{Display, Formatter, Write as _};
+use std::fs::File;
+use std::io::Write;
+use crate::game::engine::Direction;
+use crate::game::level::{Level, LevelLoadingError, LevelLoadingErrorKind, LevelPack, LevelPackMetadataField, LevelWithStats, Tile};
+
+#[cfg(test)]
+mod tests;
+
+/// A board row after the run-length decoding
+struct XsbRow {
+    tiles: Vec<char>,
+    /// The 1-based column (In bytes) of each tile in the line of the XSB file
+    columns: Vec<usize>,
+
+    line_number: usize,
+    line: String,
+}
+
+impl Level {
+    /// Converts XSB board rows to a level
+    ///
+    /// Floor tiles which cannot be reached from the player position are converted to decoration tiles.
+    fn from_xsb_rows(rows: &[XsbRow]) -> Result<Self, LevelLoadingError> {
+        let width = rows.iter().
+                map(|row| row.tiles.len()).
+                max().unwrap_or_default();
+        let height = rows.len();
+
+        let mut level = Level::new(width, height);
+        let mut player_pos = None;
+        for (y, row) in rows.iter().
+                enumerate() {
+            for (x, (c, column)) in row.tiles.iter().
+                    zip(&row.columns).
+                    enumerate() {
+                let tile = match c {
+                    '#' => Tile::Wall,
+                    '@' | 'p' => Tile::Player,
+                    '+' | 'P' => Tile::PlayerInGoal,
+                    '$' | 'b' => Tile::Box,
+                    '*' | 'B' => Tile::BoxInGoal,
+                    '.' => Tile::Goal,
+                    ' ' | '-' | '_' => Tile::Empty,
+
+                    _ => return Err(LevelLoadingError::new(
+                        LevelLoadingErrorKind::InvalidTile,
+                        format!("\"{c}\" is not a valid XSB tile"),
+                    ).at(row.line_number, Some(*column), &row.line)),
+                };
+
+                if tile.is_player() {
+                    player_pos = Some((x, y));
+                }
+
+                level.set_tile(x, y, tile);
+            }
+        }
+
+        //Mark floor outside the walls as decoration (Levels wrap around at the edges)
+        if let Some(player_pos) = player_pos {
+            let mut is_inside = vec![false; width * height];
+            let mut queue = VecDeque::new();
+
+            is_inside[player_pos.0 + player_pos.1 * width] = true;
+            queue.push_back(player_pos);
+
+            while let Some(pos) = queue.pop_front() {
+                for direction in Direction::ALL {
+                    let pos_to = direction.next_pos(width, height, pos);
+                    let index_to = pos_to.0 + pos_to.1 * width;
+                    if !is_inside[index_to] && *level.get_tile(pos_to.0, pos_to.1).unwrap() != Tile::Wall {
+                        is_inside[index_to] = true;
+                        queue.push_back(pos_to);
+                    }
+                }
+            }
+
+            for (tile, is_inside) in level.tiles.iter_mut().
+                    zip(is_inside) {
+                if !is_inside && *tile == Tile::Empty {
+                    *tile = Tile::DecorationBlank;
+                }
+            }
+        }
+
+        Ok(level)
+    }
+
+    /// Converts the level to XSB board rows, returns an error if a tile has no XSB equivalent
+    pub fn to_xsb(&self) -> Result<String, XsbConversionError> {
+        let mut out = String::with_capacity((self.width + 1) * self.height);
+
+        for (y, row) in self.tiles.chunks(self.width).
+                enumerate() {
+            let mut line = String::with_capacity(self.width);
+            for (x, tile) in row.iter().
+                    enumerate() {
+                line.push(match tile {
+                    Tile::Empty | Tile::DecorationBlank => ' ',
+                    Tile::Wall => '#',
+                    Tile::Player => '@',
+                    Tile::PlayerInGoal => '+',
+                    Tile::Box => '$',
+                    Tile::BoxInGoal => '*',
+                    Tile::Goal => '.',
+
+                    tile => return Err(XsbConversionError::new(format!(
+                        "Tile \"{}\" at ({:02}:{:02}) has no XSB equivalent",
+                        tile.to_ascii() as char, x + 1, y + 1,
+                    ))),
+                });
+            }
+
+            //Empty rows would end the level
+            let line = line.trim_end();
+            if line.is_empty() {
+                out += "-";
+            }else {
+                out += line;
+            }
+            out += "\n";
+        }
+
+        Ok(out)
+    }
+}
+
+impl LevelPack {
+    const MAX_XSB_RUN_LENGTH: usize = 1024;
+
+    /// Parses a level pack in the XSB/SOK format without reading its save game
+    ///
+    /// Rows may be run-length encoded (e.g. "3#" for "###", "|" separates rows), all lines which are not board rows
+    /// (Comments, titles, ...) separate levels.
+    ///
+    /// "Title: " lines after a level are the title of that level. "Title: ", "Author: ", "Description: " and "Version: "
+    /// lines before the first level are the metadata of the level pack ("Title: " is used as name).
+    /// Too long values are truncated.
+    pub fn read_from_xsb_str(id: impl Into<String>, path: impl Into<String>, xsb_data: impl Into<String>) -> Result<Self, LevelLoadingError> {
+        let id = id.into();
+        let path = path.into();
+        let xsb_data = xsb_data.into();
+
+        let mut level_pack = Self::new(id, path);
+        level_pack.parse_xsb_data(&xsb_data).map_err(|err| err.with_path(&level_pack.path))?;
+
+        Ok(level_pack)
+    }
+
+    /// Parses the levels and the metadata of the XSB file (The path is not added to errors)
+    fn parse_xsb_data(&mut self, xsb_data: &str) -> Result<(), LevelLoadingError> {
+        let mut levels: Vec<LevelWithStats> = Vec::new();
+        let mut metadata: Vec<(LevelPackMetadataField, String)> = Vec::new();
+
+        let mut rows = Vec::new();
+        for (i, line) in xsb_data.lines().
+                chain([""]).
+                enumerate() {
+            let line = line.trim_end();
+
+            if Self::is_xsb_board_line(line, !rows.is_empty()) {
+                rows.append(&mut Self::decode_xsb_run_length(line, i + 1)?);
+
+                continue;
+            }
+
+            if rows.is_empty() {
+                Self::parse_xsb_metadata_line(line, &mut levels, &mut metadata);
+
+                continue;
+            }
+
+            let level = Level::from_xsb_rows(&rows).map_err(|err| err.with_level(levels.len() + 1))?;
+            let mut level = LevelWithStats::new(level, None, None);
+            level.line_numbers = rows.iter().
+                    map(|row| row.line_number).
+                    collect();
+            levels.push(level);
+
+            rows.clear();
+
+            Self::parse_xsb_metadata_line(line, &mut levels, &mut metadata);
+        }
+
+        if levels.is_empty() {
+            return Err(LevelLoadingError::new(LevelLoadingErrorKind::PrematureEof, "No levels were found"));
+        }
+
+        if levels.len() > Self::MAX_LEVEL_COUNT_PER_PACK {
+            return Err(LevelLoadingError::new(
+                LevelLoadingErrorKind::TooManyLevels,
+                format!("There are too many levels (Count: {}, Max: {})", levels.len(), Self::MAX_LEVEL_COUNT_PER_PACK),
+            ));
+        }
+
+        self.levels = levels;
+        for (field, value) in metadata {
+            self.set_metadata(field, Some(value));
+        }
+
+        Ok(())
+    }
+
+    /// Converts all levels to the XSB/SOK format, returns an error if a tile has no XSB equivalent
+    pub fn to_xsb(&self) -> Result<String, XsbConversionError> {
+        let mut out = String::new();
+
+        let _ = writeln!(out, "; Level pack: {}", self.id);
+        for field in LevelPackMetadataField::ALL {
+            if let Some(value) = self.metadata(field) {
+                let key = if field == LevelPackMetadataField::Name {
+                    "Title"
+                }else {
+                    field.key()
+                };
+
+                let _ = writeln!(out, "{key}: {value}");
+            }
+        }
+
+        for (i, level) in self.levels.iter().
+                enumerate() {
+            let level_xsb = level.level().to_xsb().map_err(|err| XsbConversionError::new(format!(
+                "Level {}: {err}", i + 1,
+            )))?;
+
+            let _ = write!(out, "\n; {}\n\n{}", i + 1, level_xsb);
+
+            if let Some(title) = level.title() {
+                let _ = writeln!(out, "Title: {title}");
+            }
+        }
+
+        Ok(out)
+    }
+
+    pub fn save_xsb_to_path(&self, path: impl Into<String>) -> Result<(), Box<dyn Error>> {
+        //Convert before creating the file in order to not leave an incomplete file behind
+        let xsb = self.to_xsb()?;
+
+        let mut file = File::create(path.into())?;
+        file.write_all(xsb.as_bytes())?;
+        file.flush()?;
+
+        Ok(())
+    }
+
+    /// Stores "Title: " lines after a level as level title and metadata lines before the first level as level pack metadata
+    fn parse_xsb_metadata_line(line: &str, levels: &mut [LevelWithStats], metadata: &mut Vec<(LevelPackMetadataField, String)>) {
+        let truncate = |value: &str, max_len: usize| value.chars().take(max_len).collect::<String>();
+
+        let Some((key, value)) = line.split_once(":") else {
+            return;
+        };
+        let (key, value) = (key.trim(), value.trim());
+        if value.is_empty() {
+            return;
+        }
+
+        if let Some(level) = levels.last_mut() {
+            if key.eq_ignore_ascii_case("Title") && level.title().is_none() {
+                level.set_title(Some(truncate(value, LevelWithStats::MAX_TITLE_LEN)));
+            }
+
+            return;
+        }
+
+        let field = if key.eq_ignore_ascii_case("Title") {
+            LevelPackMetadataField::Name
+        }else if let Some(field) = LevelPackMetadataField::ALL.into_iter().
+                find(|field| *field != LevelPackMetadataField::Name && key.eq_ignore_ascii_case(field.key())) {
+            field
+        }else {
+            return;
+        };
+
+        if !metadata.iter().any(|(field_defined, _)| *field_defined == field) {
+            metadata.push((field, truncate(value, field.max_len())));
+        }
+    }
+
+    /// Returns true if the line is a board row, rows without walls are only allowed inside of a level
+    fn is_xsb_board_line(line: &str, is_in_level: bool) -> bool {
+        let is_board_line = line.chars().all(|c| matches!(
+            c, '#' | '@' | 'p' | '+' | 'P' | '$' | 'b' | '*' | 'B' | '.' | ' ' | '-' | '_' | '|' | '0'..='9'
+        )) && line.chars().any(|c| !c.is_ascii_digit() && c != '|' && c != ' ');
+
+        is_board_line && (is_in_level || line.contains('#'))
+    }
+
+    /// Expands run-length encoded rows (e.g. "3#" -> "###"), "|" separates rows
+    fn decode_xsb_run_length(line: &str, line_number: usize) -> Result<Vec<XsbRow>, LevelLoadingError> {
+        let error = |message: &str, column: usize| LevelLoadingError::new(
+            LevelLoadingErrorKind::InvalidRunLength, message,
+        ).at(line_number, Some(column), line);
+
+        let new_row = || XsbRow {
+            tiles: Vec::new(),
+            columns: Vec::new(),
+
+            line_number,
+            line: line.to_string(),
+        };
+        let mut rows = vec![new_row()];
+
+        let mut count = None;
+        for (i, c) in line.char_indices() {
+            if let Some(digit) = c.to_digit(10) {
+                count = Some(count.unwrap_or(0_usize).saturating_mul(10).saturating_add(digit as usize));
+
+                if count.is_some_and(|count| count > Self::MAX_XSB_RUN_LENGTH) {
+                    return Err(error("The run length is too large", i + 1));
+                }
+
+                continue;
+            }
+
+            if c == '|' {
+                if count.is_some() {
+                    return Err(error("A run length before \"|\" is invalid", i + 1));
+                }
+
+                rows.push(new_row());
+
+                continue;
+            }
+
+            let row = rows.last_mut().unwrap();
+            for _ in 0..count.take().unwrap_or(1) {
+                row.tiles.push(c);
+                row.columns.push(i + 1);
+            }
+        }
+
+        if count.is_some() {
+            return Err(error("A run length at the end of the row is invalid", line.len()));
+        }
+
+        Ok(rows)
+    }
+}
+
+#[derive(Debug)]
+pub struct XsbConversionError {
+    message: String
+}
+
+impl XsbConversionError {
+    fn new(message: impl Into<String>) -> Self {
+        Self { message: message.into() }
+    }
+}
+
+impl Display for XsbConversionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl Error for XsbConversionError {}
@@ -0,0 +1,99 @@
+use std::str::FromStr;
+use crate::game::level::xsb::*;
+
+#[test]
+fn read_levels() {
+    let xsb = "; Test pack\n\
+               Title: First\n\
+               \n\
+               #####\n\
+               #@$.#\n\
+               #####\n\
+               \n\
+               ; 2\n\
+               ######\n\
+               #@ *$.#\n\
+               ######\n\
+               Title: Second\n\
+               Author: Someone\n";
+
+    let level_pack = LevelPack::read_from_xsb_str("test", "test.xsb", xsb).unwrap();
+    assert_eq!(level_pack.level_count(), 2);
+    assert_eq!(level_pack.levels()[0].level().to_str(), "w: 5, h: 3\n#####\n#P@x#\n#####\n");
+
+    //Rows of different lengths are filled with decoration tiles outside the walls
+    assert_eq!(level_pack.levels()[1].level().to_str(), "w: 7, h: 3\n######b\n#P-+@x#\n######b\n");
+
+    //The title before the first level is the name of the level pack
+    assert_eq!(level_pack.metadata(LevelPackMetadataField::Name), Some("First"));
+    assert_eq!(level_pack.metadata(LevelPackMetadataField::Author), None);
+    assert_eq!(level_pack.levels()[0].title(), None);
+    assert_eq!(level_pack.levels()[1].title(), Some("Second"));
+}
+
+#[test]
+fn read_outside_floor() {
+    let xsb = "  ###\n### @#\n#$.  #\n######";
+
+    let level_pack = LevelPack::read_from_xsb_str("test", "test.xsb", xsb).unwrap();
+    assert_eq!(level_pack.levels()[0].level().to_str(), "w: 6, h: 4\nbb###b\n###-P#\n#@x--#\n######\n");
+}
+
+#[test]
+fn read_run_length_encoding() {
+    let xsb = "5#\n#@$.#\n5#\n\n3#|#@#|#$#|#.#|3#";
+
+    let level_pack = LevelPack::read_from_xsb_str("test", "test.xsb", xsb).unwrap();
+    assert_eq!(level_pack.level_count(), 2);
+    assert_eq!(level_pack.levels()[0].level().to_str(), "w: 5, h: 3\n#####\n#P@x#\n#####\n");
+    assert_eq!(level_pack.levels()[1].level().to_str(), "w: 3, h: 5\n###\n#P#\n#@#\n#x#\n###\n");
+
+    assert!(LevelPack::read_from_xsb_str("test", "test.xsb", "5#\n#@$.#\n5#5").is_err());
+}
+
+#[test]
+fn read_errors() {
+    assert!(LevelPack::read_from_xsb_str("test", "test.xsb", "; No levels").is_err());
+
+    //Columns of errors refer to the line before the run-length decoding
+    let rows = LevelPack::decode_xsb_run_length("#3 z#", 3).unwrap();
+    let err = Level::from_xsb_rows(&rows).unwrap_err();
+    assert!(err.to_string().contains("line 3, column 4"), "{err}");
+}
+
+#[test]
+fn read_player_in_goal() {
+    let level_pack = LevelPack::read_from_xsb_str("test", "test.xsb", "; Comment\n#####\n#+$ #\n#####").unwrap();
+    assert_eq!(level_pack.levels()[0].level().to_str(), "w: 5, h: 3\n#####\n#&@-#\n#####\n");
+    assert_eq!(level_pack.to_xsb().unwrap(), "; Level pack: test\n\n; 1\n\n#####\n#+$ #\n#####\n");
+}
+
+#[test]
+fn write_levels() {
+    let mut level_pack = LevelPack::new("test", "test.lvl.edit");
+    level_pack.add_level(Level::from_str("w: 6, h: 3\n######\n#P@x-#\n######").unwrap());
+    level_pack.add_level(Level::from_str("w: 5, h: 3\nb###b\n#P+x#\nb###b").unwrap());
+    level_pack.levels_mut()[1].set_title(Some("Second".to_string()));
+    level_pack.set_metadata(LevelPackMetadataField::Name, Some("Test pack".to_string()));
+    level_pack.set_metadata(LevelPackMetadataField::Author, Some("Someone".to_string()));
+
+    let xsb = level_pack.to_xsb().unwrap();
+    assert_eq!(xsb, "; Level pack: test\nTitle: Test pack\nAuthor: Someone\n\n; 1\n\n######\n#@$. #\n######\n\n; 2\n\n ###\n#@*.#\n ###\nTitle: Second\n");
+
+    //Round trip
+    let level_pack_read = LevelPack::read_from_xsb_str("test", "test.xsb", xsb).unwrap();
+    assert_eq!(level_pack_read.level_count(), 2);
+    assert_eq!(level_pack_read.levels()[1].level().to_str(), "w: 5, h: 3\nb###b\n#P+x#\nb###b\n");
+    assert_eq!(level_pack_read.levels()[1].title(), Some("Second"));
+    assert_eq!(level_pack_read.metadata(LevelPackMetadataField::Name), Some("Test pack"));
+    assert_eq!(level_pack_read.metadata(LevelPackMetadataField::Author), Some("Someone"));
+}
+
+#[test]
+fn write_errors() {
+    let mut level_pack = LevelPack::new("test", "test.lvl.edit");
+    level_pack.add_level(Level::from_str("w: 5, h: 3\n#####\n#P*=#\n#####").unwrap());
+
+    let err = level_pack.to_xsb().unwrap_err();
+    assert_eq!(err.to_string(), "Level 1: Tile \"*\" at (03:02) has no XSB equivalent");
+}
@@ -1,25 +1,38 @@
-use console_lib::{Key, Color, Console};
+use console_lib::{Key, Color};
+use crate::game::canvas::Canvas;
 use std::cmp::Ordering;
+use std::collections::VecDeque;
 use std::fmt::Write as _;
 use std::str::FromStr;
-use std::time::SystemTime;
+use std::time::{Duration, SystemTime};
 use dialog::DialogYesNo;
 use crate::game::{Game, GameState};
-use crate::game::level::{Level, LevelPack, Tile};
+use crate::game::deadlock::DeadlockAnalyzer;
+use crate::game::engine::{Direction, GameplayState};
+use crate::game::key_bindings::{Action, BoundKey, KeyBindings};
+use crate::game::level::{Level, LevelPack, LevelPackMetadataField, LevelView, LevelWithStats, Tile, TileArea};
+use crate::game::settings::{Ranking, Settings};
+use crate::game::solver::{Hint, Solver, SolverResult, SolverTask};
+use crate::game::theme::{Theme, ThemeElement};
+use crate::game::validation;
+use crate::game::screen::animation::MoveAnimation;
 use crate::game::screen::dialog::{DialogOk, DialogSelection, DialogYesCancelNo};
 use crate::collections::UndoHistory;
 
+pub mod animation;
 pub mod dialog;
 pub mod utils;
 
 #[derive(Debug, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
 pub enum ScreenId {
     StartMenu,
+    Settings,
 
     SelectLevelPack,
     SelectLevel,
 
     InGame,
+    Replay,
 
     SelectLevelPackEditor,
     LevelPackEditor,
@@ -28,7 +41,13 @@ pub enum ScreenId {
 
 #[allow(unused_variables)]
 pub trait Screen {
-    fn draw(&self, game_state: &GameState, console: &Console);
+    fn draw(&self, game_state: &GameState, console: &Canvas);
+
+    /// If true, the screen is drawn on a canvas of the console size, otherwise on a canvas of the minimal console size
+    /// which is centered in the console
+    fn is_full_size(&self) -> bool {
+        false
+    }
 
     fn update(&mut self, game_state: &mut GameState) {}
 
@@ -37,6 +56,8 @@ pub trait Screen {
 
     fn on_dialog_selection(&mut self, game_state: &mut GameState, selection: DialogSelection) {}
 
+    /// Called if the game is paused without a dialog or the help page (e.g. if the console becomes too small)
+    fn on_pause(&mut self, game_state: &mut GameState) {}
     fn on_continue(&mut self, game_state: &mut GameState) {}
     fn on_set_screen(&mut self, game_state: &mut GameState) {}
 }
@@ -50,7 +71,7 @@ impl ScreenStartMenu {
 }
 
 impl Screen for ScreenStartMenu {
-    fn draw(&self, _: &GameState, console: &Console) {//Draw border (top)
+    fn draw(&self, _: &GameState, console: &Canvas) {//Draw border (top)
         console.set_color(Color::White, Color::Blue);
         console.draw_text(
             "/------------------------------------------------------------------------\\\n"
@@ -85,6 +106,13 @@ impl Screen for ScreenStartMenu {
         console.reset_color();
         console.draw_text(" to start the game!");
 
+        console.set_cursor_pos(23, 18);
+        console.draw_text("Press ");
+        console.set_color(Color::LightRed, Color::Default);
+        console.draw_text("s");
+        console.reset_color();
+        console.draw_text(" to open the settings");
+
         console.set_cursor_pos(1, 21);
         console.draw_text("By ");
         console.set_color(Color::Default, Color::Yellow);
@@ -124,6 +152,10 @@ impl Screen for ScreenStartMenu {
         if key == Key::ENTER {
             game_state.set_screen(ScreenId::SelectLevelPack);
         }
+
+        if key == Key::S {
+            game_state.set_screen(ScreenId::Settings);
+        }
     }
 
     fn on_mouse_pressed(&mut self, game_state: &mut GameState, column: usize, row: usize) {
@@ -131,6 +163,10 @@ impl Screen for ScreenStartMenu {
             self.on_key_pressed(game_state, Key::ENTER);
         }
 
+        if row == 18 && column == 29 {
+            self.on_key_pressed(game_state, Key::S);
+        }
+
         if row == 21 && column > 64 && column < 73 {
             game_state.open_help_page();
         }
@@ -143,6 +179,422 @@ impl Screen for ScreenStartMenu {
     }
 }
 
+pub struct ScreenSettings {
+    selected_entry: usize,
+    is_waiting_for_key: bool,
+
+    /// Index of the first visible key binding of the level editor
+    key_bindings_scroll: usize,
+}
+
+impl ScreenSettings {
+    const OPTION_COUNT: usize = 8;
+    const OPTION_LABELS: [&'static str; Self::OPTION_COUNT] = [
+        "Color theme", "Blink delay", "Show timer", "Confirm reset", "Undo limit", "Wide tiles", "Animations", "Ranking",
+    ];
+
+    /// Options are drawn in two columns like the key bindings
+    const OPTION_ROWS: usize = 5;
+    const OPTION_Y: usize = 2;
+    const OPTION_VALUE_MAX_LEN: usize = Self::COLUMN_WIDTH - 22;
+
+    /// Key bindings of the game screen are drawn in the first column, key bindings of the level editor in the second column
+    const FIRST_COLUMN_ACTION_COUNT: usize = Action::GAME_SCREEN.len();
+    const COLUMN_WIDTH: usize = 37;
+    const KEY_BINDINGS_Y: usize = 9;
+    /// The key bindings of the level editor are scrolled if there are more actions than rows
+    const KEY_BINDINGS_ROWS: usize = Game::CONSOLE_MIN_HEIGHT - 2 - Self::KEY_BINDINGS_Y;
+
+    pub fn new() -> Self {
+        Self {
+            selected_entry: Default::default(),
+            is_waiting_for_key: Default::default(),
+
+            key_bindings_scroll: Default::default(),
+        }
+    }
+
+    fn entry_count() -> usize {
+        Self::OPTION_COUNT + Action::ALL.len()
+    }
+
+    fn selected_action(&self) -> Option<Action> {
+        self.selected_entry.checked_sub(Self::OPTION_COUNT).map(|index| Action::ALL[index])
+    }
+
+    /// Returns the column and the row of the option
+    fn option_pos(index: usize) -> (usize, usize) {
+        (Self::COLUMN_WIDTH * (index / Self::OPTION_ROWS), Self::OPTION_Y + index % Self::OPTION_ROWS)
+    }
+
+    /// Returns the column and the row of the key binding entry of the action or None if it is scrolled out of view
+    fn action_pos(&self, action_index: usize) -> Option<(usize, usize)> {
+        if action_index < Self::FIRST_COLUMN_ACTION_COUNT {
+            return Some((0, Self::KEY_BINDINGS_Y + action_index));
+        }
+
+        let row = (action_index - Self::FIRST_COLUMN_ACTION_COUNT).checked_sub(self.key_bindings_scroll)?;
+        (row < Self::KEY_BINDINGS_ROWS).then_some((Self::COLUMN_WIDTH, Self::KEY_BINDINGS_Y + row))
+    }
+
+    /// Scrolls the key bindings of the level editor so that the selected entry is visible
+    fn scroll_to_selected_entry(&mut self) {
+        let Some(index) = self.selected_entry.checked_sub(Self::OPTION_COUNT + Self::FIRST_COLUMN_ACTION_COUNT) else {
+            return;
+        };
+
+        if index < self.key_bindings_scroll {
+            self.key_bindings_scroll = index;
+        }else if index >= self.key_bindings_scroll + Self::KEY_BINDINGS_ROWS {
+            self.key_bindings_scroll = index + 1 - Self::KEY_BINDINGS_ROWS;
+        }
+    }
+
+    fn option_value(settings: &Settings, index: usize) -> String {
+        let yes_no = |value: bool| if value { "Yes" } else { "No" }.to_string();
+
+        match index {
+            0 if settings.theme().len() > Self::OPTION_VALUE_MAX_LEN => {
+                format!("{}..", &settings.theme()[..Self::OPTION_VALUE_MAX_LEN - 2])
+            },
+            0 => settings.theme().to_string(),
+            1 => match settings.player_blink_delay() {
+                0 => "Off".to_string(),
+                delay => format!("{} ms", delay * 40),
+            },
+            2 => yes_no(settings.show_timer()),
+            3 => yes_no(settings.confirm_reset()),
+            4 => match settings.undo_limit() {
+                0 => "Unlimited".to_string(),
+                undo_limit => undo_limit.to_string(),
+            },
+            5 => if settings.wide_tiles() { "Auto" } else { "Off" }.to_string(),
+            6 => yes_no(settings.animations()),
+            7 => match settings.ranking() {
+                Ranking::Moves => "Moves",
+                Ranking::Pushes => "Pushes",
+            }.to_string(),
+
+            _ => String::new(),
+        }
+    }
+
+    fn change_option(game_state: &mut GameState, index: usize, forward: bool) {
+        let settings = game_state.settings_mut();
+        match index {
+            0 => {
+                let theme_names = match Theme::available_names() {
+                    Ok(theme_names) => theme_names,
+                    Err(err) => {
+                        game_state.open_dialog(Box::new(DialogOk::new_error(format!("Cannot load theme: {}", err))));
+
+                        return;
+                    },
+                };
+
+                //Themes which can not be loaded are skipped (The error of the first one is shown)
+                let theme_index = theme_names.iter().position(|theme| theme == settings.theme()).unwrap_or_default();
+                let mut first_err = None;
+                let mut theme = None;
+                for i in 1..=theme_names.len() {
+                    let theme_index = if forward {
+                        (theme_index + i) % theme_names.len()
+                    }else {
+                        (theme_index + theme_names.len() - i) % theme_names.len()
+                    };
+
+                    match Theme::load(&theme_names[theme_index]) {
+                        Ok(loaded_theme) => {
+                            theme = Some(loaded_theme);
+
+                            break;
+                        },
+                        Err(err) => {
+                            first_err.get_or_insert(err);
+                        },
+                    }
+                }
+
+                if let Some(theme) = theme {
+                    settings.set_theme(theme.name());
+                    game_state.set_theme(theme);
+                }
+
+                if let Some(err) = first_err {
+                    game_state.open_dialog(Box::new(DialogOk::new_error(format!("Cannot load theme: {}", err))));
+                }
+            },
+            1 => settings.set_player_blink_delay(Settings::cycle(&Settings::PLAYER_BLINK_DELAYS, settings.player_blink_delay(), forward)),
+            2 => settings.set_show_timer(!settings.show_timer()),
+            3 => settings.set_confirm_reset(!settings.confirm_reset()),
+            4 => settings.set_undo_limit(Settings::cycle(&Settings::UNDO_LIMITS, settings.undo_limit(), forward)),
+            5 => settings.set_wide_tiles(!settings.wide_tiles()),
+            6 => settings.set_animations(!settings.animations()),
+            7 => settings.set_ranking(match settings.ranking() {
+                Ranking::Moves => Ranking::Pushes,
+                Ranking::Pushes => Ranking::Moves,
+            }),
+
+            _ => {},
+        }
+
+        if let Err(err) = game_state.settings().save() {
+            game_state.open_dialog(Box::new(DialogOk::new_error(format!("Cannot save: {}", err))));
+        }
+    }
+
+    fn set_keys(game_state: &mut GameState, action: Action, keys: Vec<BoundKey>) {
+        let mut key_bindings = game_state.key_bindings().clone();
+        key_bindings.set_keys(action, keys);
+
+        if let Err(err) = key_bindings.check_conflicts() {
+            game_state.open_dialog(Box::new(DialogOk::new_error(err.to_string())));
+
+            return;
+        }
+
+        *game_state.key_bindings_mut() = key_bindings;
+        if let Err(err) = game_state.key_bindings().save() {
+            game_state.open_dialog(Box::new(DialogOk::new_error(format!("Cannot save: {}", err))));
+        }
+    }
+
+    fn on_key_pressed_waiting_for_key(&mut self, game_state: &mut GameState, key: Key) {
+        self.is_waiting_for_key = false;
+
+        if key == Key::ESC {
+            return;
+        }
+
+        let Some(action) = self.selected_action() else {
+            return;
+        };
+
+        let Some(bound_key) = BoundKey::from_key(key) else {
+            game_state.open_dialog(Box::new(DialogOk::new_error("This key can not be bound!")));
+
+            return;
+        };
+
+        //Pressing a key which is already bound removes it
+        let mut keys = game_state.key_bindings().keys(action).to_vec();
+        if let Some(index) = keys.iter().position(|key| *key == bound_key) {
+            keys.remove(index);
+        }else {
+            keys.push(bound_key);
+        }
+
+        Self::set_keys(game_state, action, keys);
+    }
+}
+
+impl Screen for ScreenSettings {
+    fn draw(&self, game_state: &GameState, console: &Canvas) {
+        console.reset_color();
+        console.set_underline(true);
+        console.draw_text("Settings:");
+        console.set_underline(false);
+
+        for (i, label) in Self::OPTION_LABELS.iter().
+                enumerate() {
+            let (x, y) = Self::option_pos(i);
+
+            if i == self.selected_entry {
+                game_state.theme().set_color(console, ThemeElement::Selection);
+                console.set_cursor_pos(x, y);
+                console.draw_text(">");
+            }
+
+            console.set_cursor_pos(x + 2, y);
+            console.draw_text(format!("{:<16}< {} >", format!("{label}:"), Self::option_value(game_state.settings(), i)));
+            console.reset_color();
+        }
+
+        console.set_underline(true);
+        console.set_cursor_pos(0, Self::KEY_BINDINGS_Y - 1);
+        console.draw_text("Key bindings:");
+        console.set_cursor_pos(Self::COLUMN_WIDTH, Self::KEY_BINDINGS_Y - 1);
+        console.draw_text("Key bindings (Level editor):");
+        console.set_underline(false);
+
+        for (i, action) in Action::ALL.iter().
+                enumerate() {
+            let Some((x, y)) = self.action_pos(i) else {
+                continue;
+            };
+
+            if Some(*action) == self.selected_action() {
+                game_state.theme().set_color(console, ThemeElement::Selection);
+                console.set_cursor_pos(x, y);
+                console.draw_text(">");
+            }
+
+            let keys = game_state.key_bindings().keys(*action).iter().
+                    map(|key| key.to_string()).
+                    collect::<Vec<_>>().
+                    join(" ");
+
+            console.set_cursor_pos(x + 2, y);
+            console.draw_text(format!("{:<19}", format!("{}:", action.label())));
+            if keys.is_empty() {
+                console.draw_text("-");
+            }else {
+                console.draw_text(keys.chars().take(Self::COLUMN_WIDTH - 22).collect::<String>());
+            }
+            console.reset_color();
+        }
+
+        //Mark that there are more key bindings above or below
+        if self.key_bindings_scroll > 0 {
+            console.set_cursor_pos(2 * Self::COLUMN_WIDTH - 1, Self::KEY_BINDINGS_Y);
+            console.draw_text("^");
+        }
+        if self.key_bindings_scroll + Self::KEY_BINDINGS_ROWS < Action::ALL.len() - Self::FIRST_COLUMN_ACTION_COUNT {
+            console.set_cursor_pos(2 * Self::COLUMN_WIDTH - 1, Self::KEY_BINDINGS_Y + Self::KEY_BINDINGS_ROWS - 1);
+            console.draw_text("v");
+        }
+
+        console.set_cursor_pos(0, Game::CONSOLE_MIN_HEIGHT - 2);
+        if let Some(action) = self.selected_action().filter(|_| self.is_waiting_for_key) {
+            console.draw_text(format!("Press a key to add it to or remove it from \"{}\" (", action.label()));
+            console.set_color(Color::LightRed, Color::Default);
+            console.draw_text("ESC");
+            console.reset_color();
+            console.draw_text(": Cancel)");
+
+            return;
+        }
+
+        console.set_color(Color::LightRed, Color::Default);
+        console.draw_text("LEFT");
+        console.reset_color();
+        console.draw_text("/");
+        console.set_color(Color::LightRed, Color::Default);
+        console.draw_text("RIGHT");
+        console.reset_color();
+        console.draw_text(": Change option, ");
+        console.set_color(Color::LightRed, Color::Default);
+        console.draw_text("ENTER");
+        console.reset_color();
+        console.draw_text(": Add/remove key, ");
+        console.set_color(Color::LightRed, Color::Default);
+        console.draw_text("DELETE");
+        console.reset_color();
+        console.draw_text(": Unbind keys\n");
+        console.set_color(Color::LightRed, Color::Default);
+        console.draw_text("ESC");
+        console.reset_color();
+        console.draw_text(": Back (Changes are saved immediately)");
+    }
+
+    fn on_key_pressed(&mut self, game_state: &mut GameState, key: Key) {
+        if self.is_waiting_for_key {
+            self.on_key_pressed_waiting_for_key(game_state, key);
+
+            return;
+        }
+
+        if key == Key::ESC {
+            game_state.set_screen(ScreenId::StartMenu);
+
+            return;
+        }
+
+        if key == Key::F1 {
+            game_state.open_help_page();
+
+            return;
+        }
+
+        match key {
+            Key::UP => {
+                if self.selected_entry > 0 {
+                    self.selected_entry -= 1;
+                }else {
+                    self.selected_entry = Self::entry_count() - 1;
+                }
+            },
+            Key::DOWN => {
+                if self.selected_entry + 1 < Self::entry_count() {
+                    self.selected_entry += 1;
+                }else {
+                    self.selected_entry = 0;
+                }
+            },
+
+            Key::LEFT | Key::RIGHT => {
+                let Some(action_index) = self.selected_entry.checked_sub(Self::OPTION_COUNT) else {
+                    Self::change_option(game_state, self.selected_entry, key == Key::RIGHT);
+
+                    return;
+                };
+
+                //Switch to the entry in the same row of the other column
+                let action_index = if action_index < Self::FIRST_COLUMN_ACTION_COUNT {
+                    (action_index + self.key_bindings_scroll + Self::FIRST_COLUMN_ACTION_COUNT).min(Action::ALL.len() - 1)
+                }else {
+                    (action_index - self.key_bindings_scroll - Self::FIRST_COLUMN_ACTION_COUNT).min(Self::FIRST_COLUMN_ACTION_COUNT - 1)
+                };
+                self.selected_entry = Self::OPTION_COUNT + action_index;
+            },
+
+            Key::ENTER => {
+                if self.selected_action().is_some() {
+                    self.is_waiting_for_key = true;
+                }else {
+                    Self::change_option(game_state, self.selected_entry, true);
+                }
+            },
+
+            Key::DELETE => {
+                if let Some(action) = self.selected_action() {
+                    Self::set_keys(game_state, action, Vec::new());
+                }
+            },
+
+            _ => {},
+        }
+
+        self.scroll_to_selected_entry();
+    }
+
+    fn on_mouse_pressed(&mut self, game_state: &mut GameState, column: usize, row: usize) {
+        if self.is_waiting_for_key {
+            return;
+        }
+
+        let entry = if let Some(index) = (0..Self::OPTION_COUNT).
+                find(|i| {
+                    let (x, y) = Self::option_pos(*i);
+
+                    y == row && (x..x + Self::COLUMN_WIDTH).contains(&column)
+                }) {
+            index
+        }else if let Some(action_index) = (0..Action::ALL.len()).
+                find(|i| self.action_pos(*i).is_some_and(|(x, y)| {
+                    y == row && (x..x + Self::COLUMN_WIDTH).contains(&column)
+                })) {
+            Self::OPTION_COUNT + action_index
+        }else {
+            return;
+        };
+
+        //Clicking on the selected entry has the same effect as ENTER
+        if entry == self.selected_entry {
+            self.on_key_pressed(game_state, Key::ENTER);
+        }else {
+            self.selected_entry = entry;
+        }
+    }
+
+    fn on_set_screen(&mut self, _: &mut GameState) {
+        self.selected_entry = 0;
+        self.is_waiting_for_key = false;
+
+        self.key_bindings_scroll = 0;
+    }
+}
+
 pub struct ScreenSelectLevelPack {}
 
 impl ScreenSelectLevelPack {
@@ -152,7 +604,7 @@ impl ScreenSelectLevelPack {
 }
 
 impl Screen for ScreenSelectLevelPack {
-    fn draw(&self, game_state: &GameState, console: &Console) {
+    fn draw(&self, game_state: &GameState, console: &Canvas) {
         console.reset_color();
         console.set_underline(true);
         console.draw_text("Select a level pack:");
@@ -190,22 +642,27 @@ impl Screen for ScreenSelectLevelPack {
             }
 
             console.set_cursor_pos(x, y);
-            if i == game_state.get_level_pack_count() {
+            let glyph = if i == game_state.get_level_pack_count() {
                 //Level Pack Editor entry
                 console.set_color(Color::White, Color::LightBlue);
                 console.draw_text(" +");
+
+                '|'
             }else {
-                console.set_color(Color::Black, if game_state.level_packs().get(i).
+                let style = game_state.theme().style(if game_state.level_packs().get(i).
                         unwrap().level_pack_best_moves_sum().is_some() {
-                    Color::Green
+                    ThemeElement::LevelCompleted
                 }else {
-                    Color::Yellow
+                    ThemeElement::LevelNext
                 });
+                console.set_color(style.fg, style.bg);
                 console.draw_text(format!("{:2}", i + 1));
-            }
+
+                style.glyph
+            };
 
             console.reset_color();
-            console.draw_text("|");
+            console.draw_text(glyph.to_string());
 
             console.set_cursor_pos(x, y + 1);
             console.draw_text("---");
@@ -215,7 +672,7 @@ impl Screen for ScreenSelectLevelPack {
         let x = (game_state.get_level_pack_index()%24)*3;
         let y = 1 + (game_state.get_level_pack_index()/24)*2;
 
-        console.set_color(Color::Cyan, Color::Default);
+        game_state.theme().set_color(console, ThemeElement::Selection);
         console.set_cursor_pos(x, y);
         console.draw_text("----");
         console.set_cursor_pos(x, y + 1);
@@ -225,25 +682,40 @@ impl Screen for ScreenSelectLevelPack {
         console.set_cursor_pos(x, y + 2);
         console.draw_text("----");
 
-        //Draw border for best time and best moves
+        //Draw border for best time, best moves and metadata
         let y = 4 + (entry_count/24)*2;
 
         console.set_cursor_pos(0, y);
-        console.set_color(Color::Cyan, Color::Default);
-        console.draw_text(".-------------------------------------.");
-        for i in 1..4 {
+        game_state.theme().set_color(console, ThemeElement::Selection);
+        console.draw_text(".------------------------------------------------------------------------.");
+        for i in 1..5 {
             console.set_cursor_pos(0, y + i);
-            console.draw_text("|                                     |");
+            console.draw_text("|                                                                        |");
         }
-        console.set_cursor_pos(0, y + 4);
-        console.draw_text("\'-------------------------------------\'");
+        console.set_cursor_pos(0, y + 5);
+        console.draw_text("\'------------------------------------------------------------------------\'");
         console.reset_color();
 
         if game_state.get_level_pack_index() == game_state.get_level_pack_count() {
             //Level Pack Editor entry
-            console.set_cursor_pos(6, y + 2);
+            console.set_cursor_pos(24, y + 2);
             console.draw_text("Create or edit level packs");
         }else {
+            //Draw metadata
+            let level_pack = game_state.get_current_level_pack().unwrap();
+            for (i, field) in [LevelPackMetadataField::Name, LevelPackMetadataField::Author, LevelPackMetadataField::Version].
+                    into_iter().
+                    enumerate() {
+                console.set_cursor_pos(39, y + 1 + i);
+                console.draw_text(format!("{:7}: {}", field.key(), level_pack.metadata(field).unwrap_or("-")));
+            }
+
+            console.set_cursor_pos(1, y + 4);
+            console.draw_text(format!(
+                "Description: {}",
+                level_pack.metadata(LevelPackMetadataField::Description).unwrap_or("-"),
+            ));
+
             //Draw sum of best time and sum of best moves
             console.set_cursor_pos(1, y + 1);
             console.draw_text(format!("Selected level pack: {:>16}", game_state.level_packs().get(game_state.get_level_pack_index()).unwrap().id()));
@@ -262,11 +734,17 @@ impl Screen for ScreenSelectLevelPack {
                     ));
                 },
             }
+            let level_pack = game_state.get_current_level_pack().unwrap();
+            let (label, best_score_sum) = match game_state.settings().ranking() {
+                Ranking::Moves => ("Sum of best moves  :          ", level_pack.level_pack_best_moves_sum()),
+                Ranking::Pushes => ("Sum of best pushes :          ", level_pack.level_pack_best_pushes_sum()),
+            };
+
             console.set_cursor_pos(1, y + 3);
-            console.draw_text("Sum of best moves  :          ");
-            match game_state.get_current_level_pack().as_ref().unwrap().level_pack_best_moves_sum() {
+            console.draw_text(label);
+            match best_score_sum {
                 None => console.draw_text("XXXXXXX"),
-                Some(best_moves_sum) => console.draw_text(format!("{:07}", best_moves_sum)),
+                Some(best_score_sum) => console.draw_text(format!("{:07}", best_score_sum)),
             }
         }
     }
@@ -370,10 +848,10 @@ impl ScreenSelectLevel {
 }
 
 impl Screen for ScreenSelectLevel {
-    fn draw(&self, game_state: &GameState, console: &Console) {
+    fn draw(&self, game_state: &GameState, console: &Canvas) {
         console.reset_color();
         console.set_underline(true);
-        console.draw_text(format!("Select a level (Level pack \"{}\"):", game_state.get_current_level_pack().unwrap().id()));
+        console.draw_text(format!("Select a level (Level pack \"{}\"):", game_state.get_current_level_pack().unwrap().display_name()));
         console.set_underline(false);
 
         let level_count = game_state.get_current_level_pack().as_ref().unwrap().level_count();
@@ -406,19 +884,17 @@ impl Screen for ScreenSelectLevel {
             }
 
             let min_level_not_completed = game_state.get_current_level_pack().as_ref().unwrap().min_level_not_completed();
-            console.set_color(
-                Color::Black,
-                match i.cmp(&min_level_not_completed) {
-                    Ordering::Less => Color::Green,
-                    Ordering::Equal => Color::Yellow,
-                    Ordering::Greater => Color::Red,
-                }
-            );
+            let style = game_state.theme().style(match i.cmp(&min_level_not_completed) {
+                Ordering::Less => ThemeElement::LevelCompleted,
+                Ordering::Equal => ThemeElement::LevelNext,
+                Ordering::Greater => ThemeElement::LevelLocked,
+            });
+            console.set_color(style.fg, style.bg);
             console.set_cursor_pos(x, y);
             console.draw_text(utils::number_to_string_leading_ascii(2, i as u32 + 1, false));
 
             console.reset_color();
-            console.draw_text("|");
+            console.draw_text(style.glyph.to_string());
 
             console.set_cursor_pos(x, y + 1);
             console.draw_text("---");
@@ -428,7 +904,7 @@ impl Screen for ScreenSelectLevel {
         let x = (self.selected_level%24)*3;
         let y = 1 + (self.selected_level/24)*2;
 
-        console.set_color(Color::Cyan, Color::Default);
+        game_state.theme().set_color(console, ThemeElement::Selection);
         console.set_cursor_pos(x, y);
         console.draw_text("----");
         console.set_cursor_pos(x, y + 1);
@@ -438,46 +914,96 @@ impl Screen for ScreenSelectLevel {
         console.set_cursor_pos(x, y + 2);
         console.draw_text("----");
 
-        //Draw border for best time and best moves
+        //Draw border for best scores
         let y = 4 + ((level_count - 1)/24)*2;
 
         console.set_cursor_pos(0, y);
-        console.set_color(Color::Cyan, Color::Default);
-        console.draw_text(".-------------------------.");
+        game_state.theme().set_color(console, ThemeElement::Selection);
+        console.draw_text(".------------------------------------------------------------------------.");
         for i in 1..4 {
             console.set_cursor_pos(0, y + i);
-            console.draw_text("|                         |");
+            console.draw_text("|                                                                        |");
         }
         console.set_cursor_pos(0, y + 4);
-        console.draw_text("\'-------------------------\'");
+        console.draw_text("\'------------------------------------------------------------------------\'");
 
-        //Draw best time and best moves
+        //Draw best scores
         console.reset_color();
         console.set_cursor_pos(1, y + 1);
         console.draw_text("Selected level:        ");
         let selected_level = self.selected_level;
         console.draw_text(utils::number_to_string_leading_ascii(2, selected_level as u32 + 1, true));
 
+        let level = game_state.get_current_level_pack().as_ref().unwrap().levels().get(selected_level).unwrap();
+
+        if let Some(title) = level.title() {
+            console.set_cursor_pos(32, y + 1);
+            console.draw_text(format!("Title: {title}"));
+        }
+
+        //Best scores which were achieved with hints are marked with "(H)"
+        let draw_hints_marker = |hints: u32| {
+            if hints > 0 {
+                console.draw_text(" (H)");
+                console.reset_color();
+            }
+        };
+        let set_hints_color = |hints: u32| {
+            if hints > 0 {
+                game_state.theme().set_color(console, ThemeElement::HintText);
+            }
+        };
+
+        //The best actions are only achieved in strict mode
+        console.set_cursor_pos(32, y + 2);
+        console.draw_text(format!("Strict mode   : {:3} (Best: ", if game_state.is_strict_mode() { "On" } else { "Off" }));
+        match level.best_actions() {
+            None => console.draw_text("XXXX"),
+            Some(best_actions) => {
+                set_hints_color(level.best_actions_hints());
+                console.draw_text(format!("{:04}", best_actions));
+                draw_hints_marker(level.best_actions_hints());
+            },
+        }
+        console.draw_text(")");
+
         console.set_cursor_pos(1, y + 2);
         console.draw_text("Best time     : ");
-        match game_state.get_current_level_pack().as_ref().unwrap().levels().get(selected_level).unwrap().best_time() {
+        match level.best_time() {
             None => console.draw_text("XX:XX.XXX"),
             Some(best_time) => {
+                set_hints_color(level.best_time_hints());
                 console.draw_text(format!(
                     "{:02}:{:02}.{:03}",
                     best_time/60000,
                     (best_time%60000)/1000,
                     best_time%1000
                 ));
+                draw_hints_marker(level.best_time_hints());
             },
         }
-        console.set_cursor_pos(1, y + 3);
-        console.draw_text("Best moves    :      ");
-        match game_state.get_current_level_pack().as_ref().unwrap().levels().get(selected_level).unwrap().best_moves() {
-            None => console.draw_text("XXXX"),
-            Some(best_moves) => {
-                console.draw_text(format!("{:04}", best_moves));
-            },
+
+        //The score which is used for the ranking is drawn first
+        let scores = [
+            ("Best moves    :      ", level.best_moves(), level.best_moves_hints()),
+            ("Best pushes   :      ", level.best_pushes(), level.best_pushes_hints()),
+        ];
+        let scores = match game_state.settings().ranking() {
+            Ranking::Moves => scores,
+            Ranking::Pushes => [scores[1], scores[0]],
+        };
+        for (i, (label, best_score, hints)) in scores.into_iter().
+                enumerate() {
+            console.set_cursor_pos(1 + 31 * i, y + 3);
+            console.draw_text(label);
+            match best_score {
+                None => console.draw_text("XXXX"),
+                Some(best_score) => {
+                    set_hints_color(hints);
+                    console.draw_text(format!("{:04}", best_score));
+                    draw_hints_marker(hints);
+                },
+            }
         }
     }
 
@@ -533,12 +1059,27 @@ impl Screen for ScreenSelectLevel {
                     game_state.set_screen(ScreenId::InGame);
                 },
 
-                _ => {},
-            }
-        }
-    }
+                Key::S => {
+                    game_state.set_strict_mode(!game_state.is_strict_mode());
+                },
 
-    fn on_mouse_pressed(&mut self, game_state: &mut GameState, column: usize, row: usize) {
+                Key::R => {
+                    if game_state.get_current_level_pack().as_ref().unwrap().levels()[self.selected_level].best_solution().is_none() {
+                        game_state.open_dialog(Box::new(DialogOk::new_error("No solution was recorded for this level!")));
+
+                        break 'outer;
+                    }
+
+                    game_state.set_level_index(self.selected_level);
+                    game_state.set_screen(ScreenId::Replay);
+                },
+
+                _ => {},
+            }
+        }
+    }
+
+    fn on_mouse_pressed(&mut self, game_state: &mut GameState, column: usize, row: usize) {
         if row == 0 {
             return;
         }
@@ -562,7 +1103,39 @@ pub struct ScreenInGame {
     time_sec: u32,
     time_min: u32,
 
-    level: Option<UndoHistory<(Level, (usize, usize))>>,
+    level: Option<UndoHistory<GameplayState>>,
+    /// Count of pushes for each state in the undo history of the level
+    push_counts: UndoHistory<u32>,
+    /// Count of all moves and redone moves since the level was started (Undone moves are not subtracted)
+    total_actions: u32,
+    /// LURD characters of all moves from the start of the level, the moves after `lurd_move_count` were undone
+    lurd_moves: String,
+    lurd_move_count: usize,
+
+    deadlock_analyzer: Option<DeadlockAnalyzer>,
+    /// Positions of the boxes which can never reach a goal, only set if the current state is deadlocked
+    deadlocked_boxes: Vec<(usize, usize)>,
+
+    hint: Option<Hint>,
+    /// The running hint search, it is cancelled if the level state changes
+    hint_task: Option<SolverTask<Hint>>,
+    hints_used: u32,
+
+    /// Number of moves which were undone since the last move (For the undo limit)
+    undo_count: usize,
+    is_confirming_reset: bool,
+
+    animation: Option<MoveAnimation>,
+    /// Keys which were pressed while a move was animated, they are handled after the animation (At most
+    /// [ScreenInGame::MAX_QUEUED_KEYS] keys are queued, other keys are dropped so that held keys do not pile up)
+    queued_keys: VecDeque<Key>,
+    /// The remaining moves of a path to a clicked tile, they are made before queued keys are handled
+    queued_moves: VecDeque<Direction>,
+    /// The box which was clicked, it is pushed to the next clicked tile
+    selected_box: Option<(usize, usize)>,
+    /// The running search for the path which pushes the selected box to the clicked tile, it is cancelled by the next key
+    /// or click
+    push_path_task: Option<SolverTask<Option<Vec<Direction>>>>,
 
     continue_flag: bool,
     secret_found_flag: bool,
@@ -571,6 +1144,17 @@ pub struct ScreenInGame {
 
 impl ScreenInGame {
     pub const UNDO_HISTORY_SIZE_PLAYING: usize = 10000;
+    /// The maximal count of tiles of all states in the undo history while playing (Each state contains a copy of the level)
+    pub const UNDO_HISTORY_TILES_PLAYING: usize = 4_000_000;
+    pub const UNDO_HISTORY_SIZE_PLAYING_MIN: usize = 100;
+
+    const MAX_QUEUED_KEYS: usize = 2;
+
+    const HINT_MAX_NODES: usize = 500000;
+    const HINT_TIME_LIMIT: Duration = Duration::from_secs(5);
+
+    const PUSH_PATH_MAX_NODES: usize = 500000;
+    const PUSH_PATH_TIME_LIMIT: Duration = Duration::from_secs(1);
 
     pub fn new() -> Self {
         Self {
@@ -581,6 +1165,26 @@ impl ScreenInGame {
             time_min: Default::default(),
 
             level: Default::default(),
+            push_counts: UndoHistory::new(Self::UNDO_HISTORY_SIZE_PLAYING, 0),
+            total_actions: Default::default(),
+            lurd_moves: String::new(),
+            lurd_move_count: Default::default(),
+
+            deadlock_analyzer: Default::default(),
+            deadlocked_boxes: Default::default(),
+
+            hint: Default::default(),
+            hint_task: Default::default(),
+            hints_used: Default::default(),
+
+            undo_count: Default::default(),
+            is_confirming_reset: Default::default(),
+
+            animation: Default::default(),
+            queued_keys: Default::default(),
+            queued_moves: Default::default(),
+            selected_box: Default::default(),
+            push_path_task: Default::default(),
 
             continue_flag: Default::default(),
             secret_found_flag: Default::default(),
@@ -588,6 +1192,12 @@ impl ScreenInGame {
         }
     }
 
+    /// The size of the undo history while playing, it is smaller for large levels
+    pub fn undo_history_size_playing(level: &Level) -> usize {
+        (Self::UNDO_HISTORY_TILES_PLAYING / (level.width() * level.height())).
+                clamp(Self::UNDO_HISTORY_SIZE_PLAYING_MIN, Self::UNDO_HISTORY_SIZE_PLAYING)
+    }
+
     pub fn start_level(&mut self, level: &Level) {
         //Reset stats
         self.time_start = None;
@@ -595,30 +1205,418 @@ impl ScreenInGame {
         self.time_sec = 0;
         self.time_min = 0;
 
-        let level = level.clone();
-
         self.continue_flag = false;
         self.game_over_flag = false;
 
-        let mut player_pos = None;
+        self.hint = None;
+        self.hint_task = None;
+        self.hints_used = 0;
 
-        'outer:
-        for i in 0..level.width() {
-            for j in 0..level.height() {
-                if let Some(tile) = level.get_tile(i, j) {
-                    if *tile == Tile::Player {
-                        player_pos = Some((i, j));
+        self.undo_count = 0;
 
-                        break 'outer;
-                    }
+        self.animation = None;
+        self.queued_moves.clear();
+        self.selected_box = None;
+        self.push_path_task = None;
+
+        self.level = Some(UndoHistory::new(Self::undo_history_size_playing(level), GameplayState::new(level.clone()).unwrap()));
+        self.push_counts.clear_with_new_initial(0);
+        self.total_actions = 0;
+        self.lurd_moves.clear();
+        self.lurd_move_count = 0;
+
+        self.deadlock_analyzer = Some(DeadlockAnalyzer::new(level));
+        self.update_deadlocked_boxes();
+    }
+
+    fn reset_level(&mut self, level: &Level) {
+        //Hints and actions which were used before the reset still count for the best scores
+        let hints_used = self.hints_used;
+        let total_actions = self.total_actions;
+        self.start_level(level);
+        self.hints_used = hints_used;
+        self.total_actions = total_actions;
+    }
+
+    fn update_deadlocked_boxes(&mut self) {
+        let deadlock_analyzer = self.deadlock_analyzer.as_ref().unwrap();
+        let level = self.level.as_ref().unwrap().current().level();
+
+        self.deadlocked_boxes = if deadlock_analyzer.is_deadlocked(level) {
+            deadlock_analyzer.dead_boxes(level)
+        }else {
+            Vec::new()
+        };
+    }
+
+    /// Starts the search for a hint, the hint is shown once it was found
+    fn show_hint(&mut self, level_original: &Level) {
+        if self.hint_task.is_some() {
+            return;
+        }
+
+        let history = self.level.as_ref().unwrap();
+        let states = (0..=history.current_index()).
+                map(|index| history.get(index).unwrap().clone()).
+                collect::<Vec<_>>();
+
+        let level_original = level_original.clone();
+        self.hint = None;
+        self.hint_task = Some(SolverTask::spawn(
+            Solver::new(Self::HINT_MAX_NODES, Self::HINT_TIME_LIMIT),
+            move |solver| solver.hint(&level_original, &states),
+        ));
+    }
+
+    fn update_hint(&mut self) {
+        let Some(hint) = self.hint_task.as_ref().and_then(SolverTask::result) else {
+            return;
+        };
+        self.hint_task = None;
+
+        if hint != Hint::GaveUp {
+            self.hints_used += 1;
+        }
+
+        self.hint = Some(hint);
+    }
+
+    /// Makes the moves of the push path once it was found
+    fn update_push_path(&mut self) {
+        let Some(path) = self.push_path_task.as_ref().and_then(SolverTask::result) else {
+            return;
+        };
+        self.push_path_task = None;
+
+        self.queued_moves.extend(path.unwrap_or_default());
+    }
+
+    /// Returns the position of the tile which is marked by the current hint
+    fn hint_pos(&self) -> Option<(usize, usize)> {
+        let Some(Hint::Move { direction, pushed }) = self.hint else {
+            return None;
+        };
+
+        let state = self.level.as_ref().unwrap().current();
+
+        Some(pushed.unwrap_or_else(|| direction.next_pos(state.level().width(), state.level().height(), state.player_pos())))
+    }
+
+    /// Returns the position of the tile which is drawn inverted (The selected box or the tile of the current hint)
+    fn marked_area(&self) -> Option<TileArea> {
+        self.selected_box.or_else(|| self.hint_pos()).map(TileArea::from_pos)
+    }
+
+    /// The level is drawn below the status line in the whole console and scrolls with the player
+    fn level_view(game_state: &GameState, level: &Level, player_pos: (usize, usize)) -> LevelView {
+        let (width, height) = game_state.console_size();
+
+        LevelView::new(level, (width, height - 1), 1, game_state.settings().wide_tiles(), player_pos)
+    }
+
+    fn handle_key(&mut self, game_state: &mut GameState, key: Key) {
+        if key == Key::ESC && self.hint_task.is_some() {
+            self.hint_task = None;
+
+            return;
+        }
+
+        if key == Key::ESC {
+            if self.game_over_flag {
+                self.continue_flag = false;
+                self.game_over_flag = false;
+
+                game_state.set_screen(ScreenId::SelectLevel);
+
+                return;
+            }
+
+            self.time_start_in_menu = Some(SystemTime::now());
+
+            game_state.open_dialog(Box::new(DialogYesNo::new("Back to level selection?")));
+
+            return;
+        }
+
+        if key == Key::F1 {
+            self.time_start_in_menu = Some(SystemTime::now());
+
+            game_state.open_help_page();
+
+            return;
+        }
+
+        let action = game_state.key_bindings().action(&Action::GAME_SCREEN, key);
+        let direction = game_state.key_bindings().direction(Action::MOVE, key);
+
+        if action == Some(Action::Reset) && !self.continue_flag && game_state.settings().confirm_reset() {
+            self.time_start_in_menu = Some(SystemTime::now());
+            self.is_confirming_reset = true;
+
+            game_state.open_dialog(Box::new(DialogYesNo::new("Reset level?")));
+
+            return;
+        }
+
+        let undo_limit = game_state.settings().undo_limit();
+
+        let current_level_index = game_state.current_level_index;
+        let Some(level_pack) = game_state.get_current_level_pack_mut() else {
+            return;
+        };
+
+        //Level end
+        if self.continue_flag {
+            if key == Key::ENTER {
+                self.continue_flag = false;
+
+                //All levels completed
+                if current_level_index + 1 == level_pack.level_count() {
+                    self.game_over_flag = true;
+
+                    return;
+                }else {
+                    game_state.current_level_index += 1;
                 }
+
+                self.start_level(game_state.get_current_level_pack().unwrap().levels()[game_state.current_level_index].level());
+            }else if action == Some(Action::Reset) {
+                self.start_level(level_pack.levels()[current_level_index].level());
+            }
+
+            return;
+        }
+
+        if action == Some(Action::Undo) && (undo_limit == 0 || self.undo_count < undo_limit) {
+            if self.level.as_mut().unwrap().undo().is_some() {
+                self.push_counts.undo();
+                self.lurd_move_count -= 1;
+                self.undo_count += 1;
+            }
+
+            self.animation = None;
+            self.hint = None;
+            self.hint_task = None;
+            self.selected_box = None;
+            self.update_deadlocked_boxes();
+        }else if action == Some(Action::Redo) {
+            if self.level.as_mut().unwrap().redo().is_some() {
+                self.push_counts.redo();
+                self.lurd_move_count += 1;
+                self.total_actions += 1;
+                self.undo_count = self.undo_count.saturating_sub(1);
+            }
+
+            self.animation = None;
+            self.hint = None;
+            self.hint_task = None;
+            self.selected_box = None;
+            self.update_deadlocked_boxes();
+        }
+
+        if action == Some(Action::Hint) {
+            self.show_hint(level_pack.levels()[current_level_index].level());
+        }
+
+        //Reset
+        if action == Some(Action::Reset) {
+            self.reset_level(level_pack.levels()[current_level_index].level());
+        }
+
+        if let Some(direction) = direction {
+            self.move_player(game_state, direction);
+        }
+    }
+
+    /// Moves the player one tile and updates the best scores if the level was completed
+    fn move_player(&mut self, game_state: &mut GameState, direction: Direction) {
+        let animations = game_state.settings().animations();
+        let ranking = game_state.settings().ranking();
+        let is_strict_mode = game_state.is_strict_mode();
+
+        let current_level_index = game_state.current_level_index;
+        let Some(level_pack) = game_state.get_current_level_pack_mut() else {
+            return;
+        };
+
+        let level_original = level_pack.levels()[current_level_index].level();
+        let outcome = self.level.as_ref().unwrap().current().apply_move(level_original, direction);
+
+        self.time_start.get_or_insert_with(SystemTime::now);
+        self.hint = None;
+        self.hint_task = None;
+        self.selected_box = None;
+
+        if outcome.found_secret() {
+            self.game_over_flag = true;
+            self.secret_found_flag = true;
+        }
+
+        //The rest of a clicked path is not made if the player did not move or the level has ended
+        let has_won = outcome.has_won();
+        if !outcome.has_moved() || has_won || self.game_over_flag {
+            self.queued_moves.clear();
+        }
+
+        if outcome.has_moved() {
+            self.animation = animations.then(|| MoveAnimation::new(level_original, self.level.as_ref().unwrap().current(), direction, &outcome));
+
+            self.push_counts.commit_change(self.push_counts.current() + outcome.pushed().is_some() as u32);
+            self.total_actions += 1;
+            self.lurd_moves.truncate(self.lurd_move_count);
+            self.lurd_moves.push(direction.to_lurd(outcome.pushed().is_some()));
+            self.lurd_move_count += 1;
+            self.level.as_mut().unwrap().commit_change(outcome.into_state());
+            self.undo_count = 0;
+            self.update_deadlocked_boxes();
+        }
+
+        if has_won {
+            self.continue_flag = true;
+
+            //Update best scores
+            let time = self.time_millis as u64 + 1000 * self.time_sec as u64 + 60000 * self.time_min as u64;
+            let moves = self.lurd_move_count as u32;
+            let pushes = *self.push_counts.current();
+            let actions = is_strict_mode.then_some(self.total_actions);
+
+            level_pack.update_stats(current_level_index, time, moves, pushes, actions, self.hints_used);
+
+            level_pack.update_best_solution(current_level_index, &self.lurd_moves[..self.lurd_move_count], ranking);
+
+            if current_level_index >= level_pack.min_level_not_completed() {
+                level_pack.set_min_level_not_completed(current_level_index + 1);
+            }
+
+            if let Err(err) = level_pack.save_save_game() {
+                game_state.open_dialog(Box::new(DialogOk::new_error(format!("Cannot save: {}", err))));
+            }
+        }
+
+        if self.secret_found_flag {
+            game_state.open_dialog(Box::new(DialogOk::new("You have found a secret!")));
+
+            if let Err(err) = game_state.on_found_secret() {
+                game_state.open_dialog(Box::new(DialogOk::new_error(format!("Error: {}", err))));
+            }
+        }
+    }
+
+    /// Advances the animation and makes the queued moves and handles the queued keys after the current move was animated
+    fn update_animation(&mut self, game_state: &mut GameState) {
+        if let Some(ref mut animation) = self.animation {
+            if !animation.update() {
+                self.animation = None;
             }
         }
 
-        self.level = Some(UndoHistory::new(Self::UNDO_HISTORY_SIZE_PLAYING, (level, player_pos.unwrap())));
+        while !self.animation.as_ref().is_some_and(MoveAnimation::is_moving) && !game_state.is_dialog_opened() {
+            if let Some(direction) = self.queued_moves.pop_front() {
+                self.move_player(game_state, direction);
+
+                continue;
+            }
+
+            let Some(key) = self.queued_keys.pop_front() else {
+                break;
+            };
+
+            self.handle_key(game_state, key);
+        }
     }
 
-    fn draw_tutorial_level_text(&self, game_state: &GameState, console: &Console) {
+    fn draw_status_text(&self, console: &Canvas, theme: &Theme, is_strict_mode: bool, y: usize) {
+        if self.continue_flag || self.game_over_flag {
+            let text = if !self.game_over_flag {
+                "Level completed!"
+            }else if self.secret_found_flag {
+                "Secret found!"
+            }else {
+                "You have won!"
+            };
+            let text = format!(
+                "{text} (Moves: {}, Total actions: {}{})",
+                self.lurd_move_count,
+                self.total_actions,
+                if is_strict_mode { ", Strict mode" } else { "" },
+            );
+
+            console.set_cursor_pos(((console.width().saturating_sub(text.len())) as f64 * 0.5) as usize, y);
+            console.draw_text(text);
+
+            return;
+        }
+
+        if self.push_path_task.is_some() {
+            let text = "Searching for a path... (ESC: Cancel)";
+
+            theme.set_color(console, ThemeElement::HintText);
+            console.set_cursor_pos(((console.width().saturating_sub(text.len())) as f64 * 0.5) as usize, y);
+            console.draw_text(text);
+            console.reset_color();
+
+            return;
+        }
+
+        if self.hint_task.is_some() {
+            let text = "Searching for a hint... (ESC: Cancel)";
+
+            theme.set_color(console, ThemeElement::HintText);
+            console.set_cursor_pos(((console.width().saturating_sub(text.len())) as f64 * 0.5) as usize, y);
+            console.draw_text(text);
+            console.reset_color();
+
+            return;
+        }
+
+        let Some(ref hint) = self.hint else {
+            if !self.deadlocked_boxes.is_empty() {
+                let text = "Deadlock - undo or reset";
+
+                theme.set_color(console, ThemeElement::DeadlockText);
+                console.set_cursor_pos(((console.width().saturating_sub(text.len())) as f64 * 0.5) as usize, y);
+                console.draw_text(text);
+                console.reset_color();
+            }
+
+            return;
+        };
+
+        let direction_to_str = |direction: &Direction| match direction {
+            Direction::Left => "left",
+            Direction::Up => "up",
+            Direction::Right => "right",
+            Direction::Down => "down",
+        };
+
+        let text = match hint {
+            Hint::Move { direction, pushed: Some(pushed) } => {
+                let state = self.level.as_ref().unwrap().current();
+                let object = if matches!(state.level().get_tile(pushed.0, pushed.1), Some(Tile::Key | Tile::KeyInGoal)) {
+                    "key"
+                }else {
+                    "box"
+                };
+
+                format!("Hint: Push the marked {object} {}", direction_to_str(direction))
+            },
+            Hint::Move { direction, pushed: None } => format!("Hint: Move {} to the marked tile", direction_to_str(direction)),
+            Hint::Undo(1) => "Not solvable anymore: Undo 1 move".to_string(),
+            Hint::Undo(undo_count) => format!("Not solvable anymore: Undo {undo_count} moves"),
+            Hint::Reset => "Not solvable anymore: Reset the level".to_string(),
+            Hint::GaveUp => "No hint found (Level is too complex)".to_string(),
+        };
+
+        theme.set_color(console, ThemeElement::HintText);
+        console.set_cursor_pos(((console.width().saturating_sub(text.len())) as f64 * 0.5) as usize, y);
+        console.draw_text(text);
+        console.reset_color();
+    }
+
+    fn draw_tutorial_level_text(&self, game_state: &GameState, console: &Canvas) {
+        //The texts are laid out for the minimal console width (Levels are centered horizontally)
+        let console = &console.centered_area(Game::CONSOLE_MIN_WIDTH, console.height());
+
         //Draw special help text for tutorial levels (tutorial pack and tutorial levels in special pack)
         if game_state.get_level_pack_index() == 0 { //Tutorial pack
             console.reset_color();
@@ -642,8 +1640,7 @@ impl ScreenInGame {
                     console.set_cursor_pos(16, 8);
                     console.draw_text("Boxes (");
 
-                    console.set_color(Color::LightCyan, Color::Default);
-                    console.draw_text("@");
+                    Tile::Box.draw(console, game_state.theme(), false, false, false);
 
                     console.reset_color();
                     console.draw_text(") must be placed on ");
@@ -654,8 +1651,7 @@ impl ScreenInGame {
                     console.reset_color();
                     console.draw_text(" goals (");
 
-                    console.set_color(Color::Red, Color::Default);
-                    console.draw_text("x");
+                    Tile::Goal.draw(console, game_state.theme(), false, false, false);
 
                     console.reset_color();
                     console.draw_text(")");
@@ -664,14 +1660,12 @@ impl ScreenInGame {
                     console.set_cursor_pos(14, 8);
                     console.draw_text("Some boxes (");
 
-                    console.set_color(Color::LightPink, Color::Default);
-                    console.draw_text("@");
+                    Tile::BoxInGoal.draw(console, game_state.theme(), false, false, false);
 
                     console.reset_color();
                     console.draw_text(") might already be in a goal (");
 
-                    console.set_color(Color::Red, Color::Default);
-                    console.draw_text("x");
+                    Tile::Goal.draw(console, game_state.theme(), false, false, false);
 
                     console.reset_color();
                     console.draw_text(")");
@@ -680,14 +1674,12 @@ impl ScreenInGame {
                     console.set_cursor_pos(14, 8);
                     console.draw_text("Not all boxes (");
 
-                    console.set_color(Color::LightCyan, Color::Default);
-                    console.draw_text("@");
+                    Tile::Box.draw(console, game_state.theme(), false, false, false);
 
                     console.reset_color();
                     console.draw_text(") must be in a goal (");
 
-                    console.set_color(Color::Red, Color::Default);
-                    console.draw_text("x");
+                    Tile::Goal.draw(console, game_state.theme(), false, false, false);
 
                     console.reset_color();
                     console.draw_text(") to win");
@@ -696,8 +1688,7 @@ impl ScreenInGame {
                     console.set_cursor_pos(5, 8);
                     console.draw_text("One-way doors (");
 
-                    console.set_color(Color::Blue, Color::Default);
-                    console.draw_text("< ^ > v");
+                    Tile::draw_one_way_doors(console, game_state.theme());
 
                     console.reset_color();
                     console.draw_text(") can only be entered from the opened side");
@@ -716,14 +1707,12 @@ impl ScreenInGame {
                         console.set_cursor_pos(8, 8);
                         console.draw_text("Boxes (");
 
-                        console.set_color(Color::LightCyan, Color::Default);
-                        console.draw_text("@");
+                        Tile::Box.draw(console, game_state.theme(), false, false, false);
 
                         console.reset_color();
                         console.draw_text(") cannot be moved through one-way doors (");
 
-                        console.set_color(Color::Blue, Color::Default);
-                        console.draw_text("< ^ > v");
+                        Tile::draw_one_way_doors(console, game_state.theme());
 
                         console.reset_color();
                         console.draw_text(")");
@@ -738,14 +1727,12 @@ impl ScreenInGame {
                     console.set_cursor_pos(18, 8);
                     console.draw_text("Keys (");
 
-                    console.set_color(Color::LightCyan, Color::Default);
-                    console.draw_text("*");
+                    Tile::Key.draw(console, game_state.theme(), false, false, false);
 
                     console.reset_color();
                     console.draw_text(") can be used to open doors (");
 
-                    console.set_color(Color::Red, Color::Default);
-                    console.draw_text("=");
+                    Tile::LockedDoor.draw(console, game_state.theme(), false, false, false);
 
                     console.reset_color();
                     console.draw_text(")");
@@ -754,14 +1741,12 @@ impl ScreenInGame {
                     console.set_cursor_pos(19, 8);
                     console.draw_text("Every key (");
 
-                    console.set_color(Color::LightCyan, Color::Default);
-                    console.draw_text("*");
+                    Tile::Key.draw(console, game_state.theme(), false, false, false);
 
                     console.reset_color();
                     console.draw_text(") can open any door (");
 
-                    console.set_color(Color::Red, Color::Default);
-                    console.draw_text("=");
+                    Tile::LockedDoor.draw(console, game_state.theme(), false, false, false);
 
                     console.reset_color();
                     console.draw_text(")");
@@ -770,14 +1755,12 @@ impl ScreenInGame {
                     console.set_cursor_pos(21, 8);
                     console.draw_text("Keys (");
 
-                    console.set_color(Color::LightPink, Color::Default);
-                    console.draw_text("*");
+                    Tile::KeyInGoal.draw(console, game_state.theme(), false, false, false);
 
                     console.reset_color();
                     console.draw_text(") might be in a goal (");
 
-                    console.set_color(Color::Red, Color::Default);
-                    console.draw_text("x");
+                    Tile::Goal.draw(console, game_state.theme(), false, false, false);
 
                     console.reset_color();
                     console.draw_text(")");
@@ -786,8 +1769,7 @@ impl ScreenInGame {
                     console.set_cursor_pos(23, 8);
                     console.draw_text("Holes (");
 
-                    console.set_color(Color::LightBlue, Color::Default);
-                    console.draw_text("O");
+                    Tile::Hole.draw(console, game_state.theme(), false, false, false);
 
                     console.reset_color();
                     console.draw_text(") cannot be crossed");
@@ -796,8 +1778,7 @@ impl ScreenInGame {
                     console.set_cursor_pos(21, 8);
                     console.draw_text("Filled holes (");
 
-                    console.set_color(Color::Default, Color::LightBlue);
-                    console.draw_text("@");
+                    Tile::BoxInHole.draw(console, game_state.theme(), false, false, false);
 
                     console.reset_color();
                     console.draw_text(") can be crossed");
@@ -806,14 +1787,12 @@ impl ScreenInGame {
                     console.set_cursor_pos(23, 8);
                     console.draw_text("Boxes (");
 
-                    console.set_color(Color::LightCyan, Color::Default);
-                    console.draw_text("@");
+                    Tile::Box.draw(console, game_state.theme(), false, false, false);
 
                     console.reset_color();
                     console.draw_text(") can fill holes (");
 
-                    console.set_color(Color::LightBlue, Color::Default);
-                    console.draw_text("O");
+                    Tile::Hole.draw(console, game_state.theme(), false, false, false);
 
                     console.reset_color();
                     console.draw_text(")");
@@ -822,14 +1801,12 @@ impl ScreenInGame {
                     console.set_cursor_pos(13, 8);
                     console.draw_text("Keys (");
 
-                    console.set_color(Color::LightCyan, Color::Default);
-                    console.draw_text("*");
+                    Tile::Key.draw(console, game_state.theme(), false, false, false);
 
                     console.reset_color();
                     console.draw_text(") cannot fill holes (");
 
-                    console.set_color(Color::LightBlue, Color::Default);
-                    console.draw_text("O");
+                    Tile::Hole.draw(console, game_state.theme(), false, false, false);
 
                     console.reset_color();
                     console.draw_text(") and will be lost");
@@ -841,52 +1818,69 @@ impl ScreenInGame {
 }
 
 impl Screen for ScreenInGame {
-    fn draw(&self, game_state: &GameState, console: &Console) {
+    fn draw(&self, game_state: &GameState, console: &Canvas) {
         console.reset_color();
         console.draw_text(format!("Pack: {:02}", game_state.get_level_pack_index() + 1));
 
-        console.set_cursor_pos(((Game::CONSOLE_MIN_WIDTH - 9) as f64 * 0.25) as usize, 0);
+        console.set_cursor_pos(((console.width() - 9) as f64 * 0.25) as usize, 0);
         console.draw_text("Level: ");
         console.draw_text(utils::number_to_string_leading_ascii(2, game_state.current_level_index as u32 + 1, true));
 
-        console.set_cursor_pos(((Game::CONSOLE_MIN_WIDTH - 11) as f64 * 0.75) as usize, 0);
-        console.draw_text(format!("Moves: {:04}", self.level.as_ref().unwrap().current_index()));
-
-        console.set_cursor_pos(Game::CONSOLE_MIN_WIDTH - 15, 0);
-        console.draw_text(format!(
-            "Time: {:02}:{:02}.{:03}",
-            self.time_min,
-            self.time_sec,
-            self.time_millis,
-        ));
-
-        if self.continue_flag {
-            console.set_cursor_pos(((Game::CONSOLE_MIN_WIDTH - 16) as f64 * 0.5) as usize, 0);
-            console.draw_text("Level completed!");
+        //The score which is used for the ranking is drawn first
+        let moves = format!("Moves: {:04}", self.lurd_move_count);
+        let pushes = format!("Pushes: {:04}", self.push_counts.current());
+        let scores = match game_state.settings().ranking() {
+            Ranking::Moves => [moves, pushes],
+            Ranking::Pushes => [pushes, moves],
+        };
+        for (i, score) in scores.into_iter().
+                enumerate() {
+            console.set_cursor_pos(console.width() / 2 - 8 + 14 * i, 0);
+            console.draw_text(score);
         }
 
-        if self.game_over_flag {
-            if self.secret_found_flag {
-                console.set_cursor_pos(((Game::CONSOLE_MIN_WIDTH - 13) as f64 * 0.5) as usize, 0);
-                console.draw_text("Secret found!");
-            }else {
-                console.set_cursor_pos(((Game::CONSOLE_MIN_WIDTH - 13) as f64 * 0.5) as usize, 0);
-                console.draw_text("You have won!");
-            }
+        if game_state.settings().show_timer() {
+            console.set_cursor_pos(console.width() - 15, 0);
+            console.draw_text(format!(
+                "Time: {:02}:{:02}.{:03}",
+                self.time_min,
+                self.time_sec,
+                self.time_millis,
+            ));
         }
 
-        if let Some(level) = self.level.as_ref().map(|level| &level.current().0) {
-            let x_offset = ((Game::CONSOLE_MIN_WIDTH - level.width()) as f64 * 0.5) as usize;
-            let y_offset = 1;
+        if let Some(state) = self.level.as_ref().map(|level| level.current()) {
+            let level = state.level();
+            let view = Self::level_view(game_state, level, state.player_pos());
 
-            level.draw(console, x_offset, y_offset, game_state.is_player_background(), None);
+            if let Some(ref animation) = self.animation {
+                animation.level_without_moving_tiles(level).draw(
+                    console, game_state.theme(), view, game_state.is_player_background(), self.marked_area(), &self.deadlocked_boxes,
+                );
+                animation.draw(console, game_state.theme(), view, game_state.is_player_background());
+            }else {
+                level.draw(console, game_state.theme(), view, game_state.is_player_background(), self.marked_area(), &self.deadlocked_boxes);
+            }
 
             self.draw_tutorial_level_text(game_state, console);
+            self.draw_status_text(console, game_state.theme(), game_state.is_strict_mode(), (view.y_offset() + view.visible_height()).min(console.height() - 1));
         }
     }
 
+    fn is_full_size(&self) -> bool {
+        true
+    }
+
     fn update(&mut self, game_state: &mut GameState) {
-        if game_state.is_dialog_opened() || self.game_over_flag || self.continue_flag {
+        if game_state.is_dialog_opened() {
+            return;
+        }
+
+        self.update_animation(game_state);
+        self.update_hint();
+        self.update_push_path();
+
+        if self.game_over_flag || self.continue_flag {
             return;
         }
 
@@ -909,215 +1903,339 @@ impl Screen for ScreenInGame {
         }
     }
 
-    fn on_key_pressed(&mut self, game_state: &mut GameState, key: Key) {
-        if key == Key::ESC {
-            if self.game_over_flag {
-                self.continue_flag = false;
-                self.game_over_flag = false;
+    fn on_key_pressed(&mut self, game_state: &mut GameState, key: Key) {
+        //Every key cancels the search for a push path, ESC only cancels it
+        if self.push_path_task.take().is_some() && key == Key::ESC {
+            return;
+        }
+
+        let is_moving = self.animation.as_ref().is_some_and(MoveAnimation::is_moving);
+        let is_queuing = is_moving || !self.queued_moves.is_empty() || !self.queued_keys.is_empty();
+        if is_queuing && key != Key::ESC && key != Key::F1 {
+            if self.queued_keys.len() < Self::MAX_QUEUED_KEYS {
+                self.queued_keys.push_back(key);
+            }
+
+            return;
+        }
+
+        self.handle_key(game_state, key);
+    }
+
+    fn on_mouse_pressed(&mut self, game_state: &mut GameState, column: usize, row: usize) {
+        if self.continue_flag || self.game_over_flag || !self.queued_moves.is_empty() || !self.queued_keys.is_empty() {
+            return;
+        }
+
+        self.push_path_task = None;
+
+        let Some(state) = self.level.as_ref().map(|level| level.current()) else {
+            return;
+        };
+
+        let Some(pos) = Self::level_view(game_state, state.level(), state.player_pos()).tile_pos(column, row) else {
+            return;
+        };
+
+        let is_box = matches!(state.level().get_tile(pos.0, pos.1), Some(Tile::Box | Tile::BoxInGoal));
+        let path = match self.selected_box.take() {
+            //Clicking the selected box again deselects it
+            Some(box_pos) if box_pos == pos => None,
+            _ if is_box => {
+                self.selected_box = Some(pos);
+
+                None
+            },
+
+            //The push path is searched in the background, the moves are made once it was found
+            Some(box_pos) => {
+                let level_original = game_state.get_current_level_pack().unwrap().levels()[game_state.current_level_index].level().clone();
+                let state = state.clone();
+
+                self.push_path_task = Some(SolverTask::spawn(
+                    Solver::new(Self::PUSH_PATH_MAX_NODES, Self::PUSH_PATH_TIME_LIMIT),
+                    move |solver| solver.find_push_path(&level_original, &state, box_pos, pos),
+                ));
+
+                None
+            },
+            None => Solver::find_walk_path(state, pos),
+        };
+
+        self.queued_moves.extend(path.unwrap_or_default());
+    }
+
+    fn on_dialog_selection(&mut self, game_state: &mut GameState, selection: DialogSelection) {
+        if self.secret_found_flag {
+            self.continue_flag = false;
+            self.game_over_flag = false;
+            self.secret_found_flag = false;
+
+            game_state.set_screen(ScreenId::SelectLevelPack);
+
+            return;
+        }
+
+        if self.is_confirming_reset {
+            self.is_confirming_reset = false;
+
+            if selection == DialogSelection::Yes {
+                self.reset_level(game_state.get_current_level_pack().unwrap().levels()[game_state.current_level_index].level());
+            }else {
+                self.on_continue(game_state);
+            }
+
+            return;
+        }
+
+        if selection == DialogSelection::Yes {
+            self.continue_flag = false;
+            self.game_over_flag = false;
+
+            game_state.set_screen(ScreenId::SelectLevel);
+        }else if selection == DialogSelection::No {
+            self.on_continue(game_state);
+        }
+    }
+
+    fn on_pause(&mut self, _: &mut GameState) {
+        self.time_start_in_menu = Some(SystemTime::now());
+    }
+
+    fn on_continue(&mut self, _: &mut GameState) {
+        if self.game_over_flag || self.continue_flag || self.time_start.is_none() || self.time_start_in_menu.is_none() {
+            return;
+        }
+
+        let diff = SystemTime::now().duration_since(self.time_start_in_menu.take().unwrap()).
+                expect("Time manipulation detected (Start time is in the future)!");
+
+        self.time_start = self.time_start.map(|time_start| time_start + diff);
+    }
+
+    fn on_set_screen(&mut self, game_state: &mut GameState) {
+        self.queued_keys.clear();
 
-                game_state.set_screen(ScreenId::SelectLevel);
+        self.start_level(game_state.get_current_level_pack().as_ref().unwrap().levels().get(
+            game_state.get_level_index()).unwrap().level());
+    }
+}
 
-                return;
-            }
+pub struct ScreenReplay {
+    level: Option<UndoHistory<GameplayState>>,
+    moves: Vec<(Direction, bool)>,
+    /// Index of the next move which is replayed
+    move_index: usize,
 
-            self.time_start_in_menu = Some(SystemTime::now());
+    is_paused: bool,
+    speed: usize,
+    update_count: u32,
 
-            game_state.open_dialog(Box::new(DialogYesNo::new("Back to level selection?")));
+    /// Index of the first move which could not be replayed
+    invalid_move_index: Option<usize>,
+    has_won: bool,
+}
 
-            return;
-        }
+impl ScreenReplay {
+    /// Count of updates between two moves for each speed (25 updates per second)
+    const STEP_DELAYS: [u32; 5] = [25, 12, 6, 3, 1];
 
-        if key == Key::F1 {
-            self.time_start_in_menu = Some(SystemTime::now());
+    pub fn new() -> Self {
+        Self {
+            level: Default::default(),
+            moves: Default::default(),
+            move_index: Default::default(),
 
-            game_state.open_help_page();
+            is_paused: Default::default(),
+            speed: 2,
+            update_count: Default::default(),
 
-            return;
+            invalid_move_index: Default::default(),
+            has_won: Default::default(),
         }
+    }
 
-        let current_level_index = game_state.current_level_index;
-        let Some(level_pack) = game_state.get_current_level_pack_mut() else {
-            return;
-        };
+    fn start_replay(&mut self, level: &Level, solution: &str) {
+        let undo_history_size = ScreenInGame::undo_history_size_playing(level).min(solution.len() + 1);
+        self.level = Some(UndoHistory::new(undo_history_size, GameplayState::new(level.clone()).unwrap()));
+        self.moves = solution.chars().
+                filter_map(Direction::from_lurd).
+                collect();
+        self.move_index = 0;
 
-        //Level end
-        if self.continue_flag {
-            if key == Key::ENTER {
-                self.continue_flag = false;
+        self.is_paused = false;
+        self.update_count = 0;
 
-                //All levels completed
-                if current_level_index + 1 == level_pack.level_count() {
-                    self.game_over_flag = true;
+        self.invalid_move_index = None;
+        self.has_won = false;
+    }
 
-                    return;
-                }else {
-                    game_state.current_level_index += 1;
-                }
+    fn step_forward(&mut self, level_original: &Level) {
+        let level = self.level.as_mut().unwrap();
+        let move_index = self.move_index;
+        if self.has_won || self.invalid_move_index.is_some() || move_index == self.moves.len() {
+            return;
+        }
 
-                self.start_level(game_state.get_current_level_pack().unwrap().levels()[game_state.current_level_index].level());
-            }else if key == Key::R {
-                self.start_level(level_pack.levels()[current_level_index].level());
-            }
+        let (direction, is_push) = self.moves[move_index];
+        let outcome = level.current().apply_move(level_original, direction);
+
+        //Walks and pushes must match the recorded solution
+        if !outcome.has_moved() || outcome.pushed().is_some() != is_push {
+            self.invalid_move_index = Some(move_index);
+            self.is_paused = true;
 
             return;
         }
 
-        if key == Key::Z {
-            self.level.as_mut().unwrap().undo();
-        }else if key == Key::Y {
-            self.level.as_mut().unwrap().redo();
-        }
+        self.has_won = outcome.has_won();
+        level.commit_change(outcome.into_state());
+        self.move_index += 1;
+    }
 
-        //Reset
-        if key == Key::R {
-            self.start_level(level_pack.levels()[current_level_index].level());
+    fn step_backward(&mut self) {
+        if self.level.as_mut().unwrap().undo().is_some() {
+            self.move_index -= 1;
+            self.invalid_move_index = None;
+            self.has_won = false;
         }
+    }
+}
 
-        if key.is_arrow_key() {
-            let (mut level, mut player_pos) = self.level.as_ref().unwrap().current().clone();
-
-            let width = level.width();
-            let height = level.height();
+impl Screen for ScreenReplay {
+    fn draw(&self, game_state: &GameState, console: &Canvas) {
+        let Some(level) = self.level.as_ref() else {
+            return;
+        };
+        let move_index = self.move_index;
 
-            let (x_from, y_from) = player_pos;
+        console.reset_color();
+        console.draw_text(format!("Pack: {:02}", game_state.get_level_pack_index() + 1));
 
-            let x_to = match key {
-                Key::LEFT => if x_from == 0 {
-                    width - 1
-                }else {
-                    x_from - 1
-                },
-                Key::RIGHT => if x_from == width - 1 {
-                    0
-                }else {
-                    x_from + 1
-                },
-                _ => x_from,
-            };
-            let y_to = match key {
-                Key::UP => if y_from == 0 {
-                    height - 1
-                }else {
-                    y_from - 1
-                },
-                Key::DOWN => if y_from == height - 1 {
-                    0
-                }else {
-                    y_from + 1
-                },
-                _ => y_from,
-            };
+        console.set_cursor_pos(((console.width() - 9) as f64 * 0.25) as usize, 0);
+        console.draw_text("Level: ");
+        console.draw_text(utils::number_to_string_leading_ascii(2, game_state.current_level_index as u32 + 1, true));
 
-            let one_way_door_tile = match key {
-                Key::LEFT => Tile::OneWayLeft,
-                Key::UP => Tile::OneWayUp,
-                Key::RIGHT => Tile::OneWayRight,
-                Key::DOWN => Tile::OneWayDown,
-                _ => return, //Should never happen
-            };
+        console.set_cursor_pos(((console.width() - 11) as f64 * 0.75) as usize, 0);
+        console.draw_text(format!("Moves: {:04}/{:04}", move_index, self.moves.len()));
 
-            //Set players old position to old level data
-            let mut tile = level_pack.levels()[current_level_index].level().get_tile(x_from, y_from).unwrap().clone();
-            if tile == Tile::Player || tile == Tile::Box || tile == Tile::Key || tile == Tile::LockedDoor {
-                tile = Tile::Empty;
-            }else if tile == Tile::BoxInGoal || tile == Tile::KeyInGoal {
-                tile = Tile::Goal;
-            }else if tile == Tile::Hole || tile == Tile::BoxInHole {
-                tile = Tile::BoxInHole;
-            }
+        console.set_cursor_pos(console.width() - 10, 0);
+        console.draw_text(format!("Speed: {}/{}", self.speed + 1, Self::STEP_DELAYS.len()));
 
-            level.set_tile(x_from, y_from, tile);
+        if self.has_won {
+            console.set_cursor_pos(((console.width() - 16) as f64 * 0.5) as usize, 0);
+            console.draw_text("Level completed!");
+        }else if self.is_paused {
+            console.set_cursor_pos(((console.width() - 6) as f64 * 0.5) as usize, 0);
+            console.draw_text("Paused");
+        }
 
-            self.time_start.get_or_insert_with(SystemTime::now);
+        let state = level.current();
+        let level = state.level();
+        let view = ScreenInGame::level_view(game_state, level, state.player_pos());
 
-            let mut has_won = false;
-            let tile = level.get_tile(x_to, y_to).unwrap().clone();
-            if matches!(tile, Tile::Empty | Tile::Goal | Tile::Secret | Tile::BoxInHole) || tile == one_way_door_tile ||
-                    matches!(tile, Tile::Box | Tile::BoxInGoal | Tile::Key | Tile::KeyInGoal if level.move_box_or_key(
-                        level_pack.levels().get(current_level_index).unwrap().level(), &mut has_won, x_from, y_from, x_to, y_to)) {
-                if tile == Tile::Secret {
-                    self.game_over_flag = true;
-                    self.secret_found_flag = true;
-                }
+        level.draw(console, game_state.theme(), view, game_state.is_player_background(), None, &[]);
 
-                player_pos = (x_to, y_to);
-            }
+        let text = if let Some(invalid_move_index) = self.invalid_move_index {
+            console.set_color(Color::Red, Color::Default);
 
-            //Set player to new position
-            level.set_tile(player_pos.0, player_pos.1, Tile::Player);
+            let (direction, is_push) = self.moves[invalid_move_index];
+            format!("Invalid solution: Move {} (\"{}\") is not possible", invalid_move_index + 1, direction.to_lurd(is_push))
+        }else if !self.has_won && move_index == self.moves.len() {
+            console.set_color(Color::Red, Color::Default);
 
-            if player_pos != (x_from, y_from) {
-                self.level.as_mut().unwrap().commit_change((level, player_pos));
-            }
+            "Invalid solution: The level was not completed".to_string()
+        }else {
+            console.reset_color();
 
-            if has_won {
-                self.continue_flag = true;
+            "SPACE: Pause, LEFT/RIGHT: Single step, +/-: Speed, r: Restart".to_string()
+        };
 
-                //Update best scores
-                let time = self.time_millis as u64 + 1000 * self.time_sec as u64 + 60000 * self.time_min as u64;
-                let moves = self.level.as_ref().unwrap().current_index() as u32;
+        console.set_cursor_pos(
+            ((console.width() - text.len()) as f64 * 0.5) as usize,
+            (view.y_offset() + view.visible_height()).min(console.height() - 1),
+        );
+        console.draw_text(text);
+        console.reset_color();
+    }
 
-                level_pack.update_stats(current_level_index, time, moves);
+    fn is_full_size(&self) -> bool {
+        true
+    }
 
-                if current_level_index >= level_pack.min_level_not_completed() {
-                    level_pack.set_min_level_not_completed(current_level_index + 1);
-                }
+    fn update(&mut self, game_state: &mut GameState) {
+        if game_state.is_dialog_opened() || self.is_paused {
+            return;
+        }
 
-                if let Err(err) = level_pack.save_save_game() {
-                    game_state.open_dialog(Box::new(DialogOk::new_error(format!("Cannot save: {}", err))));
-                }
-            }
+        self.update_count += 1;
+        if self.update_count < Self::STEP_DELAYS[self.speed] {
+            return;
+        }
+        self.update_count = 0;
 
-            if self.secret_found_flag {
-                game_state.open_dialog(Box::new(DialogOk::new("You have found a secret!")));
+        let Some(level_pack) = game_state.get_current_level_pack() else {
+            return;
+        };
 
-                if let Err(err) = game_state.on_found_secret() {
-                    game_state.open_dialog(Box::new(DialogOk::new_error(format!("Error: {}", err))));
-                }
-            }
-        }
+        self.step_forward(level_pack.levels()[game_state.current_level_index].level());
     }
 
-    fn on_dialog_selection(&mut self, game_state: &mut GameState, selection: DialogSelection) {
-        if self.secret_found_flag {
-            self.continue_flag = false;
-            self.game_over_flag = false;
-            self.secret_found_flag = false;
-
-            game_state.set_screen(ScreenId::SelectLevelPack);
+    fn on_key_pressed(&mut self, game_state: &mut GameState, key: Key) {
+        if key == Key::ESC {
+            game_state.set_screen(ScreenId::SelectLevel);
 
             return;
         }
 
-        if selection == DialogSelection::Yes {
-            self.continue_flag = false;
-            self.game_over_flag = false;
+        if key == Key::F1 {
+            game_state.open_help_page();
 
-            game_state.set_screen(ScreenId::SelectLevel);
-        }else if selection == DialogSelection::No {
-            self.on_continue(game_state);
+            return;
         }
-    }
 
-    fn on_continue(&mut self, _: &mut GameState) {
-        if self.game_over_flag || self.continue_flag || self.time_start.is_none() || self.time_start_in_menu.is_none() {
+        let current_level_index = game_state.current_level_index;
+        let Some(level_pack) = game_state.get_current_level_pack() else {
             return;
-        }
+        };
+        let level_with_stats = &level_pack.levels()[current_level_index];
 
-        let diff = SystemTime::now().duration_since(self.time_start_in_menu.take().unwrap()).
-                expect("Time manipulation detected (Start time is in the future)!");
+        match key {
+            Key::SPACE => self.is_paused = !self.is_paused,
 
-        self.time_start = self.time_start.map(|time_start| time_start + diff);
+            Key::RIGHT => {
+                self.is_paused = true;
+                self.step_forward(level_with_stats.level());
+            },
+            Key::LEFT => {
+                self.is_paused = true;
+                self.step_backward();
+            },
+
+            Key::PLUS if self.speed + 1 < Self::STEP_DELAYS.len() => self.speed += 1,
+            Key::MINUS if self.speed > 0 => self.speed -= 1,
+
+            Key::R => {
+                if let Some(solution) = level_with_stats.best_solution() {
+                    self.start_replay(level_with_stats.level(), solution);
+                }
+            },
+
+            _ => {},
+        }
     }
 
     fn on_set_screen(&mut self, game_state: &mut GameState) {
-        self.start_level(game_state.get_current_level_pack().as_ref().unwrap().levels().get(
-            game_state.get_level_index()).unwrap().level());
+        let level_with_stats = game_state.get_current_level_pack().as_ref().unwrap().levels().get(
+            game_state.get_level_index()).unwrap();
+
+        self.start_replay(level_with_stats.level(), level_with_stats.best_solution().unwrap_or_default());
     }
 }
 
 pub struct ScreenSelectLevelPackEditor {
     is_exporting_level_pack: bool,
+    is_exporting_level_pack_as_xsb: bool,
     is_deleting_level_pack: bool,
 
     is_creating_new_level_pack: bool,
@@ -1128,6 +2246,7 @@ impl ScreenSelectLevelPackEditor {
     pub fn new() -> Self {
         Self {
             is_exporting_level_pack: Default::default(),
+            is_exporting_level_pack_as_xsb: Default::default(),
             is_deleting_level_pack: Default::default(),
 
             is_creating_new_level_pack: Default::default(),
@@ -1137,7 +2256,7 @@ impl ScreenSelectLevelPackEditor {
 }
 
 impl Screen for ScreenSelectLevelPackEditor {
-    fn draw(&self, game_state: &GameState, console: &Console) {
+    fn draw(&self, game_state: &GameState, console: &Canvas) {
         console.reset_color();
         console.set_underline(true);
         console.draw_text("Edit a level pack:");
@@ -1201,7 +2320,7 @@ impl Screen for ScreenSelectLevelPackEditor {
         let x = (game_state.editor_state.get_level_pack_index()%24)*3;
         let y = 1 + (game_state.editor_state.get_level_pack_index()/24)*2;
 
-        console.set_color(Color::Cyan, Color::Default);
+        game_state.theme().set_color(console, ThemeElement::Selection);
         console.set_cursor_pos(x, y);
         console.draw_text("----");
         console.set_cursor_pos(x, y + 1);
@@ -1215,7 +2334,7 @@ impl Screen for ScreenSelectLevelPackEditor {
         let y = 4 + (entry_count/24)*2;
 
         console.set_cursor_pos(0, y);
-        console.set_color(Color::Cyan, Color::Default);
+        game_state.theme().set_color(console, ThemeElement::Selection);
         console.draw_text(".------------------------------------------------------------------------.");
         for i in 1..4 {
             console.set_cursor_pos(0, y + i);
@@ -1230,7 +2349,7 @@ impl Screen for ScreenSelectLevelPackEditor {
             console.draw_text("Enter a new level pack ID:");
 
             console.set_cursor_pos(1, y + 2);
-            console.set_color(Color::Cyan, Color::Default);
+            game_state.theme().set_color(console, ThemeElement::Selection);
             console.draw_text(format!("> {}", &self.new_level_pack_id));
         }else if game_state.editor_state.get_level_pack_index() == game_state.editor_state.get_level_pack_count() {
             //Level Pack Editor entry
@@ -1254,6 +2373,12 @@ impl Screen for ScreenSelectLevelPackEditor {
 
             console.set_cursor_pos(1, y + 2);
             console.draw_text(format!("Levels: {}", game_state.editor_state.get_current_level_pack().unwrap().level_count()));
+
+            console.set_cursor_pos(1, y + 3);
+            console.draw_text(format!(
+                "Name: {}",
+                game_state.editor_state.get_current_level_pack().unwrap().metadata(LevelPackMetadataField::Name).unwrap_or("-"),
+            ));
         }
     }
 
@@ -1350,6 +2475,12 @@ impl Screen for ScreenSelectLevelPackEditor {
             game_state.open_dialog(Box::new(DialogYesNo::new("Do you want to export the level pack to the current directory?")));
         }
 
+        if key == Key::X && game_state.editor_state.selected_level_pack_index != game_state.editor_state.get_level_pack_count() {
+            self.is_exporting_level_pack_as_xsb = true;
+
+            game_state.open_dialog(Box::new(DialogYesNo::new("Export the level pack as XSB file to the current directory?")));
+        }
+
         if key == Key::DELETE && game_state.editor_state.selected_level_pack_index != game_state.editor_state.get_level_pack_count() {
             self.is_deleting_level_pack = true;
 
@@ -1453,6 +2584,28 @@ impl Screen for ScreenSelectLevelPackEditor {
                     game_state.open_dialog(Box::new(DialogOk::new("The level pack was exported successfully")));
                 }
             }
+        }else if self.is_exporting_level_pack_as_xsb {
+            self.is_exporting_level_pack_as_xsb = false;
+
+            if selection == DialogSelection::Yes {
+                let level_pack = game_state.editor_state.get_current_level_pack().unwrap();
+                let path = level_pack.id().to_string() + ".xsb";
+
+                if std::fs::exists(&path).ok().is_none_or(|exists| exists) {
+                    game_state.open_dialog(Box::new(DialogOk::new_error(format!(
+                        "File \"{}\" already exists!",
+                        path,
+                    ))));
+
+                    return;
+                }
+
+                if let Err(err) = level_pack.save_xsb_to_path(path) {
+                    game_state.open_dialog(Box::new(DialogOk::new_error(format!("Cannot export: {}", err))));
+                }else {
+                    game_state.open_dialog(Box::new(DialogOk::new("The level pack was exported successfully")));
+                }
+            }
         }else if self.is_deleting_level_pack {
             self.is_deleting_level_pack = false;
 
@@ -1476,6 +2629,13 @@ pub struct ScreenLevelPackEditor {
     is_deleting_level: bool,
     new_level_width_str: String,
     new_level_height_str: String,
+
+    /// Index into [LevelPackMetadataField::ALL] of the field which is edited
+    editing_metadata_index: Option<usize>,
+    metadata_values: Vec<String>,
+
+    is_editing_level_title: bool,
+    level_title: String,
 }
 
 impl ScreenLevelPackEditor {
@@ -1486,12 +2646,104 @@ impl ScreenLevelPackEditor {
             is_deleting_level: Default::default(),
             new_level_width_str: String::new(),
             new_level_height_str: String::new(),
+
+            editing_metadata_index: Default::default(),
+            metadata_values: Vec::new(),
+
+            is_editing_level_title: Default::default(),
+            level_title: String::new(),
+        }
+    }
+
+    fn is_text_input_key(key: Key) -> bool {
+        key.to_ascii().is_some_and(|c| c.is_ascii_graphic() || c == b' ')
+    }
+
+    fn on_key_pressed_editing_metadata(&mut self, game_state: &mut GameState, key: Key, index: usize) {
+        let field = LevelPackMetadataField::ALL[index];
+
+        match key {
+            key if Self::is_text_input_key(key) => {
+                if self.metadata_values[index].len() >= field.max_len() {
+                    return;
+                }
+
+                let _ = write!(self.metadata_values[index], "{}", key.to_ascii().unwrap() as char);
+            },
+            Key::DELETE => {
+                self.metadata_values[index].pop();
+            },
+
+            Key::TAB => {
+                self.editing_metadata_index = Some((index + 1) % LevelPackMetadataField::ALL.len());
+            },
+
+            Key::ENTER => {
+                let level_pack = game_state.editor_state.get_current_level_pack_mut().unwrap();
+                for (field, value) in LevelPackMetadataField::ALL.into_iter().
+                        zip(self.metadata_values.iter()) {
+                    let value = value.trim();
+
+                    level_pack.set_metadata(field, (!value.is_empty()).then(|| value.to_string()));
+                }
+
+                if let Err(err) = level_pack.save_editor_level_pack() {
+                    game_state.open_dialog(Box::new(DialogOk::new_error(format!("Cannot save: {}", err))));
+                }
+
+                self.editing_metadata_index = None;
+                self.metadata_values.clear();
+            },
+
+            Key::ESC => {
+                self.editing_metadata_index = None;
+                self.metadata_values.clear();
+            },
+
+            _ => {},
+        }
+    }
+
+    fn on_key_pressed_editing_level_title(&mut self, game_state: &mut GameState, key: Key) {
+        match key {
+            key if Self::is_text_input_key(key) => {
+                if self.level_title.len() >= LevelWithStats::MAX_TITLE_LEN {
+                    return;
+                }
+
+                let _ = write!(self.level_title, "{}", key.to_ascii().unwrap() as char);
+            },
+            Key::DELETE => {
+                self.level_title.pop();
+            },
+
+            Key::ENTER => {
+                let index = game_state.editor_state.selected_level_index;
+                let level_pack = game_state.editor_state.get_current_level_pack_mut().unwrap();
+
+                let title = self.level_title.trim();
+                level_pack.levels_mut()[index].set_title((!title.is_empty()).then(|| title.to_string()));
+
+                if let Err(err) = level_pack.save_editor_level_pack() {
+                    game_state.open_dialog(Box::new(DialogOk::new_error(format!("Cannot save: {}", err))));
+                }
+
+                self.is_editing_level_title = false;
+                self.level_title = String::new();
+            },
+
+            Key::ESC => {
+                self.is_editing_level_title = false;
+                self.level_title = String::new();
+            },
+
+            _ => {},
         }
     }
 }
 
 impl Screen for ScreenLevelPackEditor {
-    fn draw(&self, game_state: &GameState, console: &Console) {
+    fn draw(&self, game_state: &GameState, console: &Canvas) {
         console.reset_color();
         console.set_underline(true);
         console.draw_text(format!("Edit a level (Level pack \"{}\"):", game_state.editor_state.get_current_level_pack().unwrap().id()));
@@ -1555,7 +2807,7 @@ impl Screen for ScreenLevelPackEditor {
         let x = (game_state.editor_state.get_level_index()%24)*3;
         let y = 1 + (game_state.editor_state.get_level_index()/24)*2;
 
-        console.set_color(Color::Cyan, Color::Default);
+        game_state.theme().set_color(console, ThemeElement::Selection);
         console.set_cursor_pos(x, y);
         console.draw_text("----");
         console.set_cursor_pos(x, y + 1);
@@ -1569,7 +2821,7 @@ impl Screen for ScreenLevelPackEditor {
         let y = 4 + (entry_count/24)*2;
 
         console.set_cursor_pos(0, y);
-        console.set_color(Color::Cyan, Color::Default);
+        game_state.theme().set_color(console, ThemeElement::Selection);
         console.draw_text(".------------------------------------------------------------------------.");
         for i in 1..4 {
             console.set_cursor_pos(0, y + i);
@@ -1598,6 +2850,28 @@ impl Screen for ScreenLevelPackEditor {
             }, Color::Default);
             console.set_cursor_pos(14, y + 2);
             console.draw_text(format!("Height: {}", &self.new_level_height_str));
+        }else if let Some(editing_metadata_index) = self.editing_metadata_index {
+            console.set_cursor_pos(1, y + 1);
+            console.draw_text("Edit the level pack information:");
+
+            //Positions of name, author, description and version
+            for (i, (x, row)) in [(1, y + 2), (38, y + 2), (1, y + 3), (40, y + 1)].into_iter().
+                    enumerate() {
+                console.set_color(if i == editing_metadata_index {
+                    Color::Cyan
+                }else {
+                    Color::LightBlue
+                }, Color::Default);
+                console.set_cursor_pos(x, row);
+                console.draw_text(format!("{}: {}", LevelPackMetadataField::ALL[i].key(), &self.metadata_values[i]));
+            }
+        }else if self.is_editing_level_title {
+            console.set_cursor_pos(1, y + 1);
+            console.draw_text(format!("Enter a title for level {}:", game_state.editor_state.selected_level_index + 1));
+
+            console.set_cursor_pos(1, y + 2);
+            game_state.theme().set_color(console, ThemeElement::Selection);
+            console.draw_text(format!("> {}", &self.level_title));
         }else if game_state.editor_state.get_level_index() == game_state.editor_state.get_current_level_pack().unwrap().level_count() {
             //Level Editor entry
             if has_max_level_count {
@@ -1627,6 +2901,12 @@ impl Screen for ScreenLevelPackEditor {
                 game_state.editor_state.get_current_level().unwrap().width(),
                 game_state.editor_state.get_current_level().unwrap().height(),
             ));
+
+            console.set_cursor_pos(1, y + 3);
+            console.draw_text(format!(
+                "Title: {}",
+                game_state.editor_state.get_current_level_pack().unwrap().levels()[selected_level].title().unwrap_or("-"),
+            ));
         }
     }
 
@@ -1635,13 +2915,13 @@ impl Screen for ScreenLevelPackEditor {
             match key {
                 key if key.is_ascii() && key.is_numeric() => {
                     if self.is_editing_height {
-                        if self.new_level_height_str.len() >= 2 {
+                        if self.new_level_height_str.len() >= 3 {
                             return;
                         }
 
                         let _ = write!(self.new_level_height_str, "{}", key.to_ascii().unwrap() as char);
                     }else {
-                        if self.new_level_width_str.len() >= 2 {
+                        if self.new_level_width_str.len() >= 3 {
                             return;
                         }
 
@@ -1661,8 +2941,8 @@ impl Screen for ScreenLevelPackEditor {
                 },
 
                 Key::ENTER => {
-                    if !(1..=2).contains(&self.new_level_width_str.len()) {
-                        game_state.open_dialog(Box::new(DialogOk::new_error(format!("Width must be >= 3 and <= {}!", Game::LEVEL_MAX_WIDTH))));
+                    if !(1..=3).contains(&self.new_level_width_str.len()) {
+                        game_state.open_dialog(Box::new(DialogOk::new_error(format!("Width must be >= {} and <= {}!", Game::LEVEL_MIN_WIDTH, Game::LEVEL_MAX_WIDTH))));
 
                         return;
                     }
@@ -1673,8 +2953,8 @@ impl Screen for ScreenLevelPackEditor {
                         return;
                     };
 
-                    if !(3..=Game::LEVEL_MAX_WIDTH).contains(&width) {
-                        game_state.open_dialog(Box::new(DialogOk::new_error(format!("Width must be >= 3 and <= {}!", Game::LEVEL_MAX_WIDTH))));
+                    if !(Game::LEVEL_MIN_WIDTH..=Game::LEVEL_MAX_WIDTH).contains(&width) {
+                        game_state.open_dialog(Box::new(DialogOk::new_error(format!("Width must be >= {} and <= {}!", Game::LEVEL_MIN_WIDTH, Game::LEVEL_MAX_WIDTH))));
 
                         return;
                     }
@@ -1685,8 +2965,8 @@ impl Screen for ScreenLevelPackEditor {
                         return;
                     }
 
-                    if !(1..=2).contains(&self.new_level_height_str.len()) {
-                        game_state.open_dialog(Box::new(DialogOk::new_error(format!("Height must be >= 3 and <= {}!", Game::LEVEL_MAX_HEIGHT))));
+                    if !(1..=3).contains(&self.new_level_height_str.len()) {
+                        game_state.open_dialog(Box::new(DialogOk::new_error(format!("Height must be >= {} and <= {}!", Game::LEVEL_MIN_HEIGHT, Game::LEVEL_MAX_HEIGHT))));
 
                         return;
                     }
@@ -1697,8 +2977,8 @@ impl Screen for ScreenLevelPackEditor {
                         return;
                     };
 
-                    if !(3..=Game::LEVEL_MAX_HEIGHT).contains(&height) {
-                        game_state.open_dialog(Box::new(DialogOk::new_error(format!("Height must be >= 3 and <= {}!", Game::LEVEL_MAX_HEIGHT))));
+                    if !(Game::LEVEL_MIN_HEIGHT..=Game::LEVEL_MAX_HEIGHT).contains(&height) {
+                        game_state.open_dialog(Box::new(DialogOk::new_error(format!("Height must be >= {} and <= {}!", Game::LEVEL_MIN_HEIGHT, Game::LEVEL_MAX_HEIGHT))));
 
                         return;
                     }
@@ -1726,6 +3006,18 @@ impl Screen for ScreenLevelPackEditor {
             return;
         }
 
+        if let Some(editing_metadata_index) = self.editing_metadata_index {
+            self.on_key_pressed_editing_metadata(game_state, key, editing_metadata_index);
+
+            return;
+        }
+
+        if self.is_editing_level_title {
+            self.on_key_pressed_editing_level_title(game_state, key);
+
+            return;
+        }
+
         if key == Key::ESC {
             game_state.set_screen(ScreenId::SelectLevelPackEditor);
 
@@ -1738,6 +3030,26 @@ impl Screen for ScreenLevelPackEditor {
             return;
         }
 
+        if key == Key::I {
+            let level_pack = game_state.editor_state.get_current_level_pack().unwrap();
+
+            self.editing_metadata_index = Some(0);
+            self.metadata_values = LevelPackMetadataField::ALL.into_iter().
+                    map(|field| level_pack.metadata(field).unwrap_or_default().to_string()).
+                    collect();
+
+            return;
+        }
+
+        if key == Key::T && game_state.editor_state.selected_level_index != game_state.editor_state.get_current_level_pack().unwrap().level_count() {
+            let index = game_state.editor_state.selected_level_index;
+
+            self.is_editing_level_title = true;
+            self.level_title = game_state.editor_state.get_current_level_pack().unwrap().levels()[index].title().unwrap_or_default().to_string();
+
+            return;
+        }
+
         'outer: {
             //Include Level Pack Editor entry
             let entry_count = game_state.editor_state.get_current_level_pack().unwrap().level_count() + 1;
@@ -1789,12 +3101,10 @@ impl Screen for ScreenLevelPackEditor {
                     }
                 },
 
-                Key::DELETE => {
-                    if game_state.editor_state.selected_level_index != game_state.editor_state.get_current_level_pack().unwrap().level_count() {
-                        self.is_deleting_level = true;
+                Key::DELETE if game_state.editor_state.selected_level_index != game_state.editor_state.get_current_level_pack().unwrap().level_count() => {
+                    self.is_deleting_level = true;
 
-                        game_state.open_dialog(Box::new(DialogYesNo::new(format!("Do you really want to delete level {}?", game_state.editor_state.selected_level_index + 1))));
-                    }
+                    game_state.open_dialog(Box::new(DialogYesNo::new(format!("Do you really want to delete level {}?", game_state.editor_state.selected_level_index + 1))));
                 },
 
                 _ => {},
@@ -1803,7 +3113,7 @@ impl Screen for ScreenLevelPackEditor {
     }
 
     fn on_mouse_pressed(&mut self, game_state: &mut GameState, column: usize, row: usize) {
-        if row == 0 {
+        if row == 0 || self.editing_metadata_index.is_some() || self.is_editing_level_title {
             return;
         }
 
@@ -1836,13 +3146,33 @@ pub struct ScreenLevelEditor {
     level: UndoHistory<Level>,
     is_vertical_input: bool,
     is_reverse_input: bool,
-    playing_level: Option<UndoHistory<(Level, (usize, usize))>>,
+    playing_level: Option<UndoHistory<GameplayState>>,
+    /// Count of moves in the playing mode (The undo history of large levels is shorter than the count of moves)
+    playing_move_count: usize,
     cursor_pos: (usize, usize),
+
+    /// The corner of the selected area which is not at the cursor position
+    selection_start: Option<(usize, usize)>,
+    /// The area which is moved to the cursor position if the move action is used again
+    moving_area: Option<TileArea>,
+    clipboard: Option<Level>,
+
+    /// The tile which was inserted last, it is used for flood fills
+    current_tile: Tile,
+
+    is_resizing: bool,
+    is_editing_resize_height: bool,
+    resize_width_str: String,
+    resize_height_str: String,
+    /// Column and row of the side at which the level is kept when it is resized (0 = left/top, 1 = center, 2 = right/bottom)
+    resize_anchor: (usize, usize),
+
+    /// The running solvability check, input is blocked until it has finished or is cancelled
+    solver_task: Option<SolverTask<SolverResult>>,
 }
 
 impl ScreenLevelEditor {
     pub const UNDO_HISTORY_SIZE: usize = 256;
-    pub const UNDO_HISTORY_SIZE_PLAYING: usize = 10000;
 
     pub fn new() -> Self {
         Self {
@@ -1850,132 +3180,450 @@ impl ScreenLevelEditor {
             is_vertical_input: Default::default(),
             is_reverse_input: Default::default(),
             playing_level: Default::default(),
+            playing_move_count: Default::default(),
             cursor_pos: Default::default(),
+
+            selection_start: Default::default(),
+            moving_area: Default::default(),
+            clipboard: Default::default(),
+
+            current_tile: Tile::Wall,
+
+            is_resizing: Default::default(),
+            is_editing_resize_height: Default::default(),
+            resize_width_str: String::new(),
+            resize_height_str: String::new(),
+            resize_anchor: Default::default(),
+
+            solver_task: Default::default(),
         }
     }
 
-    fn on_key_pressed_playing(&mut self, key: Key) {
-        if let Some(level_history) = self.playing_level.as_mut() {
-            if matches!(key, Key::Z | Key::Y) {
-                let is_undo = key == Key::Z;
+    /// Replaces the level with a cropped or padded copy in which the tile at `offset` is the top left corner of the
+    /// current level, the cursor stays on the same tile if possible
+    fn resize_level(&mut self, width: usize, height: usize, offset: (isize, isize)) {
+        let level = self.level.current().resized(width, height, offset);
 
-                if is_undo {
-                    level_history.undo()
+        self.cursor_pos.0 = (self.cursor_pos.0 as isize + offset.0).clamp(0, width as isize - 1) as usize;
+        self.cursor_pos.1 = (self.cursor_pos.1 as isize + offset.1).clamp(0, height as isize - 1) as usize;
+
+        self.commit_level_if_changed(level);
+    }
+
+    fn on_key_pressed_resizing(&mut self, game_state: &mut GameState, key: Key) {
+        match key {
+            key if key.is_ascii() && key.is_numeric() => {
+                let input = if self.is_editing_resize_height {
+                    &mut self.resize_height_str
                 }else {
-                    level_history.redo()
+                    &mut self.resize_width_str
                 };
-            }
 
-            if key.is_arrow_key() {
-                let (mut level, mut player_pos) = level_history.current().clone();
+                if input.len() < 3 {
+                    let _ = write!(input, "{}", key.to_ascii().unwrap() as char);
+                }
+            },
+            Key::DELETE => {
+                if self.is_editing_resize_height {
+                    self.resize_height_str.pop();
+                }else {
+                    self.resize_width_str.pop();
+                }
+            },
 
-                let width = level.width();
-                let height = level.height();
+            Key::TAB => {
+                self.is_editing_resize_height = !self.is_editing_resize_height;
+            },
 
-                let (x_from, y_from) = player_pos;
+            Key::LEFT => self.resize_anchor.0 = self.resize_anchor.0.saturating_sub(1),
+            Key::UP => self.resize_anchor.1 = self.resize_anchor.1.saturating_sub(1),
+            Key::RIGHT => self.resize_anchor.0 = (self.resize_anchor.0 + 1).min(2),
+            Key::DOWN => self.resize_anchor.1 = (self.resize_anchor.1 + 1).min(2),
 
-                let x_to = match key {
-                    Key::LEFT => if x_from == 0 {
-                        width - 1
-                    }else {
-                        x_from - 1
-                    },
-                    Key::RIGHT => if x_from == width - 1 {
-                        0
-                    }else {
-                        x_from + 1
-                    },
-                    _ => x_from,
-                };
-                let y_to = match key {
-                    Key::UP => if y_from == 0 {
-                        height - 1
-                    }else {
-                        y_from - 1
-                    },
-                    Key::DOWN => if y_from == height - 1 {
-                        0
-                    }else {
-                        y_from + 1
+            Key::ENTER => {
+                let parse_size = |input: &str, name: &str, min: usize, max: usize| usize::from_str(input).ok().
+                        filter(|size| (min..=max).contains(size)).
+                        ok_or_else(|| format!("{name} must be >= {min} and <= {max}!"));
+
+                let size = parse_size(&self.resize_width_str, "Width", Game::LEVEL_MIN_WIDTH, Game::LEVEL_MAX_WIDTH).
+                        and_then(|width| Ok((width, parse_size(&self.resize_height_str, "Height", Game::LEVEL_MIN_HEIGHT, Game::LEVEL_MAX_HEIGHT)?)));
+                let (width, height) = match size {
+                    Ok(size) => size,
+                    Err(err) => {
+                        game_state.open_dialog(Box::new(DialogOk::new_error(err)));
+
+                        return;
                     },
-                    _ => y_from,
                 };
 
-                let one_way_door_tile = match key {
-                    Key::LEFT => Tile::OneWayLeft,
-                    Key::UP => Tile::OneWayUp,
-                    Key::RIGHT => Tile::OneWayRight,
-                    Key::DOWN => Tile::OneWayDown,
-                    _ => return, //Should never happen
-                };
+                //The anchor side keeps its position, the size difference is split evenly for the center
+                let level = self.level.current();
+                let offset = (
+                    (width as isize - level.width() as isize) * self.resize_anchor.0 as isize / 2,
+                    (height as isize - level.height() as isize) * self.resize_anchor.1 as isize / 2,
+                );
+                self.resize_level(width, height, offset);
+
+                self.is_resizing = false;
+            },
+
+            Key::ESC => {
+                self.is_resizing = false;
+            },
+
+            _ => {},
+        }
+    }
+
+    fn draw_resizing(&self, game_state: &GameState, console: &Canvas) {
+        //The dialog is laid out for the minimal console width
+        let console = &console.centered_area(Game::CONSOLE_MIN_WIDTH, console.height());
+        let y = console.height() - 5;
+
+        console.set_cursor_pos(0, y);
+        game_state.theme().set_color(console, ThemeElement::Selection);
+        console.draw_text(".------------------------------------------------------------------------.");
+        for i in 1..4 {
+            console.set_cursor_pos(0, y + i);
+            console.draw_text("|                                                                        |");
+        }
+        console.set_cursor_pos(0, y + 4);
+        console.draw_text("\'------------------------------------------------------------------------\'");
+        console.reset_color();
+
+        console.set_cursor_pos(1, y + 1);
+        console.draw_text(format!(
+            "Resize level ({}x{}):", self.level.current().width(), self.level.current().height(),
+        ));
+
+        console.set_color(if self.is_editing_resize_height {
+            Color::LightBlue
+        }else {
+            Color::Cyan
+        }, Color::Default);
+        console.set_cursor_pos(1, y + 2);
+        console.draw_text(format!("Width: {}", &self.resize_width_str));
+
+        console.set_color(if self.is_editing_resize_height {
+            Color::Cyan
+        }else {
+            Color::LightBlue
+        }, Color::Default);
+        console.set_cursor_pos(14, y + 2);
+        console.draw_text(format!("Height: {}", &self.resize_height_str));
+
+        console.reset_color();
+        console.set_cursor_pos(1, y + 3);
+        console.draw_text("TAB: Switch input, Arrow keys: Anchor");
+
+        console.set_cursor_pos(52, y + 1);
+        console.draw_text("Anchor:");
+        for row in 0..3 {
+            console.set_cursor_pos(62, y + 1 + row);
+            for column in 0..3 {
+                if (column, row) == self.resize_anchor {
+                    console.set_color(Color::Cyan, Color::Default);
+                    console.draw_text("X ");
+                    console.reset_color();
+                }else {
+                    console.draw_text("- ");
+                }
+            }
+        }
+    }
+
+    /// Returns the area between the start of the selection and the cursor position
+    fn selection(&self) -> Option<TileArea> {
+        self.selection_start.map(|selection_start| TileArea::from_corners(selection_start, self.cursor_pos))
+    }
+
+    /// Starts the selection at the cursor position or ends it
+    fn toggle_selection(&mut self) {
+        self.moving_area = None;
+        self.selection_start = match self.selection_start {
+            Some(_) => None,
+            None => Some(self.cursor_pos),
+        };
+    }
+
+    /// Returns the area which is drawn inverted (The selection, the destination of a moved area or the cursor)
+    fn marked_area(&self) -> TileArea {
+        if let Some(area) = self.selection() {
+            return area;
+        }
+
+        let Some(area) = self.moving_area else {
+            return TileArea::from_pos(self.cursor_pos);
+        };
+
+        let level = self.level.current();
+        TileArea::from_corners(self.cursor_pos, (
+            (self.cursor_pos.0 + area.width() - 1).min(level.width() - 1),
+            (self.cursor_pos.1 + area.height() - 1).min(level.height() - 1),
+        ))
+    }
+
+    /// Commits the level as a single undo step if it differs from the current level
+    fn commit_level_if_changed(&mut self, level: Level) {
+        let level_current = self.level.current();
+        if (level.width(), level.height()) != (level_current.width(), level_current.height()) || level.tiles() != level_current.tiles() {
+            self.level.commit_change(level);
+        }
+    }
+
+    /// Handles the actions for selected areas, returns false if the key was not used
+    fn on_key_pressed_selection(&mut self, action: Option<Action>, key: Key) -> bool {
+        let selection = self.selection();
+
+        match (action, key) {
+            (Some(Action::EditorSelect), _) => self.toggle_selection(),
+
+            (Some(action @ (Action::EditorCopy | Action::EditorCut)), _) if selection.is_some() => {
+                let selection = selection.unwrap();
+
+                self.clipboard = Some(self.level.current().copy_area(selection));
+                self.selection_start = None;
+
+                if action == Action::EditorCut {
+                    let mut level = self.level.current().clone();
+                    level.fill_area(selection, Tile::Empty);
+                    self.commit_level_if_changed(level);
+                }
+            },
+
+            (Some(Action::EditorPaste), _) => {
+                if let Some(clipboard) = &self.clipboard {
+                    let mut level = self.level.current().clone();
+                    level.paste(self.cursor_pos, clipboard);
+                    self.commit_level_if_changed(level);
+                }
+            },
 
-                //Set players old position to old level data
-                let mut tile = self.level.current().get_tile(x_from, y_from).unwrap().clone();
-                if tile == Tile::Player || tile == Tile::Box || tile == Tile::Key || tile == Tile::LockedDoor {
-                    tile = Tile::Empty;
-                }else if tile == Tile::BoxInGoal || tile == Tile::KeyInGoal {
-                    tile = Tile::Goal;
-                }else if tile == Tile::Hole || tile == Tile::BoxInHole {
-                    tile = Tile::BoxInHole;
+            (Some(Action::EditorMove), _) => {
+                if let Some(area) = self.moving_area.take() {
+                    let mut level = self.level.current().clone();
+                    let tiles = level.copy_area(area);
+                    level.fill_area(area, Tile::Empty);
+                    level.paste(self.cursor_pos, &tiles);
+                    self.commit_level_if_changed(level);
+                }else if selection.is_some() {
+                    self.moving_area = selection;
+                    self.selection_start = None;
+                }
+            },
+
+            (Some(action @ (Action::EditorLine | Action::EditorRectangle)), _) if selection.is_some() => {
+                let mut level = self.level.current().clone();
+                if action == Action::EditorLine {
+                    level.draw_line(self.selection_start.unwrap(), self.cursor_pos, Tile::Wall);
+                }else {
+                    level.draw_rectangle(selection.unwrap(), Tile::Wall);
                 }
+                self.commit_level_if_changed(level);
 
-                level.set_tile(x_from, y_from, tile);
+                self.selection_start = None;
+            },
+
+            //Fill and clear the selection
+            (None, Key::DELETE) if selection.is_some() => {
+                let mut level = self.level.current().clone();
+                level.fill_area(selection.unwrap(), Tile::Empty);
+                self.commit_level_if_changed(level);
+            },
+            (None, key) if selection.is_some() && key.is_ascii() => {
+                if let Ok(tile_input) = Tile::from_ascii(key.to_ascii().unwrap()) {
+                    //A level can only contain a single player
+                    if tile_input != Tile::Secret && !tile_input.is_player() {
+                        let mut level = self.level.current().clone();
+                        level.fill_area(selection.unwrap(), tile_input.clone());
+                        self.commit_level_if_changed(level);
 
-                let mut has_won = false;
-                let tile = level.get_tile(x_to, y_to).unwrap().clone();
-                if matches!(tile, Tile::Empty | Tile::Goal | Tile::Secret | Tile::BoxInHole) || tile == one_way_door_tile ||
-                        matches!(tile, Tile::Box | Tile::BoxInGoal | Tile::Key | Tile::KeyInGoal if level.move_box_or_key(
-                            self.level.current(), &mut has_won, x_from, y_from, x_to, y_to)) {
-                    player_pos = (x_to, y_to);
+                        self.current_tile = tile_input;
+                    }
                 }
+            },
+
+            _ => return false,
+        }
+
+        true
+    }
+
+    /// Creates the start state for playing or solving the level, opens an error dialog if the player tile count is not 1
+    fn create_gameplay_state(&self, game_state: &mut GameState) -> Option<GameplayState> {
+        let player_tile_count = self.level.current().tiles().iter().filter(|tile| tile.is_player()).count();
+        if player_tile_count == 0 {
+            game_state.open_dialog(Box::new(DialogOk::new_error("Level does not contain a player tile!")));
+
+            return None;
+        }else if player_tile_count > 1 {
+            game_state.open_dialog(Box::new(DialogOk::new_error("Level contains too many player tiles!")));
+
+            return None;
+        }
+
+        GameplayState::new(self.level.current().clone())
+    }
+
+    fn on_key_pressed_playing(&mut self, key_bindings: &KeyBindings, key: Key) {
+        if let Some(level_history) = self.playing_level.as_mut() {
+            if let Some(action @ (Action::Undo | Action::Redo)) = key_bindings.action(&Action::EDITOR_PLAYING, key) {
+                let is_undo = action == Action::Undo;
 
-                //Set player to new position
-                level.set_tile(player_pos.0, player_pos.1, Tile::Player);
+                if is_undo {
+                    if level_history.undo().is_some() {
+                        self.playing_move_count -= 1;
+                    }
+                }else if level_history.redo().is_some() {
+                    self.playing_move_count += 1;
+                }
+            }
 
-                if player_pos != (x_from, y_from) {
-                    level_history.commit_change((level, player_pos));
+            if let Some(direction) = key_bindings.direction(Action::MOVE, key) {
+                let outcome = level_history.current().apply_move(self.level.current(), direction);
+                if outcome.has_moved() {
+                    level_history.commit_change(outcome.into_state());
+                    self.playing_move_count += 1;
                 }
             }
         }
     }
 
-    fn on_key_pressed_editing(&mut self, game_state: &mut GameState, key: Key) {
-        match key {
-            Key::LEFT => {
+    /// The level scrolls with the cursor in the editing mode and with the player in the playing mode
+    fn level_view(&self, game_state: &GameState) -> LevelView {
+        match &self.playing_level {
+            Some(level) => ScreenInGame::level_view(game_state, level.current().level(), level.current().player_pos()),
+            //The last row is used for the validation status
+            None => {
+                let (width, height) = game_state.console_size();
+
+                LevelView::new(self.level.current(), (width, height - 2), 1, game_state.settings().wide_tiles(), self.cursor_pos)
+            },
+        }
+    }
+
+    fn draw_validation_status(&self, console: &Canvas) {
+        let status = validation::level_status(self.level.current());
+
+        let boxes_color = if status.goal_count == 0 || status.box_count < status.goal_count {
+            Color::LightRed
+        }else if status.may_lack_boxes() {
+            Color::LightYellow
+        }else {
+            Color::Default
+        };
+        let hole_text = if status.hole_count > 0 {
+            format!(" (+{})", status.hole_count)
+        }else {
+            String::new()
+        };
+
+        let segments = [
+            (format!("Player: {}", status.player_count), if status.player_count == 1 { Color::Default } else { Color::LightRed }),
+            (format!("Boxes: {}/{}{hole_text}", status.box_count, status.goal_count), boxes_color),
+            (
+                format!("Keys: {}/{}", status.key_count, status.locked_door_count),
+                if status.key_count < status.locked_door_count { Color::LightYellow } else { Color::Default },
+            ),
+            (
+                format!("Unreachable: {}", status.unreachable_goal_count),
+                if status.unreachable_goal_count > 0 { Color::LightRed } else { Color::Default },
+            ),
+            (
+                format!("Solved: {}", if status.is_solved { "Yes" } else { "No" }),
+                if status.is_solved { Color::LightRed } else { Color::Default },
+            ),
+        ];
+
+        //Segments which do not fit in the console width are cut off
+        let mut remaining_width = console.width();
+        console.set_cursor_pos(0, console.height() - 1);
+        for (i, (mut text, color)) in segments.into_iter().
+                enumerate() {
+            if i > 0 {
+                text.insert_str(0, "  ");
+            }
+
+            text.truncate(remaining_width);
+            remaining_width -= text.len();
+
+            console.set_color(color, Color::Default);
+            console.draw_text(text);
+        }
+        console.reset_color();
+    }
+
+    fn move_cursor(&mut self, direction: Direction) {
+        match direction {
+            Direction::Left => {
                 if self.cursor_pos.0 > 0 {
                     self.cursor_pos.0 -= 1;
                 }else {
                     self.cursor_pos.0 = self.level.current().width() - 1;
                 }
             },
-            Key::UP => {
+            Direction::Up => {
                 if self.cursor_pos.1 > 0 {
                     self.cursor_pos.1 -= 1;
                 }else {
                     self.cursor_pos.1 = self.level.current().height() - 1;
                 }
             },
-            Key::RIGHT => {
+            Direction::Right => {
                 if self.cursor_pos.0 < self.level.current().width() - 1 {
                     self.cursor_pos.0 += 1;
                 }else {
                     self.cursor_pos.0 = 0;
                 }
             },
-            Key::DOWN => {
+            Direction::Down => {
                 if self.cursor_pos.1 < self.level.current().height() - 1 {
                     self.cursor_pos.1 += 1;
                 }else {
                     self.cursor_pos.1 = 0;
                 }
             },
+        }
+    }
 
-            Key::DELETE => {
+    fn on_key_pressed_editing(&mut self, game_state: &mut GameState, key: Key) {
+        let level_size = (self.level.current().width(), self.level.current().height());
+
+        self.on_key_pressed_editing_level(game_state, key);
+
+        //Selected and moved areas are discarded if the size of the level was changed
+        if level_size != (self.level.current().width(), self.level.current().height()) {
+            self.selection_start = None;
+            self.moving_area = None;
+        }
+    }
+
+    fn on_key_pressed_editing_level(&mut self, game_state: &mut GameState, key: Key) {
+        if let Some(direction) = game_state.key_bindings().direction(Action::EDITOR_CURSOR, key) {
+            self.move_cursor(direction);
+
+            return;
+        }
+
+        if let Some(direction) = game_state.key_bindings().direction(Action::EDITOR_DIRECTION, key) {
+            self.is_vertical_input = matches!(direction, Direction::Up | Direction::Down);
+            self.is_reverse_input = matches!(direction, Direction::Left | Direction::Up);
+
+            return;
+        }
+
+        let action = game_state.key_bindings().action(&Action::EDITOR_EDITING, key);
+        if self.on_key_pressed_selection(action, key) {
+            return;
+        }
+
+        match (action, key) {
+            (None, Key::DELETE) => {
                 if self.is_vertical_input {
-                    if self.level.current().width() == 3 {
+                    if self.level.current().width() == Game::LEVEL_MIN_WIDTH {
                         game_state.open_dialog(Box::new(DialogOk::new_error(format!(
                             "Level width limit reached (min: {})",
-                            3,
+                            Game::LEVEL_MIN_WIDTH,
                         ))));
 
                         return;
@@ -2008,10 +3656,10 @@ impl ScreenLevelEditor {
 
                     self.level.commit_change(new_level);
                 }else {
-                    if self.level.current().height() == 3 {
+                    if self.level.current().height() == Game::LEVEL_MIN_HEIGHT {
                         game_state.open_dialog(Box::new(DialogOk::new_error(format!(
                             "Level height limit reached (min: {})",
-                            3,
+                            Game::LEVEL_MIN_HEIGHT,
                         ))));
 
                         return;
@@ -2046,13 +3694,8 @@ impl ScreenLevelEditor {
                 }
             },
 
-            Key::W | Key::A | Key::S | Key::D => {
-                self.is_vertical_input = key == Key::W || key == Key::S;
-                self.is_reverse_input = key == Key::W || key == Key::A;
-            },
-
-            Key::C | Key::I => {
-                let is_copy = key == Key::C;
+            (Some(action @ (Action::EditorInsert | Action::EditorCopy)), _) => {
+                let is_copy = action == Action::EditorCopy;
 
                 if self.is_vertical_input {
                     if self.level.current().height() == Game::LEVEL_MAX_HEIGHT {
@@ -2135,8 +3778,76 @@ impl ScreenLevelEditor {
                 }
             },
 
-            Key::Z | Key::Y => {
-                let is_undo = key == Key::Z;
+            //A level can only contain a single player
+            (Some(Action::EditorFloodFill), _) if !self.current_tile.is_player() => {
+                let mut level = self.level.current().clone();
+                level.flood_fill(self.cursor_pos, self.current_tile.clone());
+                self.commit_level_if_changed(level);
+            },
+
+            (Some(Action::EditorAutoWall), _) => {
+                let Some(state) = self.create_gameplay_state(game_state) else {
+                    return;
+                };
+
+                let mut level = self.level.current().clone();
+                level.surround_with_walls(state.player_pos());
+                self.commit_level_if_changed(level);
+            },
+
+            (Some(action @ (
+                Action::EditorRotateRight | Action::EditorRotateLeft | Action::EditorRotate180 |
+                Action::EditorMirrorHorizontally | Action::EditorMirrorVertically | Action::EditorTranspose
+            )), _) => {
+                let level = self.level.current();
+                let level = match action {
+                    Action::EditorRotateRight => level.rotated_clockwise(),
+                    Action::EditorRotateLeft => level.rotated_counterclockwise(),
+                    Action::EditorRotate180 => level.rotated_180(),
+                    Action::EditorMirrorHorizontally => level.mirrored_horizontally(),
+                    Action::EditorMirrorVertically => level.mirrored_vertically(),
+                    _ => level.transposed(),
+                };
+
+                self.cursor_pos.0 = self.cursor_pos.0.min(level.width() - 1);
+                self.cursor_pos.1 = self.cursor_pos.1.min(level.height() - 1);
+
+                self.commit_level_if_changed(level);
+            },
+
+            (Some(Action::EditorResize), _) => {
+                self.is_resizing = true;
+                self.is_editing_resize_height = false;
+                self.resize_width_str = self.level.current().width().to_string();
+                self.resize_height_str = self.level.current().height().to_string();
+                self.resize_anchor = (0, 0);
+            },
+
+            (Some(Action::EditorAutoCrop), _) => {
+                let Some(area) = self.level.current().content_area() else {
+                    return;
+                };
+
+                let (x, y) = area.positions().next().unwrap();
+                let width = area.width().max(Game::LEVEL_MIN_WIDTH);
+                let height = area.height().max(Game::LEVEL_MIN_HEIGHT);
+                self.resize_level(width, height, (-(x as isize), -(y as isize)));
+            },
+
+            (Some(Action::EditorCheckSolvable), _) => {
+                let Some(state) = self.create_gameplay_state(game_state) else {
+                    return;
+                };
+
+                let level = self.level.current().clone();
+                self.solver_task = Some(SolverTask::spawn(
+                    Solver::new(Solver::DEFAULT_MAX_NODES, Solver::DEFAULT_TIME_LIMIT),
+                    move |solver| solver.solve(&level, &state),
+                ));
+            },
+
+            (Some(action @ (Action::Undo | Action::Redo)), _) => {
+                let is_undo = action == Action::Undo;
 
                 let level = if is_undo {
                     self.level.undo()
@@ -2155,33 +3866,28 @@ impl ScreenLevelEditor {
                 }
             },
 
-            key if key.is_ascii() => {
+            (None, key) if key.is_ascii() => {
                 if let Ok(tile_input) = Tile::from_ascii(key.to_ascii().unwrap()) {
                     if tile_input != Tile::Secret {
                         let mut level = self.level.current().clone();
                         let tile = level.get_tile_mut(self.cursor_pos.0, self.cursor_pos.1).unwrap();
 
                         if *tile != tile_input {
-                            *tile = tile_input;
+                            *tile = tile_input.clone();
 
                             self.level.commit_change(level);
                         }
+
+                        self.current_tile = tile_input;
                     }
                 }
 
-                if self.is_vertical_input {
-                    self.on_key_pressed_editing(game_state, if self.is_reverse_input {
-                        Key::UP
-                    }else {
-                        Key::DOWN
-                    });
-                }else {
-                    self.on_key_pressed_editing(game_state, if self.is_reverse_input {
-                        Key::LEFT
-                    }else {
-                        Key::RIGHT
-                    });
-                }
+                self.move_cursor(match self.is_vertical_input {
+                    true if self.is_reverse_input => Direction::Up,
+                    true => Direction::Down,
+                    false if self.is_reverse_input => Direction::Left,
+                    false => Direction::Right,
+                });
             },
 
             _ => {},
@@ -2190,13 +3896,13 @@ impl ScreenLevelEditor {
 }
 
 impl Screen for ScreenLevelEditor {
-    fn draw(&self, game_state: &GameState, console: &Console) {
+    fn draw(&self, game_state: &GameState, console: &Canvas) {
         console.reset_color();
-        if let Some(level_history) = &self.playing_level {
+        if self.playing_level.is_some() {
             console.draw_text("Playing");
 
-            console.set_cursor_pos(((Game::CONSOLE_MIN_WIDTH - 11) as f64 * 0.75) as usize, 0);
-            console.draw_text(format!("Moves: {:04}", level_history.current_index()));
+            console.set_cursor_pos(((console.width() - 11) as f64 * 0.75) as usize, 0);
+            console.draw_text(format!("Moves: {:04}", self.playing_move_count));
         }else {
             console.draw_text(format!(
                 "Editing ({})",
@@ -2208,19 +3914,81 @@ impl Screen for ScreenLevelEditor {
                 }
             ));
 
-            console.set_cursor_pos(((Game::CONSOLE_MIN_WIDTH - 14) as f64 * 0.5) as usize, 0);
+            console.set_cursor_pos(14, 0);
+            console.draw_text("Tile: ");
+            self.current_tile.draw(console, game_state.theme(), game_state.is_player_background(), false, false);
+            console.reset_color();
+
+            console.set_cursor_pos(((console.width() - 14) as f64 * 0.5) as usize, 0);
             console.draw_text(format!("Cursor ({:02}:{:02})", self.cursor_pos.0 + 1, self.cursor_pos.1 + 1));
+
+            let area_text = match (self.selection(), self.moving_area) {
+                (Some(area), _) => Some(("Selected", area)),
+                (None, Some(area)) => Some(("Moving", area)),
+                (None, None) => None,
+            };
+            if let Some((text, area)) = area_text {
+                let text = format!("{text} ({}x{})", area.width(), area.height());
+
+                console.set_cursor_pos(console.width() - text.len(), 0);
+                console.draw_text(text);
+            }
+        }
+
+        self.playing_level.as_ref().map_or(self.level.current(), |level| level.current().level()).
+                draw(console, game_state.theme(), self.level_view(game_state), game_state.is_player_background(),
+                     self.playing_level.as_ref().map_or(Some(self.marked_area()), |_| None), &[]);
+
+        if self.solver_task.is_some() {
+            console.set_cursor_pos(0, console.height() - 1);
+            console.set_color(Color::Cyan, Color::Default);
+            console.draw_text("Solving... (ESC: Cancel)");
+            console.reset_color();
+        }else if self.playing_level.is_none() {
+            self.draw_validation_status(console);
         }
 
-        let x_offset = ((Game::CONSOLE_MIN_WIDTH - self.level.current().width()) as f64 * 0.5) as usize;
-        let y_offset = 1;
+        if self.is_resizing {
+            self.draw_resizing(game_state, console);
+        }
+    }
+
+    fn is_full_size(&self) -> bool {
+        true
+    }
 
-        self.playing_level.as_ref().map_or(self.level.current(), |level| &level.current().0).
-                draw(console, x_offset, y_offset, game_state.is_player_background(),
-                     self.playing_level.as_ref().map_or(Some(self.cursor_pos), |_| None));
+    fn update(&mut self, game_state: &mut GameState) {
+        let Some(result) = self.solver_task.as_ref().and_then(SolverTask::result) else {
+            return;
+        };
+        self.solver_task = None;
+
+        game_state.open_dialog(Box::new(match result {
+            SolverResult::Solved(solution) => DialogOk::new(format!(
+                "Level is solvable ({} moves, {} pushes)",
+                solution.moves().len(),
+                solution.pushes(),
+            )),
+            SolverResult::Unsolvable => DialogOk::new_error("Level is not solvable!"),
+            SolverResult::GaveUp => DialogOk::new_error("Solver gave up (Level is too complex)!"),
+        }));
     }
 
     fn on_key_pressed(&mut self, game_state: &mut GameState, key: Key) {
+        if self.solver_task.is_some() {
+            if key == Key::ESC {
+                self.solver_task = None;
+            }
+
+            return;
+        }
+
+        if self.is_resizing {
+            self.on_key_pressed_resizing(game_state, key);
+
+            return;
+        }
+
         if key == Key::ESC {
             game_state.open_dialog(Box::new(DialogYesCancelNo::new("Exiting (Save changes?)")));
 
@@ -2233,38 +4001,20 @@ impl Screen for ScreenLevelEditor {
             return;
         }
 
-        if key == Key::R {
+        if game_state.key_bindings().is_bound(Action::EditorSwitchMode, key) {
             self.playing_level = if self.playing_level.is_some() {
                 None
             }else {
-                let player_tile_count = self.level.current().tiles().iter().filter(|tile| **tile == Tile::Player).count();
-                if player_tile_count == 0 {
-                    game_state.open_dialog(Box::new(DialogOk::new_error("Level does not contain a player tile!")));
-
-                    return;
-                }else if player_tile_count > 1 {
-                    game_state.open_dialog(Box::new(DialogOk::new_error("Level contains too many player tiles!")));
-
+                let Some(state) = self.create_gameplay_state(game_state) else {
                     return;
-                }
-
-                let mut player_pos = None;
-
-                'outer:
-                for i in 0..self.level.current().width() {
-                    for j in 0..self.level.current().height() {
-                        if let Some(tile) = self.level.current().get_tile(i, j) {
-                            if *tile == Tile::Player {
-                                player_pos = Some((i, j));
+                };
 
-                                break 'outer;
-                            }
-                        }
-                    }
-                }
+                self.playing_move_count = 0;
 
-                Some(UndoHistory::new(Self::UNDO_HISTORY_SIZE_PLAYING, (self.level.current().clone(), player_pos.unwrap())))
+                Some(UndoHistory::new(ScreenInGame::undo_history_size_playing(self.level.current()), state))
             };
+            self.selection_start = None;
+            self.moving_area = None;
 
             return;
         }
@@ -2272,33 +4022,24 @@ impl Screen for ScreenLevelEditor {
         if self.playing_level.is_none() {
             self.on_key_pressed_editing(game_state, key);
         }else {
-            self.on_key_pressed_playing(key);
+            self.on_key_pressed_playing(game_state.key_bindings(), key);
         }
     }
 
-    fn on_mouse_pressed(&mut self, _: &mut GameState, column: usize, row: usize) {
-        if row == 0 || self.playing_level.is_some() {
-            return;
-        }
-
-        let x_offset = ((Game::CONSOLE_MIN_WIDTH - self.level.current().width()) as f64 * 0.5) as usize;
-        let y_offset = 1;
-
-        if column < x_offset {
-            return;
-        }
-
-        let x = column - x_offset;
-        if x >= self.level.current().width() {
+    fn on_mouse_pressed(&mut self, game_state: &mut GameState, column: usize, row: usize) {
+        if row == 0 || self.playing_level.is_some() || self.is_resizing || self.solver_task.is_some() {
             return;
         }
 
-        let y = row - y_offset;
-        if y >= self.level.current().height() {
-            return;
+        //A click on the tile at the cursor starts or ends the selection, other clicks move the cursor which is the
+        //opposite corner of the selection
+        if let Some(pos) = self.level_view(game_state).tile_pos(column, row) {
+            if pos == self.cursor_pos {
+                self.toggle_selection();
+            }else {
+                self.cursor_pos = pos;
+            }
         }
-
-        self.cursor_pos = (x, y);
     }
 
     fn on_dialog_selection(&mut self, game_state: &mut GameState, selection: DialogSelection) {
@@ -2324,6 +4065,12 @@ impl Screen for ScreenLevelEditor {
         self.playing_level = None;
         self.cursor_pos = (0, 0);
 
+        self.selection_start = None;
+        self.moving_area = None;
+        self.current_tile = Tile::Wall;
+        self.is_resizing = false;
+        self.solver_task = None;
+
         self.level.clear_with_new_initial(game_state.editor_state.get_current_level().unwrap().clone());
     }
 }
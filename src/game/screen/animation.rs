@@ -0,0 +1,170 @@
+use crate::game::canvas::Canvas;
+use crate::game::engine::{Direction, GameplayState, MoveOutcome};
+use crate::game::level::{Level, LevelView, Tile};
+use crate::game::theme::Theme;
+
+#[cfg(test)]
+mod tests;
+
+/// A tile which is drawn between its old and its new position while a move is animated
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct MovingTile {
+    tile: Tile,
+    pos_from: (usize, usize),
+    pos_to: (usize, usize),
+
+    /// The tile which is drawn at the new position until the moving tile has arrived
+    tile_under: Tile,
+}
+
+/// The animation of a single move, it is drawn on top of the state after the move
+///
+/// The player and a pushed box or key are drawn between the old and the new tile for [MoveAnimation::MOVE_FRAMES]
+/// updates. Afterward, a box which reached a goal, an opened door and a box or key which fell into a hole flash for
+/// [MoveAnimation::EFFECT_FRAMES] updates. Only the first phase blocks input.
+///
+/// Tiles which move across a level edge (And appear at the other side of the level) are not animated.
+#[derive(Debug, Clone)]
+pub struct MoveAnimation {
+    frame: u32,
+
+    moving_tiles: Vec<MovingTile>,
+    /// Tiles which flash after the move and their positions
+    effects: Vec<(Tile, (usize, usize))>,
+}
+
+impl MoveAnimation {
+    pub const MOVE_FRAMES: u32 = 3;
+    pub const EFFECT_FRAMES: u32 = 6;
+
+    /// Creates the animation of the move in `direction` from `state` which resulted in `outcome`
+    ///
+    /// `level_original` must be the level in the state in which it was started
+    pub fn new(level_original: &Level, state: &GameplayState, direction: Direction, outcome: &MoveOutcome) -> Self {
+        let level = state.level();
+        let is_wrapped = |(x, y): (usize, usize)| match direction {
+            Direction::Left => x == 0,
+            Direction::Up => y == 0,
+            Direction::Right => x == level.width() - 1,
+            Direction::Down => y == level.height() - 1,
+        };
+
+        let mut moving_tiles = Vec::with_capacity(2);
+        let mut effects = Vec::new();
+
+        if let Some((pos_from, pos_to)) = outcome.pushed() {
+            let tile = level.get_tile(pos_from.0, pos_from.1).unwrap().clone();
+            let tile_under = level.get_tile(pos_to.0, pos_to.1).unwrap().clone();
+            let is_box = matches!(tile, Tile::Box | Tile::BoxInGoal);
+
+            match tile_under {
+                Tile::Goal if is_box => effects.push((Tile::BoxInGoal, pos_to)),
+                Tile::Hole if is_box => effects.push((Tile::BoxInHole, pos_to)),
+
+                _ => {},
+            }
+
+            if !is_wrapped(pos_from) {
+                moving_tiles.push(MovingTile { tile, pos_from, pos_to, tile_under });
+            }
+        }
+
+        if let Some(pos) = outcome.opened_door() {
+            effects.push((Tile::LockedDoor, pos));
+        }
+
+        if let Some(pos) = outcome.destroyed_key().filter(|pos| outcome.opened_door() != Some(*pos)) {
+            effects.push((Tile::Hole, pos));
+        }
+
+        let pos_from = state.player_pos();
+        let pos_to = outcome.state().player_pos();
+        if !is_wrapped(pos_from) {
+            moving_tiles.push(MovingTile {
+                tile: Tile::Player,
+                pos_from,
+                pos_to,
+                tile_under: GameplayState::tile_under_player(level_original, pos_to),
+            });
+        }
+
+        Self {
+            frame: 0,
+
+            moving_tiles,
+            effects,
+        }
+    }
+
+    /// Returns false if the animation has finished
+    pub fn update(&mut self) -> bool {
+        self.frame += 1;
+
+        self.frame < Self::MOVE_FRAMES + Self::EFFECT_FRAMES
+    }
+
+    /// Returns true while the moving tiles have not arrived at their new positions (Input should be queued)
+    pub fn is_moving(&self) -> bool {
+        self.frame < Self::MOVE_FRAMES
+    }
+
+    /// Returns the level of the state after the move without the tiles which are still moving
+    pub fn level_without_moving_tiles(&self, level: &Level) -> Level {
+        let mut level = level.clone();
+        if self.is_moving() {
+            for moving_tile in self.moving_tiles.iter() {
+                level.set_tile(moving_tile.pos_to.0, moving_tile.pos_to.1, moving_tile.tile_under.clone());
+            }
+        }
+
+        level
+    }
+
+    /// Draws the moving tiles and the effects on top of the level
+    pub fn draw(&self, console: &Canvas, theme: &Theme, view: LevelView, is_player_background: bool) {
+        if self.is_moving() {
+            for moving_tile in self.moving_tiles.iter() {
+                let Some(pos_to) = view.console_pos(moving_tile.pos_to) else {
+                    continue;
+                };
+                //The old position is not visible if the level was scrolled by the move
+                let pos_from = view.console_pos(moving_tile.pos_from).unwrap_or(pos_to);
+
+                //Wide tiles are moved by single columns, narrow tiles and vertical moves can only be drawn at full tiles
+                //(The distance is rounded to the nearest column or row)
+                let steps = 2 * (Self::MOVE_FRAMES as usize + 1);
+                let distance = |distance: usize| (2 * distance * (self.frame as usize + 1) + steps / 2) / steps;
+                let progress = |from: usize, to: usize| if to >= from {
+                    from + distance(to - from)
+                }else {
+                    from - distance(from - to)
+                };
+
+                console.set_cursor_pos(progress(pos_from.0, pos_to.0), progress(pos_from.1, pos_to.1));
+                Self::draw_tile(console, theme, view, &moving_tile.tile, is_player_background, false);
+            }
+
+            return;
+        }
+
+        let effect_frame = self.frame - Self::MOVE_FRAMES;
+        for (tile, pos) in self.effects.iter() {
+            let Some((column, row)) = view.console_pos(*pos) else {
+                continue;
+            };
+
+            console.set_cursor_pos(column, row);
+            Self::draw_tile(console, theme, view, tile, is_player_background, effect_frame.is_multiple_of(2));
+        }
+    }
+
+    fn draw_tile(console: &Canvas, theme: &Theme, view: LevelView, tile: &Tile, is_player_background: bool, inverted: bool) {
+        let mut style = tile.style(theme, is_player_background, false);
+        style.draw(console, inverted);
+
+        if view.tile_width() == 2 {
+            style.glyph = ' ';
+            style.draw(console, inverted);
+        }
+    }
+}
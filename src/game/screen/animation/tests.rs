@@ -0,0 +1,66 @@
+use std::str::FromStr;
+use crate::game::engine::Direction;
+use crate::game::screen::animation::*;
+
+fn animate(level: &str, direction: Direction) -> (Level, MoveAnimation) {
+    let level_original = Level::from_str(level).unwrap();
+    let state = GameplayState::new(level_original.clone()).unwrap();
+
+    let outcome = state.apply_move(&level_original, direction);
+    let animation = MoveAnimation::new(&level_original, &state, direction, &outcome);
+
+    (outcome.into_state().level().clone(), animation)
+}
+
+#[test]
+fn moving_tiles() {
+    let (level, mut animation) = animate("w: 4, h: 1\nP@x-", Direction::Right);
+    assert!(animation.is_moving());
+    assert_eq!(animation.effects, [(Tile::BoxInGoal, (2, 0))]);
+
+    //The new positions show the tiles under the moving tiles until the move has finished
+    assert_eq!(animation.level_without_moving_tiles(&level).to_str(), "w: 4, h: 1\n--x-\n");
+
+    for _ in 0..MoveAnimation::MOVE_FRAMES {
+        assert!(animation.update());
+    }
+    assert!(!animation.is_moving());
+    assert_eq!(animation.level_without_moving_tiles(&level).to_str(), "w: 4, h: 1\n-P+-\n");
+
+    for _ in 1..MoveAnimation::EFFECT_FRAMES {
+        assert!(animation.update());
+    }
+    assert!(!animation.update());
+}
+
+#[test]
+fn effects() {
+    let (level, animation) = animate("w: 3, h: 1\nP@o", Direction::Right);
+    assert_eq!(animation.effects, [(Tile::BoxInHole, (2, 0))]);
+    assert_eq!(animation.level_without_moving_tiles(&level).to_str(), "w: 3, h: 1\n--o\n");
+
+    let (_, animation) = animate("w: 3, h: 1\nP*=", Direction::Right);
+    assert_eq!(animation.effects, [(Tile::LockedDoor, (2, 0))]);
+
+    let (_, animation) = animate("w: 3, h: 1\nP*o", Direction::Right);
+    assert_eq!(animation.effects, [(Tile::Hole, (2, 0))]);
+
+    let (_, animation) = animate("w: 3, h: 1\nP@-", Direction::Right);
+    assert!(animation.effects.is_empty());
+}
+
+#[test]
+fn wrapped_moves() {
+    //Tiles which move across a level edge appear at the other side without animation
+    let (level, animation) = animate("w: 3, h: 1\nP--", Direction::Left);
+    assert!(animation.moving_tiles.is_empty());
+    assert_eq!(animation.level_without_moving_tiles(&level).to_str(), "w: 3, h: 1\n--P\n");
+
+    let (level, animation) = animate("w: 3, h: 1\n@P-", Direction::Left);
+    assert_eq!(animation.moving_tiles.len(), 1);
+    assert_eq!(animation.moving_tiles[0].tile, Tile::Player);
+    assert_eq!(animation.level_without_moving_tiles(&level).to_str(), "w: 3, h: 1\n--@\n");
+
+    let (_, animation) = animate("w: 1, h: 3\n-\n-\nP", Direction::Down);
+    assert!(animation.moving_tiles.is_empty());
+}
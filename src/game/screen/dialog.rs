@@ -1,4 +1,8 @@
-use console_lib::{Key, Color, Console};
+use console_lib::{Key, Color};
+use crate::game::canvas::Canvas;
+
+#[cfg(test)]
+mod tests;
 
 #[derive(Debug, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
 pub enum DialogSelection {
@@ -9,7 +13,7 @@ pub enum DialogSelection {
 }
 
 pub trait Dialog {
-    fn draw_border(&self, console: &Console, x: usize, y: usize, width: usize, height: usize) {
+    fn draw_border(&self, console: &Canvas, x: usize, y: usize, width: usize, height: usize) {
         console.set_cursor_pos(x, y);
         console.draw_text(" ".repeat(width));
 
@@ -24,7 +28,7 @@ pub trait Dialog {
         }
     }
 
-    fn draw(&self, console: &Console, console_width: usize, console_height: usize);
+    fn draw(&self, console: &Canvas, console_width: usize, console_height: usize);
 
     fn on_key_pressed(&self, console_width: usize, console_height: usize, key: Key) -> Option<DialogSelection>;
     fn on_mouse_pressed(&self, console_width: usize, console_height: usize, column: usize, row: usize) -> Option<DialogSelection>;
@@ -41,7 +45,7 @@ impl DialogYesNo {
 }
 
 impl Dialog for DialogYesNo {
-    fn draw(&self, console: &Console, console_width: usize, console_height: usize) {
+    fn draw(&self, console: &Canvas, console_width: usize, console_height: usize) {
         let char_count = self.message.chars().count();
 
         let width = char_count.max(16);
@@ -134,29 +138,74 @@ impl DialogOk {
     }
 }
 
-impl Dialog for DialogOk {
-    fn draw(&self, console: &Console, console_width: usize, console_height: usize) {
-        let char_count = self.message.chars().count();
+impl DialogOk {
+    /// Splits the message into lines which fit into the console (Lines are split at spaces if possible)
+    fn message_lines(&self, console_width: usize) -> Vec<String> {
+        let max_width = console_width.saturating_sub(4).max(16);
+
+        let mut lines = Vec::new();
+        let mut line = String::new();
+        for word in self.message.split(' ') {
+            let line_len = line.chars().count();
+            if line_len > 0 && line_len + 1 + word.chars().count() > max_width {
+                lines.push(std::mem::take(&mut line));
+            }else if line_len > 0 {
+                line.push(' ');
+            }
+
+            for c in word.chars() {
+                if line.chars().count() == max_width {
+                    lines.push(std::mem::take(&mut line));
+                }
+
+                line.push(c);
+            }
+        }
+        lines.push(line);
+
+        lines
+    }
+
+    /// Returns the message lines, the width without border and the start position
+    fn layout(&self, console_width: usize, console_height: usize) -> (Vec<String>, usize, (usize, usize)) {
+        let lines = self.message_lines(console_width);
 
+        let char_count = lines.iter().map(|line| line.chars().count()).max().unwrap_or_default();
         let width = char_count.max(16);
         let width_with_border = width + 2;
 
-        let x_start = ((console_width - width_with_border) as f64 * 0.5) as usize;
-        let y_start = ((console_height - 6) as f64 * 0.5) as usize;
+        let x_start = ((console_width.saturating_sub(width_with_border)) as f64 * 0.5) as usize;
+        let y_start = ((console_height.saturating_sub(lines.len() + 5)) as f64 * 0.5) as usize;
 
-        let whitespace_count_half = ((width - char_count) as f64 * 0.5) as usize;
+        (lines, width, (x_start, y_start))
+    }
+}
+
+impl Dialog for DialogOk {
+    fn draw(&self, console: &Canvas, console_width: usize, console_height: usize) {
+        let (lines, width, (x_start, y_start)) = self.layout(console_width, console_height);
 
         console.set_color(self.fg_color, Color::Yellow);
-        console.set_cursor_pos(x_start + 1, y_start + 1);
-        console.draw_text(format!(
-            "{}{}{}",
-            " ".repeat(whitespace_count_half),
-            self.message,
-            " ".repeat(width - char_count - whitespace_count_half),
-        ));
+        for (i, line) in lines.iter().
+                enumerate() {
+            let char_count = line.chars().count();
+            let whitespace_count_half = ((width - char_count) as f64 * 0.5) as usize;
+
+            console.set_cursor_pos(x_start + 1, y_start + 1 + i);
+            console.draw_text(format!(
+                "{}{}{}",
+                " ".repeat(whitespace_count_half),
+                line,
+                " ".repeat(width - char_count - whitespace_count_half),
+            ));
+        }
+
+        let char_count = lines.iter().map(|line| line.chars().count()).max().unwrap_or_default();
+        let whitespace_count_half = ((width - char_count) as f64 * 0.5) as usize;
+        let y = y_start + lines.len();
 
         console.set_color(Color::Black, Color::Yellow);
-        console.set_cursor_pos(x_start + 1, y_start + 2);
+        console.set_cursor_pos(x_start + 1, y + 1);
         console.draw_text(format!(
             "{}{}{}",
             " ".repeat(whitespace_count_half),
@@ -164,12 +213,12 @@ impl Dialog for DialogOk {
             " ".repeat(width - char_count - whitespace_count_half),
         ));
 
-        console.set_cursor_pos(x_start + 1, y_start + 3);
+        console.set_cursor_pos(x_start + 1, y + 2);
         console.draw_text(" ".repeat(width));
 
         let whitespace_count_half = ((width - 4) as f64 * 0.5) as usize;
 
-        console.set_cursor_pos(x_start + 1, y_start + 4);
+        console.set_cursor_pos(x_start + 1, y + 3);
         console.draw_text(format!(
             "{}[o]k{}",
             " ".repeat(whitespace_count_half),
@@ -177,7 +226,7 @@ impl Dialog for DialogOk {
         ));
 
         console.set_color(Color::LightBlack, Color::Red);
-        self.draw_border(console, x_start, y_start, width_with_border, 5);
+        self.draw_border(console, x_start, y_start, width + 2, lines.len() + 4);
     }
 
     fn on_key_pressed(&self, _: usize, _: usize, key: Key) -> Option<DialogSelection> {
@@ -189,17 +238,11 @@ impl Dialog for DialogOk {
     }
 
     fn on_mouse_pressed(&self, console_width: usize, console_height: usize, column: usize, row: usize) -> Option<DialogSelection> {
-        let char_count = self.message.chars().count();
-
-        let width = char_count.max(16);
-        let width_with_border = width + 2;
-
-        let x_start = ((console_width - width_with_border) as f64 * 0.5) as usize;
-        let y_start = ((console_height - 6) as f64 * 0.5) as usize;
+        let (lines, width, (x_start, y_start)) = self.layout(console_width, console_height);
 
         let whitespace_count_half = ((width - 4) as f64 * 0.5) as usize;
 
-        if row == y_start + 4 && (x_start + whitespace_count_half + 1..x_start + whitespace_count_half + 5).contains(&column) {
+        if row == y_start + lines.len() + 3 && (x_start + whitespace_count_half + 1..x_start + whitespace_count_half + 5).contains(&column) {
             return Some(DialogSelection::Ok);
         }
 
@@ -218,7 +261,7 @@ impl DialogYesCancelNo {
 }
 
 impl Dialog for DialogYesCancelNo {
-    fn draw(&self, console: &Console, console_width: usize, console_height: usize) {
+    fn draw(&self, console: &Canvas, console_width: usize, console_height: usize) {
         let char_count = self.message.chars().count();
 
         let width = char_count.max(31);
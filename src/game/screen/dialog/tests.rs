@@ -0,0 +1,15 @@
+use crate::game::screen::dialog::*;
+
+#[test]
+fn ok_dialog_message_lines() {
+    let dialog = DialogOk::new("Short message");
+    assert_eq!(dialog.message_lines(74), ["Short message"]);
+
+    //Long messages are split at spaces
+    let dialog = DialogOk::new("Cannot load theme: Line 3: \"abc\" is not a valid glyph");
+    assert_eq!(dialog.message_lines(30), ["Cannot load theme: Line 3:", "\"abc\" is not a valid glyph"]);
+
+    //Words which are longer than a line are split
+    let dialog = DialogOk::new(format!("File: /{}", "a".repeat(40)));
+    assert_eq!(dialog.message_lines(24), ["File:", format!("/{}", "a".repeat(19)).as_str(), &"a".repeat(20), "a"]);
+}
@@ -0,0 +1,277 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter, Write as _};
+use std::fs::File;
+use std::io::Write;
+use std::str::FromStr;
+use crate::game::Game;
+use crate::game::theme::Theme;
+
+#[cfg(test)]
+mod tests;
+
+/// The score by which best solutions are ranked, the other score is only compared if both are equal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ranking {
+    Moves,
+    Pushes,
+}
+
+impl Ranking {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Ranking::Moves => "moves",
+            Ranking::Pushes => "pushes",
+        }
+    }
+}
+
+/// Preferences which are stored in the save game folder (Key bindings are stored separately)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Settings {
+    theme: String,
+    player_blink_delay: u32,
+    show_timer: bool,
+    confirm_reset: bool,
+    undo_limit: usize,
+    wide_tiles: bool,
+    animations: bool,
+    ranking: Ranking,
+}
+
+impl Settings {
+    const FILE_NAME: &'static str = "settings.cfg";
+
+    /// Choices for the player blink delay in updates (0 = no blinking)
+    pub const PLAYER_BLINK_DELAYS: [u32; 7] = [0, 3, 6, 9, 12, 18, 25];
+    pub const MAX_PLAYER_BLINK_DELAY: u32 = 250;
+
+    /// Choices for the undo limit (0 = unlimited)
+    pub const UNDO_LIMITS: [usize; 8] = [0, 1, 3, 5, 10, 25, 50, 100];
+
+    /// Reads the settings file from the save game folder, the file is created with the default settings if it does not exist
+    pub fn read_or_create() -> Result<Self, Box<dyn Error>> {
+        let mut settings_file = Game::get_or_create_save_game_folder()?;
+        settings_file.push(Self::FILE_NAME);
+
+        if !std::fs::exists(&settings_file)? {
+            let settings = Self::default();
+            settings.save()?;
+
+            return Ok(settings);
+        }
+
+        let settings_data = std::fs::read_to_string(&settings_file)?;
+        Ok(Self::from_str(&settings_data).map_err(|err| SettingsError::new(format!(
+            "Error while loading settings \"{}\": {err}", settings_file.to_string_lossy(),
+        )))?)
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn Error>> {
+        let mut settings_file = Game::get_or_create_save_game_folder()?;
+        settings_file.push(Self::FILE_NAME);
+
+        let mut file = File::create(settings_file)?;
+        file.write_all(self.to_str().as_bytes())?;
+        file.flush()?;
+
+        Ok(())
+    }
+
+    pub fn theme(&self) -> &str {
+        &self.theme
+    }
+
+    pub fn set_theme(&mut self, theme: impl Into<String>) {
+        self.theme = theme.into();
+    }
+
+    /// The number of updates after which the colors of the player tile are swapped (0 = no blinking)
+    pub fn player_blink_delay(&self) -> u32 {
+        self.player_blink_delay
+    }
+
+    pub fn set_player_blink_delay(&mut self, player_blink_delay: u32) {
+        self.player_blink_delay = player_blink_delay.min(Self::MAX_PLAYER_BLINK_DELAY);
+    }
+
+    pub fn show_timer(&self) -> bool {
+        self.show_timer
+    }
+
+    pub fn set_show_timer(&mut self, show_timer: bool) {
+        self.show_timer = show_timer;
+    }
+
+    /// If true, resetting a level must be confirmed in a dialog
+    pub fn confirm_reset(&self) -> bool {
+        self.confirm_reset
+    }
+
+    pub fn set_confirm_reset(&mut self, confirm_reset: bool) {
+        self.confirm_reset = confirm_reset;
+    }
+
+    /// The number of moves which can be undone in a row (0 = unlimited)
+    pub fn undo_limit(&self) -> usize {
+        self.undo_limit
+    }
+
+    pub fn set_undo_limit(&mut self, undo_limit: usize) {
+        self.undo_limit = undo_limit;
+    }
+
+    /// If true, tiles are drawn with two columns if the level fits in the console
+    pub fn wide_tiles(&self) -> bool {
+        self.wide_tiles
+    }
+
+    pub fn set_wide_tiles(&mut self, wide_tiles: bool) {
+        self.wide_tiles = wide_tiles;
+    }
+
+    /// If true, moves are animated and pushing boxes onto goals or into holes and opening doors is highlighted
+    pub fn animations(&self) -> bool {
+        self.animations
+    }
+
+    pub fn set_animations(&mut self, animations: bool) {
+        self.animations = animations;
+    }
+
+    pub fn ranking(&self) -> Ranking {
+        self.ranking
+    }
+
+    pub fn set_ranking(&mut self, ranking: Ranking) {
+        self.ranking = ranking;
+    }
+
+    pub fn to_str(&self) -> String {
+        let mut out = String::new();
+
+        out += "# Settings (player_blink_delay: Updates of 40 ms, 0 = no blinking; undo_limit: 0 = unlimited)\n";
+        let _ = writeln!(out, "theme = {}", self.theme);
+        let _ = writeln!(out, "player_blink_delay = {}", self.player_blink_delay);
+        let _ = writeln!(out, "show_timer = {}", self.show_timer);
+        let _ = writeln!(out, "confirm_reset = {}", self.confirm_reset);
+        let _ = writeln!(out, "undo_limit = {}", self.undo_limit);
+        let _ = writeln!(out, "wide_tiles = {}", self.wide_tiles);
+        let _ = writeln!(out, "animations = {}", self.animations);
+        let _ = writeln!(out, "ranking = {}", self.ranking.name());
+
+        out
+    }
+
+    /// Returns the next (Or previous if `forward` is false) choice after the current value, wraps around at the end
+    pub fn cycle<T: Copy + Ord>(choices: &[T], current: T, forward: bool) -> T {
+        let next = if forward {
+            choices.iter().find(|choice| **choice > current).or(choices.first())
+        }else {
+            choices.iter().rev().find(|choice| **choice < current).or(choices.last())
+        };
+
+        next.copied().unwrap_or(current)
+    }
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            theme: Theme::DEFAULT_NAME.to_string(),
+            player_blink_delay: Game::PLAYER_BACKGROUND_DELAY,
+            show_timer: true,
+            confirm_reset: false,
+            undo_limit: 0,
+            wide_tiles: true,
+            animations: true,
+            ranking: Ranking::Moves,
+        }
+    }
+}
+
+impl FromStr for Settings {
+    type Err = SettingsError;
+
+    /// Parses settings, settings which are not listed keep their default values
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut settings = Self::default();
+
+        for (i, line) in s.lines().
+                enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with("#") {
+                continue;
+            }
+
+            let error = |message: String| SettingsError::new(format!("Line {}: {message}", i + 1));
+            let parse_bool = |value: &str| match value {
+                "true" => Ok(true),
+                "false" => Ok(false),
+                _ => Err(error(format!("\"{value}\" is not a valid boolean (Expected: \"true\" or \"false\")"))),
+            };
+
+            let Some((name, value)) = line.split_once("=") else {
+                return Err(error("\"=\" is missing".to_string()));
+            };
+            let (name, value) = (name.trim(), value.trim());
+
+            match name {
+                "theme" => {
+                    //User themes are loaded after the settings, only the name is checked here
+                    if !Theme::is_valid_name(value) {
+                        return Err(error(format!("\"{value}\" is not a valid theme name")));
+                    }
+
+                    settings.set_theme(value);
+                },
+                "player_blink_delay" => {
+                    let player_blink_delay = value.parse().ok().
+                            filter(|delay| *delay <= Self::MAX_PLAYER_BLINK_DELAY).
+                            ok_or_else(|| error(format!(
+                                "\"{value}\" is not a valid delay (0 - {})", Self::MAX_PLAYER_BLINK_DELAY,
+                            )))?;
+
+                    settings.set_player_blink_delay(player_blink_delay);
+                },
+                "show_timer" => settings.set_show_timer(parse_bool(value)?),
+                "confirm_reset" => settings.set_confirm_reset(parse_bool(value)?),
+                "undo_limit" => {
+                    let undo_limit = value.parse().
+                            map_err(|_| error(format!("\"{value}\" is not a valid undo limit")))?;
+
+                    settings.set_undo_limit(undo_limit);
+                },
+                "wide_tiles" => settings.set_wide_tiles(parse_bool(value)?),
+                "animations" => settings.set_animations(parse_bool(value)?),
+                "ranking" => settings.set_ranking(match value {
+                    "moves" => Ranking::Moves,
+                    "pushes" => Ranking::Pushes,
+                    _ => return Err(error(format!("\"{value}\" is not a valid ranking (Expected: \"moves\" or \"pushes\")"))),
+                }),
+
+                _ => return Err(error(format!("\"{name}\" is not a valid setting"))),
+            }
+        }
+
+        Ok(settings)
+    }
+}
+
+#[derive(Debug)]
+pub struct SettingsError {
+    message: String
+}
+
+impl SettingsError {
+    fn new(message: impl Into<String>) -> Self {
+        Self { message: message.into() }
+    }
+}
+
+impl Display for SettingsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl Error for SettingsError {}
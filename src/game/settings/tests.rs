@@ -0,0 +1,71 @@
+use std::str::FromStr;
+use crate::game::settings::*;
+
+#[test]
+fn read_settings() {
+    let settings = Settings::from_str(
+        "# Comment\n\nplayer_blink_delay = 6\nshow_timer = false\nundo_limit = 5\nranking = pushes\n",
+    ).unwrap();
+    assert_eq!(settings.player_blink_delay(), 6);
+    assert!(!settings.show_timer());
+    assert_eq!(settings.undo_limit(), 5);
+    assert_eq!(settings.ranking(), Ranking::Pushes);
+
+    //User themes are only checked when they are loaded
+    assert_eq!(Settings::from_str("theme = my_theme").unwrap().theme(), "my_theme");
+
+    //Settings which are not listed keep their default values
+    assert_eq!(settings.theme(), "default");
+    assert!(!settings.confirm_reset());
+}
+
+#[test]
+fn read_errors() {
+    let err = SettingsError::to_string;
+
+    assert_eq!(err(&Settings::from_str("show_timer").unwrap_err()), "Line 1: \"=\" is missing");
+    assert_eq!(err(&Settings::from_str("\nsound = true").unwrap_err()), "Line 2: \"sound\" is not a valid setting");
+    assert_eq!(err(&Settings::from_str("theme = ../pink").unwrap_err()), "Line 1: \"../pink\" is not a valid theme name");
+    assert_eq!(
+        err(&Settings::from_str("confirm_reset = yes").unwrap_err()),
+        "Line 1: \"yes\" is not a valid boolean (Expected: \"true\" or \"false\")",
+    );
+    assert_eq!(err(&Settings::from_str("player_blink_delay = 1000").unwrap_err()), "Line 1: \"1000\" is not a valid delay (0 - 250)");
+    assert_eq!(err(&Settings::from_str("undo_limit = -1").unwrap_err()), "Line 1: \"-1\" is not a valid undo limit");
+    assert_eq!(
+        err(&Settings::from_str("ranking = time").unwrap_err()),
+        "Line 1: \"time\" is not a valid ranking (Expected: \"moves\" or \"pushes\")",
+    );
+}
+
+#[test]
+fn write_settings() {
+    let mut settings = Settings::default();
+    settings.set_confirm_reset(true);
+    settings.set_player_blink_delay(0);
+    settings.set_wide_tiles(false);
+    settings.set_animations(false);
+    settings.set_ranking(Ranking::Pushes);
+
+    let settings_str = settings.to_str();
+    assert!(settings_str.contains("\nconfirm_reset = true\n"), "{settings_str}");
+    assert!(settings_str.contains("\nplayer_blink_delay = 0\n"), "{settings_str}");
+    assert!(settings_str.contains("\nwide_tiles = false\n"), "{settings_str}");
+    assert!(settings_str.contains("\nanimations = false\n"), "{settings_str}");
+    assert!(settings_str.contains("\nranking = pushes\n"), "{settings_str}");
+
+    //Round trip
+    assert_eq!(Settings::from_str(&settings_str).unwrap(), settings);
+}
+
+#[test]
+fn cycle_choices() {
+    assert_eq!(Settings::cycle(&Settings::UNDO_LIMITS, 5, true), 10);
+    assert_eq!(Settings::cycle(&Settings::UNDO_LIMITS, 5, false), 3);
+    assert_eq!(Settings::cycle(&Settings::UNDO_LIMITS, 100, true), 0);
+    assert_eq!(Settings::cycle(&Settings::UNDO_LIMITS, 0, false), 100);
+
+    //Values which are not a choice (e.g. from the settings file) continue with the nearest choice
+    assert_eq!(Settings::cycle(&Settings::UNDO_LIMITS, 7, true), 10);
+    assert_eq!(Settings::cycle(&Settings::UNDO_LIMITS, 7, false), 5);
+}
@@ -0,0 +1,474 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::{mpsc, Arc};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+use crate::game::deadlock::DeadlockAnalyzer;
+use crate::game::engine::{Direction, GameplayState};
+use crate::game::level::{Level, Tile};
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Solution {
+    moves: Vec<Direction>,
+    pushes: usize,
+}
+
+impl Solution {
+    pub fn moves(&self) -> &[Direction] {
+        &self.moves
+    }
+
+    pub fn pushes(&self) -> usize {
+        self.pushes
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolverResult {
+    Solved(Solution),
+    Unsolvable,
+
+    /// The node or time budget was exhausted before a solution was found
+    GaveUp,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Hint {
+    /// The next move of a solution, `pushed` is the position of the box or key which will be pushed by it
+    Move {
+        direction: Direction,
+        pushed: Option<(usize, usize)>,
+    },
+
+    /// The current state is not solvable, but the state after this count of undos is solvable
+    Undo(usize),
+
+    /// No state in the undo history is solvable
+    Reset,
+
+    /// The node or time budget was exhausted before a hint was found
+    GaveUp,
+}
+
+/// A search node: The push which leads from the parent node to this node
+struct Node {
+    parent: usize,
+    player_pos: (usize, usize),
+    direction: Direction,
+}
+
+/// Breadth-first search over pushes (Boxes and keys), walks without pushes are only used to
+/// determine which pushes are possible. The found solutions have the minimal count of pushes.
+/// Pushes which lead to a deadlock are not searched any further.
+pub struct Solver {
+    max_nodes: usize,
+    time_limit: Duration,
+    /// If set, the search gives up as if the time limit was exceeded
+    is_cancelled: Arc<AtomicBool>,
+}
+
+impl Solver {
+    pub const DEFAULT_MAX_NODES: usize = 500000;
+    pub const DEFAULT_TIME_LIMIT: Duration = Duration::from_secs(10);
+
+    pub fn new(max_nodes: usize, time_limit: Duration) -> Self {
+        Self {
+            max_nodes,
+            time_limit,
+            is_cancelled: Default::default(),
+        }
+    }
+
+    fn has_time_run_out(&self, time_start: Instant) -> bool {
+        time_start.elapsed() >= self.time_limit || self.is_cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Searches for a solution starting at `state`
+    ///
+    /// `level_original` must be the level in the state in which it was started
+    pub fn solve(&self, level_original: &Level, state: &GameplayState) -> SolverResult {
+        let time_start = Instant::now();
+        let mut expanded_node_count = 0_usize;
+
+        let mut nodes = vec![Node {
+            parent: usize::MAX,
+            player_pos: state.player_pos(),
+            direction: Direction::Left,
+        }];
+        let mut visited = HashSet::new();
+        let mut queue = VecDeque::new();
+
+        let deadlock_analyzer = DeadlockAnalyzer::new(level_original);
+
+        let reachable = Self::reachable_positions(level_original, state);
+        visited.insert(Self::state_key(state, &reachable));
+        queue.push_back((0, state.clone(), reachable));
+
+        while let Some((node_index, state_current, reachable)) = queue.pop_front() {
+            if nodes.len() >= self.max_nodes || (expanded_node_count.is_multiple_of(64) && self.has_time_run_out(time_start)) {
+                return SolverResult::GaveUp;
+            }
+            expanded_node_count += 1;
+
+            let width = state_current.level().width();
+            let height = state_current.level().height();
+
+            for (index, _) in reachable.iter().
+                    enumerate().
+                    filter(|(_, is_reachable)| **is_reachable) {
+                let pos = (index % width, index / width);
+
+                for direction in Direction::ALL {
+                    let pos_to = direction.next_pos(width, height, pos);
+                    if !matches!(state_current.level().get_tile(pos_to.0, pos_to.1).unwrap(), Tile::Box | Tile::BoxInGoal | Tile::Key | Tile::KeyInGoal) {
+                        continue;
+                    }
+
+                    let outcome = state_current.with_player_pos(level_original, pos).apply_move(level_original, direction);
+                    if !outcome.has_moved() {
+                        continue;
+                    }
+
+                    nodes.push(Node {
+                        parent: node_index,
+                        player_pos: pos,
+                        direction,
+                    });
+
+                    if outcome.has_won() {
+                        return SolverResult::Solved(Self::build_solution(level_original, state.clone(), &nodes));
+                    }
+
+                    let state_new = outcome.into_state();
+                    if deadlock_analyzer.is_deadlocked(state_new.level()) {
+                        nodes.pop();
+
+                        continue;
+                    }
+
+                    let reachable_new = Self::reachable_positions(level_original, &state_new);
+                    if visited.insert(Self::state_key(&state_new, &reachable_new)) {
+                        queue.push_back((nodes.len() - 1, state_new, reachable_new));
+                    }else {
+                        nodes.pop();
+                    }
+                }
+            }
+        }
+
+        SolverResult::Unsolvable
+    }
+
+    /// Searches for the next move from the last state of `states` (The states of the undo history up to the current state)
+    ///
+    /// If the current state is not solvable, the previous states are checked until a solvable state is found.
+    /// The time limit is used for all states together.
+    ///
+    /// `level_original` must be the level in the state in which it was started
+    pub fn hint(&self, level_original: &Level, states: &[GameplayState]) -> Hint {
+        let time_start = Instant::now();
+        let mut checked_state_keys = HashSet::new();
+
+        for (index, state) in states.iter().
+                enumerate().
+                rev() {
+
+            //States which only differ in the player position inside the reachable area have the same result
+            let reachable = Self::reachable_positions(level_original, state);
+            if !checked_state_keys.insert(Self::state_key(state, &reachable)) {
+                continue;
+            }
+
+            let Some(time_limit) = self.time_limit.checked_sub(time_start.elapsed()) else {
+                return Hint::GaveUp;
+            };
+
+            let solver = Solver {
+                max_nodes: self.max_nodes,
+                time_limit,
+                is_cancelled: self.is_cancelled.clone(),
+            };
+            match solver.solve(level_original, state) {
+                SolverResult::Solved(solution) => {
+                    let undo_count = states.len() - 1 - index;
+                    if undo_count > 0 {
+                        return Hint::Undo(undo_count);
+                    }
+
+                    let direction = solution.moves()[0];
+                    let pushed = state.apply_move(level_original, direction).pushed().
+                            map(|(pos_from, _)| pos_from);
+
+                    return Hint::Move { direction, pushed };
+                },
+
+                SolverResult::Unsolvable => {},
+                SolverResult::GaveUp => return Hint::GaveUp,
+            }
+        }
+
+        Hint::Reset
+    }
+
+    /// Returns all positions the player can reach without pushing anything (Indexed by `x + y * width`)
+    fn reachable_positions(level_original: &Level, state: &GameplayState) -> Vec<bool> {
+        let level = state.level();
+        let width = level.width();
+        let height = level.height();
+
+        let mut reachable = vec![false; width * height];
+        let mut queue = VecDeque::new();
+
+        reachable[state.player_pos().0 + state.player_pos().1 * width] = true;
+        queue.push_back(state.player_pos());
+
+        while let Some(pos) = queue.pop_front() {
+            for direction in Direction::ALL {
+                let pos_to = direction.next_pos(width, height, pos);
+                let index_to = pos_to.0 + pos_to.1 * width;
+                if reachable[index_to] {
+                    continue;
+                }
+
+                let tile = if pos_to == state.player_pos() {
+                    GameplayState::tile_under_player(level_original, pos_to)
+                }else {
+                    level.get_tile(pos_to.0, pos_to.1).unwrap().clone()
+                };
+
+                //Entering the secret tile would end the level without completing it
+                if tile != Tile::Secret && GameplayState::is_walkable(&tile, direction) {
+                    reachable[index_to] = true;
+                    queue.push_back(pos_to);
+                }
+            }
+        }
+
+        reachable
+    }
+
+    /// Returns a key which is equal for all states which only differ in the player position inside the reachable area
+    ///
+    /// The whole reachable area is part of the key, because one-way doors can make the area smaller after moving inside of it
+    fn state_key(state: &GameplayState, reachable: &[bool]) -> (Vec<u32>, Vec<u64>) {
+        let tiles = state.level().tiles().iter().
+                enumerate().
+                filter_map(|(index, tile)| {
+                    let kind = match tile {
+                        Tile::Box | Tile::BoxInGoal => 0,
+                        Tile::Key | Tile::KeyInGoal => 1,
+                        Tile::LockedDoor => 2,
+                        Tile::Hole => 3,
+                        _ => return None,
+                    };
+
+                    Some(index as u32 * 4 + kind)
+                }).collect::<Vec<_>>();
+
+        let reachable = reachable.chunks(64).
+                map(|chunk| chunk.iter().
+                        enumerate().
+                        fold(0_u64, |bits, (i, is_reachable)| bits | (*is_reachable as u64) << i)).
+                collect::<Vec<_>>();
+
+        (tiles, reachable)
+    }
+
+    /// Replays the pushes of the last node from the start state and adds the walks in between
+    fn build_solution(level_original: &Level, state_start: GameplayState, nodes: &[Node]) -> Solution {
+        let mut pushes = Vec::new();
+        let mut node_index = nodes.len() - 1;
+        while node_index != 0 {
+            let node = &nodes[node_index];
+            pushes.push((node.player_pos, node.direction));
+
+            node_index = node.parent;
+        }
+        pushes.reverse();
+
+        let mut moves = Vec::new();
+        let mut state = state_start;
+        for (player_pos, direction) in pushes.iter() {
+            for walk_direction in Self::find_walk_path(&state, *player_pos).unwrap() {
+                state = state.apply_move(level_original, walk_direction).into_state();
+                moves.push(walk_direction);
+            }
+
+            state = state.apply_move(level_original, *direction).into_state();
+            moves.push(*direction);
+        }
+
+        Solution {
+            moves,
+            pushes: pushes.len(),
+        }
+    }
+
+    /// Returns the shortest walk (Without pushing anything) from the player position to `pos` or None if `pos` is not reachable
+    pub fn find_walk_path(state: &GameplayState, pos: (usize, usize)) -> Option<Vec<Direction>> {
+        let level = state.level();
+        let width = level.width();
+        let height = level.height();
+
+        let mut came_from: Vec<Option<Direction>> = vec![None; width * height];
+        let mut queue = VecDeque::new();
+
+        let start = state.player_pos();
+        queue.push_back(start);
+
+        while let Some(current) = queue.pop_front() {
+            if current == pos {
+                break;
+            }
+
+            for direction in Direction::ALL {
+                let pos_to = direction.next_pos(width, height, current);
+                let index_to = pos_to.0 + pos_to.1 * width;
+                if pos_to == start || came_from[index_to].is_some() {
+                    continue;
+                }
+
+                let tile = level.get_tile(pos_to.0, pos_to.1).unwrap();
+                if *tile != Tile::Secret && GameplayState::is_walkable(tile, direction) {
+                    came_from[index_to] = Some(direction);
+                    queue.push_back(pos_to);
+                }
+            }
+        }
+
+        let mut path = Vec::new();
+        let mut current = pos;
+        while current != start {
+            let direction = came_from[current.0 + current.1 * width]?;
+
+            path.push(direction);
+            current = direction.opposite().next_pos(width, height, current);
+        }
+        path.reverse();
+
+        Some(path)
+    }
+
+    /// Returns the shortest sequence of moves which pushes the box at `box_pos` to `target` without pushing anything else
+    /// or None if there is none (Or the node or time budget was exhausted)
+    ///
+    /// `level_original` must be the level in the state in which it was started
+    pub fn find_push_path(&self, level_original: &Level, state: &GameplayState, box_pos: (usize, usize), target: (usize, usize)) -> Option<Vec<Direction>> {
+        let width = state.level().width();
+        let height = state.level().height();
+
+        let tile_box = state.level().get_tile(box_pos.0, box_pos.1)?;
+        if !matches!(tile_box, Tile::Box | Tile::BoxInGoal) {
+            return None;
+        }
+
+        //The level without the player and the pushed box
+        let mut level = state.level().clone();
+        let player_pos = state.player_pos();
+        level.set_tile(player_pos.0, player_pos.1, GameplayState::tile_under_player(level_original, player_pos));
+        level.set_tile(box_pos.0, box_pos.1, if *tile_box == Tile::BoxInGoal { Tile::Goal } else { Tile::Empty });
+
+        let node_key = |box_pos: (usize, usize), player_pos: (usize, usize)| {
+            (box_pos.0 + box_pos.1 * width) * width * height + player_pos.0 + player_pos.1 * width
+        };
+
+        let time_start = Instant::now();
+
+        let start_key = node_key(box_pos, player_pos);
+        let mut came_from: HashMap<usize, (usize, Direction)> = HashMap::new();
+        let mut queue = VecDeque::from([(box_pos, player_pos)]);
+
+        let mut end_key = None;
+        while let Some((box_current, player_current)) = queue.pop_front() {
+            if box_current == target {
+                end_key = Some(node_key(box_current, player_current));
+
+                break;
+            }
+
+            if came_from.len() >= self.max_nodes || self.has_time_run_out(time_start) {
+                return None;
+            }
+
+            //A box which fell into a hole cannot be pushed any further
+            if *level.get_tile(box_current.0, box_current.1).unwrap() == Tile::Hole {
+                continue;
+            }
+
+            let key_current = node_key(box_current, player_current);
+            for direction in Direction::ALL {
+                let player_next = direction.next_pos(width, height, player_current);
+                let box_next = if player_next == box_current {
+                    let box_next = direction.next_pos(width, height, box_current);
+                    let tile = level.get_tile(box_next.0, box_next.1).unwrap();
+                    if !matches!(tile, Tile::Empty | Tile::Goal | Tile::Hole | Tile::BoxInHole) {
+                        continue;
+                    }
+
+                    box_next
+                }else {
+                    let tile = level.get_tile(player_next.0, player_next.1).unwrap();
+                    if *tile == Tile::Secret || !GameplayState::is_walkable(tile, direction) {
+                        continue;
+                    }
+
+                    box_current
+                };
+
+                let key_next = node_key(box_next, player_next);
+                if key_next == start_key || came_from.contains_key(&key_next) {
+                    continue;
+                }
+
+                came_from.insert(key_next, (key_current, direction));
+                queue.push_back((box_next, player_next));
+            }
+        }
+
+        let mut path = Vec::new();
+        let mut current = end_key?;
+        while current != start_key {
+            let (parent, direction) = came_from[&current];
+
+            path.push(direction);
+            current = parent;
+        }
+        path.reverse();
+
+        Some(path)
+    }
+}
+
+/// A search of a solver which runs in a separate thread, the search is cancelled if the task is dropped
+pub struct SolverTask<T> {
+    receiver: mpsc::Receiver<T>,
+    is_cancelled: Arc<AtomicBool>,
+}
+
+impl<T: Send + 'static> SolverTask<T> {
+    pub fn spawn(solver: Solver, search: impl FnOnce(&Solver) -> T + Send + 'static) -> Self {
+        let is_cancelled = solver.is_cancelled.clone();
+
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            //The receiver is gone if the task was cancelled
+            let _ = sender.send(search(&solver));
+        });
+
+        Self { receiver, is_cancelled }
+    }
+
+    /// Returns the result if the search has finished
+    pub fn result(&self) -> Option<T> {
+        self.receiver.try_recv().ok()
+    }
+}
+
+impl<T> Drop for SolverTask<T> {
+    fn drop(&mut self) {
+        self.is_cancelled.store(true, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,206 @@
+use std::str::FromStr;
+use std::time::Duration;
+use crate::game::solver::*;
+
+fn solve(level: &str) -> SolverResult {
+    let level = Level::from_str(level).unwrap();
+    let state = GameplayState::new(level.clone()).unwrap();
+
+    Solver::new(Solver::DEFAULT_MAX_NODES, Duration::from_secs(60)).solve(&level, &state)
+}
+
+fn assert_solution_wins(level: &str, solution: &Solution) {
+    let level = Level::from_str(level).unwrap();
+    let mut state = GameplayState::new(level.clone()).unwrap();
+
+    for (i, direction) in solution.moves().iter().
+            enumerate() {
+        let outcome = state.apply_move(&level, *direction);
+        assert!(outcome.has_moved());
+
+        if i == solution.moves().len() - 1 {
+            assert!(outcome.has_won());
+        }else {
+            assert!(!outcome.has_won());
+        }
+
+        state = outcome.into_state();
+    }
+}
+
+#[test]
+fn single_push() {
+    let level = "w: 10, h: 3\n##########\n#P-----@x#\n##########";
+
+    let SolverResult::Solved(solution) = solve(level) else {
+        panic!("Level should be solvable");
+    };
+    assert_eq!(solution.moves().len(), 6);
+    assert_eq!(solution.pushes(), 1);
+    assert_solution_wins(level, &solution);
+}
+
+#[test]
+fn multiple_boxes() {
+    let level = "w: 9, h: 4\n#########\n##----@x#\n#P-##-@x#\n#########";
+
+    let SolverResult::Solved(solution) = solve(level) else {
+        panic!("Level should be solvable");
+    };
+    assert_solution_wins(level, &solution);
+}
+
+#[test]
+fn wrap_around() {
+    //The player must walk across the level edge to push the box from the other side
+    let level = "w: 5, h: 1\n@Px--";
+
+    let SolverResult::Solved(solution) = solve(level) else {
+        panic!("Level should be solvable");
+    };
+    assert_eq!(solution.moves(), &[Direction::Right; 5]);
+    assert_eq!(solution.pushes(), 2);
+    assert_solution_wins(level, &solution);
+}
+
+#[test]
+fn key_and_locked_door() {
+    let level = "w: 7, h: 3\n#######\n#P*=@x#\n#######";
+
+    let SolverResult::Solved(solution) = solve(level) else {
+        panic!("Level should be solvable");
+    };
+    assert_eq!(solution.pushes(), 2);
+    assert_solution_wins(level, &solution);
+}
+
+#[test]
+fn hole() {
+    let level = "w: 8, h: 3\n########\n#P@o@-x#\n########";
+
+    let SolverResult::Solved(solution) = solve(level) else {
+        panic!("Level should be solvable");
+    };
+    assert_solution_wins(level, &solution);
+}
+
+#[test]
+fn one_way_door_blocks_box() {
+    let level = "w: 7, h: 3\n#######\n#P@>-x#\n#######";
+
+    assert_eq!(solve(level), SolverResult::Unsolvable);
+}
+
+#[test]
+fn box_in_corner() {
+    let level = "w: 6, h: 4\n######\n#@---#\n#-P-x#\n######";
+
+    assert_eq!(solve(level), SolverResult::Unsolvable);
+}
+
+#[test]
+fn gave_up() {
+    let level = Level::from_str("w: 9, h: 4\n#########\n##----@x#\n#P-##-@x#\n#########").unwrap();
+    let state = GameplayState::new(level.clone()).unwrap();
+
+    assert_eq!(Solver::new(2, Duration::from_secs(60)).solve(&level, &state), SolverResult::GaveUp);
+}
+
+#[test]
+fn walk_path() {
+    let level = Level::from_str("w: 5, h: 3\n#####\n#P#-#\n#---#").unwrap();
+    let state = GameplayState::new(level).unwrap();
+
+    assert_eq!(
+        Solver::find_walk_path(&state, (3, 1)),
+        Some(vec![Direction::Down, Direction::Right, Direction::Right, Direction::Up]),
+    );
+    assert_eq!(Solver::find_walk_path(&state, (2, 1)), None);
+}
+
+#[test]
+fn push_path() {
+    let level = Level::from_str("w: 7, h: 5\n#######\n#-----#\n#-P@--#\n#-----#\n#######").unwrap();
+    let state = GameplayState::new(level.clone()).unwrap();
+    let solver = Solver::new(Solver::DEFAULT_MAX_NODES, Solver::DEFAULT_TIME_LIMIT);
+
+    assert_eq!(solver.find_push_path(&level, &state, (3, 2), (4, 2)), Some(vec![Direction::Right]));
+    assert_eq!(
+        solver.find_push_path(&level, &state, (3, 2), (3, 1)),
+        Some(vec![Direction::Down, Direction::Right, Direction::Up]),
+    );
+
+    //Only boxes can be pushed to a target
+    assert_eq!(solver.find_push_path(&level, &state, (4, 2), (5, 2)), None);
+
+    //Boxes cannot be pulled out of corners
+    let level = Level::from_str("w: 5, h: 4\n#####\n#@--#\n#--P#\n#####").unwrap();
+    let state = GameplayState::new(level.clone()).unwrap();
+    assert_eq!(solver.find_push_path(&level, &state, (1, 1), (2, 1)), None);
+}
+
+fn hint(level: &str, moves: &[Direction]) -> Hint {
+    let level = Level::from_str(level).unwrap();
+    let mut states = vec![GameplayState::new(level.clone()).unwrap()];
+
+    for direction in moves {
+        let outcome = states.last().unwrap().apply_move(&level, *direction);
+        assert!(outcome.has_moved());
+
+        states.push(outcome.into_state());
+    }
+
+    Solver::new(Solver::DEFAULT_MAX_NODES, Duration::from_secs(60)).hint(&level, &states)
+}
+
+#[test]
+fn hint_move() {
+    let level = "w: 10, h: 3\n##########\n#P-----@x#\n##########";
+
+    assert_eq!(hint(level, &[]), Hint::Move { direction: Direction::Right, pushed: None });
+    assert_eq!(hint(level, &[Direction::Right; 5]), Hint::Move { direction: Direction::Right, pushed: Some((7, 1)) });
+}
+
+#[test]
+fn hint_undo() {
+    let level = "w: 7, h: 5\n#######\n#-----#\n#-P@--#\n#----x#\n#######";
+
+    //The box is pushed against the top wall and the player walks away from it
+    assert_eq!(
+        hint(level, &[Direction::Right, Direction::Down, Direction::Right, Direction::Up, Direction::Left]),
+        Hint::Undo(2),
+    );
+}
+
+#[test]
+fn hint_reset() {
+    let level = "w: 6, h: 4\n######\n#@---#\n#-P-x#\n######";
+
+    assert_eq!(hint(level, &[Direction::Right]), Hint::Reset);
+}
+
+#[test]
+fn cancel_task() {
+    let level = Level::from_str("w: 10, h: 3\n##########\n#P-----@x#\n##########").unwrap();
+    let state = GameplayState::new(level.clone()).unwrap();
+
+    let task = SolverTask::spawn(Solver::new(Solver::DEFAULT_MAX_NODES, Duration::from_secs(60)), move |solver| {
+        solver.solve(&level, &state)
+    });
+
+    let result = loop {
+        if let Some(result) = task.result() {
+            break result;
+        }
+
+        thread::sleep(Duration::from_millis(1));
+    };
+    assert!(matches!(result, SolverResult::Solved(_)));
+
+    //A cancelled solver gives up immediately
+    let solver = Solver::new(Solver::DEFAULT_MAX_NODES, Duration::from_secs(60));
+    solver.is_cancelled.store(true, Ordering::Relaxed);
+    let level = Level::from_str("w: 10, h: 3\n##########\n#P-----@x#\n##########").unwrap();
+    let state = GameplayState::new(level.clone()).unwrap();
+    assert_eq!(solver.solve(&level, &state), SolverResult::GaveUp);
+}
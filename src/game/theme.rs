@@ -0,0 +1,510 @@
+use console_lib::Color;
+use crate::game::canvas::Canvas;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{Display, Formatter, Write as _};
+use std::fs::File;
+use std::io::Write;
+use crate::game::Game;
+use crate::game::level::Tile;
+
+#[cfg(test)]
+mod tests;
+
+/// An element of the game which is drawn with the colors and the glyph of a theme
+#[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum ThemeElement {
+    Empty,
+    OneWayLeft,
+    OneWayUp,
+    OneWayRight,
+    OneWayDown,
+    Wall,
+    Player,
+    /// The player tile while its colors are swapped (Blinking)
+    PlayerBlink,
+    /// The player on a goal in the level editor
+    PlayerInGoal,
+    Key,
+    KeyInGoal,
+    LockedDoor,
+    Box,
+    /// A box which can never reach a goal
+    BoxDeadlocked,
+    BoxInGoal,
+    Goal,
+    Hole,
+    BoxInHole,
+    DecorationBlank,
+    Secret,
+
+    /// Completed levels and level packs in the selection screens (The glyph is drawn after the level number)
+    LevelCompleted,
+    /// The first level which was not completed yet in the level selection and level packs which were not completed
+    LevelNext,
+    /// Levels which can not be played yet
+    LevelLocked,
+    /// The border around the selected level or level pack (The glyph is not used)
+    Selection,
+    /// Hint messages in the game screen (The glyph is not used)
+    HintText,
+    /// Deadlock messages in the game screen (The glyph is not used)
+    DeadlockText,
+}
+
+impl ThemeElement {
+    pub const ALL: [ThemeElement; 26] = [
+        ThemeElement::Empty, ThemeElement::OneWayLeft, ThemeElement::OneWayUp, ThemeElement::OneWayRight,
+        ThemeElement::OneWayDown, ThemeElement::Wall, ThemeElement::Player, ThemeElement::PlayerBlink,
+        ThemeElement::PlayerInGoal, ThemeElement::Key, ThemeElement::KeyInGoal, ThemeElement::LockedDoor, ThemeElement::Box, ThemeElement::BoxDeadlocked,
+        ThemeElement::BoxInGoal, ThemeElement::Goal, ThemeElement::Hole, ThemeElement::BoxInHole,
+        ThemeElement::DecorationBlank, ThemeElement::Secret,
+
+        ThemeElement::LevelCompleted, ThemeElement::LevelNext, ThemeElement::LevelLocked, ThemeElement::Selection,
+        ThemeElement::HintText, ThemeElement::DeadlockText,
+    ];
+
+    pub fn from_tile(tile: &Tile) -> Self {
+        match tile {
+            Tile::Empty => ThemeElement::Empty,
+            Tile::OneWayLeft => ThemeElement::OneWayLeft,
+            Tile::OneWayUp => ThemeElement::OneWayUp,
+            Tile::OneWayRight => ThemeElement::OneWayRight,
+            Tile::OneWayDown => ThemeElement::OneWayDown,
+            Tile::Wall => ThemeElement::Wall,
+            Tile::Player => ThemeElement::Player,
+            Tile::PlayerInGoal => ThemeElement::PlayerInGoal,
+            Tile::Key => ThemeElement::Key,
+            Tile::KeyInGoal => ThemeElement::KeyInGoal,
+            Tile::LockedDoor => ThemeElement::LockedDoor,
+            Tile::Box => ThemeElement::Box,
+            Tile::BoxInGoal => ThemeElement::BoxInGoal,
+            Tile::Goal => ThemeElement::Goal,
+            Tile::Hole => ThemeElement::Hole,
+            Tile::BoxInHole => ThemeElement::BoxInHole,
+            Tile::DecorationBlank => ThemeElement::DecorationBlank,
+            Tile::Secret => ThemeElement::Secret,
+        }
+    }
+
+    /// The name of the element in theme files
+    pub fn name(&self) -> &'static str {
+        match self {
+            ThemeElement::Empty => "empty",
+            ThemeElement::OneWayLeft => "one_way_left",
+            ThemeElement::OneWayUp => "one_way_up",
+            ThemeElement::OneWayRight => "one_way_right",
+            ThemeElement::OneWayDown => "one_way_down",
+            ThemeElement::Wall => "wall",
+            ThemeElement::Player => "player",
+            ThemeElement::PlayerBlink => "player_blink",
+            ThemeElement::PlayerInGoal => "player_in_goal",
+            ThemeElement::Key => "key",
+            ThemeElement::KeyInGoal => "key_in_goal",
+            ThemeElement::LockedDoor => "locked_door",
+            ThemeElement::Box => "box",
+            ThemeElement::BoxDeadlocked => "box_deadlocked",
+            ThemeElement::BoxInGoal => "box_in_goal",
+            ThemeElement::Goal => "goal",
+            ThemeElement::Hole => "hole",
+            ThemeElement::BoxInHole => "box_in_hole",
+            ThemeElement::DecorationBlank => "decoration_blank",
+            ThemeElement::Secret => "secret",
+
+            ThemeElement::LevelCompleted => "level_completed",
+            ThemeElement::LevelNext => "level_next",
+            ThemeElement::LevelLocked => "level_locked",
+            ThemeElement::Selection => "selection",
+            ThemeElement::HintText => "hint_text",
+            ThemeElement::DeadlockText => "deadlock_text",
+        }
+    }
+}
+
+/// Foreground color, background color and glyph of a theme element
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Style {
+    pub fg: Color,
+    pub bg: Color,
+    pub glyph: char,
+}
+
+impl Style {
+    pub const fn new(fg: Color, bg: Color, glyph: char) -> Self {
+        Self { fg, bg, glyph }
+    }
+
+    /// Draws the glyph at the current cursor position
+    pub fn draw(&self, console: &Canvas, inverted: bool) {
+        //Inverted colors are not visible if both colors are the same (e.g. the "no_color" theme)
+        let is_underlined = inverted && self.fg == self.bg;
+        if is_underlined {
+            console.set_underline(true);
+        }
+
+        console.set_color_invertible(self.fg, self.bg, inverted);
+        console.draw_text(self.glyph.to_string());
+
+        if is_underlined {
+            console.set_underline(false);
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Theme {
+    name: String,
+    styles: HashMap<ThemeElement, Style>,
+}
+
+impl Theme {
+    pub const DEFAULT_NAME: &'static str = "default";
+
+    /// "light" is meant for light terminal backgrounds, "high_contrast" uses colors which can be distinguished with
+    /// color blindness and "no_color" distinguishes all tiles only by their glyph
+    pub const BUILT_IN_NAMES: [&'static str; 4] = [Self::DEFAULT_NAME, "light", "high_contrast", "no_color"];
+
+    pub const MAX_NAME_LEN: usize = 24;
+
+    const FILE_EXTENSION: &'static str = ".theme";
+    const EXAMPLE_FILE_NAME: &'static str = "theme_example.txt";
+
+    const COLOR_NAMES: [(&'static str, Color); 17] = [
+        ("black", Color::Black), ("blue", Color::Blue), ("green", Color::Green), ("cyan", Color::Cyan),
+        ("red", Color::Red), ("pink", Color::Pink), ("yellow", Color::Yellow), ("white", Color::White),
+        ("light_black", Color::LightBlack), ("light_blue", Color::LightBlue), ("light_green", Color::LightGreen),
+        ("light_cyan", Color::LightCyan), ("light_red", Color::LightRed), ("light_pink", Color::LightPink),
+        ("light_yellow", Color::LightYellow), ("light_white", Color::LightWhite), ("default", Color::Default),
+    ];
+
+    /// Returns the built-in theme with the name
+    pub fn built_in(name: &str) -> Option<Self> {
+        let style_fn = match name {
+            Self::DEFAULT_NAME => Self::default_style,
+            "light" => Self::light_style,
+            "high_contrast" => Self::high_contrast_style,
+            "no_color" => Self::no_color_style,
+
+            _ => return None,
+        };
+
+        Some(Self {
+            name: name.to_string(),
+            styles: HashMap::from_iter(ThemeElement::ALL.map(|element| (element, style_fn(element)))),
+        })
+    }
+
+    /// Returns the built-in theme or reads the user theme "<name>.theme" from the save game folder
+    pub fn load(name: &str) -> Result<Self, Box<dyn Error>> {
+        if let Some(theme) = Self::built_in(name) {
+            return Ok(theme);
+        }
+
+        if !Self::is_valid_name(name) {
+            return Err(Box::new(ThemeError::new(format!("\"{name}\" is not a valid theme name"))));
+        }
+
+        let mut theme_file = Game::get_or_create_save_game_folder()?;
+        theme_file.push(format!("{name}{}", Self::FILE_EXTENSION));
+
+        if !std::fs::exists(&theme_file)? {
+            return Err(Box::new(ThemeError::new(format!("Theme \"{name}\" does not exist"))));
+        }
+
+        let theme_data = std::fs::read_to_string(&theme_file)?;
+        Ok(Self::read_from_str(name, &theme_data).map_err(|err| ThemeError::new(format!(
+            "Error while loading theme \"{}\": {err}", theme_file.to_string_lossy(),
+        )))?)
+    }
+
+    /// Writes the default theme in the format of user themes to "theme_example.txt" in the save game folder if it does not exist
+    pub fn create_example_file() -> Result<(), Box<dyn Error>> {
+        let mut example_file = Game::get_or_create_save_game_folder()?;
+        example_file.push(Self::EXAMPLE_FILE_NAME);
+
+        if !std::fs::exists(&example_file)? {
+            let mut file = File::create(example_file)?;
+            file.write_all(Self::built_in(Self::DEFAULT_NAME).unwrap().to_str().as_bytes())?;
+            file.flush()?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the names of all built-in themes followed by the names of all user themes in the save game folder
+    pub fn available_names() -> Result<Vec<String>, Box<dyn Error>> {
+        let mut names = Self::BUILT_IN_NAMES.map(|name| name.to_string()).to_vec();
+
+        let mut user_theme_names = Vec::new();
+        for entry in std::fs::read_dir(Game::get_or_create_save_game_folder()?)? {
+            let entry = entry?;
+            if !entry.path().is_file() {
+                continue;
+            }
+
+            if let Some(name) = entry.file_name().to_str().
+                    and_then(|file_name| file_name.strip_suffix(Self::FILE_EXTENSION)).
+                    filter(|name| Self::is_valid_name(name) && !names.iter().any(|built_in| built_in == name)) {
+                user_theme_names.push(name.to_string());
+            }
+        }
+
+        user_theme_names.sort();
+        names.append(&mut user_theme_names);
+
+        Ok(names)
+    }
+
+    pub fn is_valid_name(name: &str) -> bool {
+        !name.is_empty() && name.len() <= Self::MAX_NAME_LEN &&
+                name.bytes().all(|c| c.is_ascii_alphanumeric() || c == b'_' || c == b'-')
+    }
+
+    /// Parses a user theme
+    ///
+    /// Each line has the format "<element> = <foreground color> <background color> [<glyph>]" (Glyphs are ASCII characters
+    /// and "SPACE" is a space glyph),
+    /// elements which are not listed use the style of the theme in the optional first line "base = <built-in theme>"
+    /// (Or of the default theme).
+    pub fn read_from_str(name: impl Into<String>, theme_data: &str) -> Result<Self, ThemeError> {
+        let mut theme = Self::built_in(Self::DEFAULT_NAME).unwrap();
+        theme.name = name.into();
+
+        let mut elements_defined = Vec::new();
+        for (i, line) in theme_data.lines().
+                enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with("#") {
+                continue;
+            }
+
+            let error = |message: String| ThemeError::new(format!("Line {}: {message}", i + 1));
+
+            let Some((key, value)) = line.split_once("=") else {
+                return Err(error("\"=\" is missing".to_string()));
+            };
+            let (key, value) = (key.trim(), value.trim());
+
+            if key == "base" {
+                if !elements_defined.is_empty() {
+                    return Err(error("The base theme must be defined before all elements".to_string()));
+                }
+
+                let base_theme = Self::built_in(value).ok_or_else(|| error(format!("\"{value}\" is not a built-in theme")))?;
+                theme.styles = base_theme.styles;
+
+                continue;
+            }
+
+            let Some(element) = ThemeElement::ALL.into_iter().find(|element| element.name() == key) else {
+                return Err(error(format!("\"{key}\" is not a valid element")));
+            };
+
+            if elements_defined.contains(&element) {
+                return Err(error(format!("Element \"{key}\" is defined multiple times")));
+            }
+            elements_defined.push(element);
+
+            let parse_color = |color: &str| Self::COLOR_NAMES.iter().
+                    find(|(name, _)| *name == color).
+                    map(|(_, color)| *color).
+                    ok_or_else(|| error(format!("\"{color}\" is not a valid color")));
+
+            let parts = value.split_whitespace().collect::<Vec<_>>();
+            let (fg, bg, glyph) = match parts[..] {
+                [fg, bg] => (fg, bg, None),
+                [fg, bg, glyph] => (fg, bg, Some(glyph)),
+
+                _ => return Err(error("Expected \"<foreground color> <background color> [<glyph>]\"".to_string())),
+            };
+
+            let style = theme.styles.get_mut(&element).unwrap();
+            style.fg = parse_color(fg)?;
+            style.bg = parse_color(bg)?;
+
+            if let Some(glyph) = glyph {
+                let mut chars = glyph.chars();
+                style.glyph = match (chars.next(), chars.next()) {
+                    _ if glyph == "SPACE" => ' ',
+                    (Some(c), None) if c.is_ascii_graphic() => c,
+
+                    //Non-ASCII characters are escaped, because the console can only draw ASCII characters
+                    _ => return Err(error(format!(
+                        "\"{}\" is not a valid glyph (Expected a single ASCII character)", glyph.escape_default(),
+                    ))),
+                };
+            }
+        }
+
+        Ok(theme)
+    }
+
+    /// Converts the theme to the format of user themes
+    pub fn to_str(&self) -> String {
+        let mut out = String::new();
+
+        let color_name = |color: Color| Self::COLOR_NAMES.iter().
+                find(|(_, color_named)| *color_named == color).
+                map_or("default", |(name, _)| name);
+
+        let _ = writeln!(out, "# Theme: {} (Copy to \"<name>{}\" to create a user theme)", self.name, Self::FILE_EXTENSION);
+        let _ = writeln!(out, "# <element> = <foreground color> <background color> [<glyph>] (Optional first line: base = <built-in theme>)");
+        let _ = writeln!(out, "# Colors: {}", Self::COLOR_NAMES.map(|(name, _)| name).join(", "));
+        for element in ThemeElement::ALL {
+            let style = self.style(element);
+            let glyph = if style.glyph == ' ' {
+                "SPACE".to_string()
+            }else {
+                style.glyph.to_string()
+            };
+
+            let _ = writeln!(out, "{} = {} {} {}", element.name(), color_name(style.fg), color_name(style.bg), glyph);
+        }
+
+        out
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn style(&self, element: ThemeElement) -> Style {
+        self.styles.get(&element).copied().unwrap_or_else(|| Self::default_style(element))
+    }
+
+    /// Sets the foreground and background color of the element (The glyph is not drawn)
+    pub fn set_color(&self, console: &Canvas, element: ThemeElement) {
+        let style = self.style(element);
+        console.set_color(style.fg, style.bg);
+    }
+
+    fn default_style(element: ThemeElement) -> Style {
+        match element {
+            ThemeElement::Empty => Style::new(Color::LightBlue, Color::Default, '-'),
+            ThemeElement::OneWayLeft => Style::new(Color::LightBlue, Color::Default, '<'),
+            ThemeElement::OneWayUp => Style::new(Color::LightBlue, Color::Default, '^'),
+            ThemeElement::OneWayRight => Style::new(Color::LightBlue, Color::Default, '>'),
+            ThemeElement::OneWayDown => Style::new(Color::LightBlue, Color::Default, 'v'),
+            ThemeElement::Wall => Style::new(Color::LightGreen, Color::Default, '#'),
+            ThemeElement::Player => Style::new(Color::Yellow, Color::Default, 'P'),
+            ThemeElement::PlayerBlink => Style::new(Color::Default, Color::Yellow, 'P'),
+            ThemeElement::PlayerInGoal => Style::new(Color::LightPink, Color::Default, 'P'),
+            ThemeElement::Key => Style::new(Color::LightCyan, Color::Default, '*'),
+            ThemeElement::KeyInGoal => Style::new(Color::LightPink, Color::Default, '*'),
+            ThemeElement::LockedDoor => Style::new(Color::LightRed, Color::Default, '='),
+            ThemeElement::Box => Style::new(Color::LightCyan, Color::Default, '@'),
+            ThemeElement::BoxDeadlocked => Style::new(Color::Red, Color::Default, '@'),
+            ThemeElement::BoxInGoal => Style::new(Color::LightPink, Color::Default, '@'),
+            ThemeElement::Goal => Style::new(Color::LightRed, Color::Default, 'x'),
+            ThemeElement::Hole => Style::new(Color::LightBlue, Color::Default, 'O'),
+            ThemeElement::BoxInHole => Style::new(Color::Default, Color::LightBlue, '@'),
+            ThemeElement::DecorationBlank => Style::new(Color::LightBlue, Color::Default, ' '),
+            ThemeElement::Secret => Style::new(Color::LightBlue, Color::Default, '+'),
+
+            ThemeElement::LevelCompleted => Style::new(Color::Black, Color::Green, '|'),
+            ThemeElement::LevelNext => Style::new(Color::Black, Color::Yellow, '|'),
+            ThemeElement::LevelLocked => Style::new(Color::Black, Color::Red, '|'),
+            ThemeElement::Selection => Style::new(Color::Cyan, Color::Default, ' '),
+            ThemeElement::HintText => Style::new(Color::Yellow, Color::Default, ' '),
+            ThemeElement::DeadlockText => Style::new(Color::Red, Color::Default, ' '),
+        }
+    }
+
+    fn light_style(element: ThemeElement) -> Style {
+        match element {
+            ThemeElement::Empty => Style::new(Color::LightBlack, Color::Default, '-'),
+            ThemeElement::OneWayLeft => Style::new(Color::Blue, Color::Default, '<'),
+            ThemeElement::OneWayUp => Style::new(Color::Blue, Color::Default, '^'),
+            ThemeElement::OneWayRight => Style::new(Color::Blue, Color::Default, '>'),
+            ThemeElement::OneWayDown => Style::new(Color::Blue, Color::Default, 'v'),
+            ThemeElement::Wall => Style::new(Color::Green, Color::Default, '#'),
+            ThemeElement::Player => Style::new(Color::Black, Color::Default, 'P'),
+            ThemeElement::PlayerBlink => Style::new(Color::LightWhite, Color::Black, 'P'),
+            ThemeElement::PlayerInGoal => Style::new(Color::Pink, Color::Default, 'P'),
+            ThemeElement::Key => Style::new(Color::Cyan, Color::Default, '*'),
+            ThemeElement::KeyInGoal => Style::new(Color::Pink, Color::Default, '*'),
+            ThemeElement::LockedDoor => Style::new(Color::Red, Color::Default, '='),
+            ThemeElement::Box => Style::new(Color::Blue, Color::Default, '@'),
+            ThemeElement::BoxInGoal => Style::new(Color::Pink, Color::Default, '@'),
+            ThemeElement::Goal => Style::new(Color::Red, Color::Default, 'x'),
+            ThemeElement::Hole => Style::new(Color::Blue, Color::Default, 'O'),
+            ThemeElement::BoxInHole => Style::new(Color::LightWhite, Color::Blue, '@'),
+            ThemeElement::Secret => Style::new(Color::LightBlack, Color::Default, '+'),
+
+            ThemeElement::Selection => Style::new(Color::Blue, Color::Default, ' '),
+            ThemeElement::HintText => Style::new(Color::Pink, Color::Default, ' '),
+
+            element => Self::default_style(element),
+        }
+    }
+
+    fn high_contrast_style(element: ThemeElement) -> Style {
+        match element {
+            ThemeElement::Empty => Style::new(Color::LightBlack, Color::Default, '-'),
+            ThemeElement::OneWayLeft => Style::new(Color::LightWhite, Color::Default, '<'),
+            ThemeElement::OneWayUp => Style::new(Color::LightWhite, Color::Default, '^'),
+            ThemeElement::OneWayRight => Style::new(Color::LightWhite, Color::Default, '>'),
+            ThemeElement::OneWayDown => Style::new(Color::LightWhite, Color::Default, 'v'),
+            ThemeElement::Wall => Style::new(Color::LightWhite, Color::Default, '#'),
+            ThemeElement::Player => Style::new(Color::LightYellow, Color::Default, 'P'),
+            ThemeElement::PlayerBlink => Style::new(Color::Black, Color::LightYellow, 'P'),
+            ThemeElement::PlayerInGoal => Style::new(Color::LightYellow, Color::Default, '&'),
+            ThemeElement::Key => Style::new(Color::LightCyan, Color::Default, '*'),
+            ThemeElement::KeyInGoal => Style::new(Color::LightYellow, Color::Default, '~'),
+            ThemeElement::LockedDoor => Style::new(Color::LightWhite, Color::Default, '='),
+            ThemeElement::Box => Style::new(Color::LightCyan, Color::Default, '@'),
+            ThemeElement::BoxDeadlocked => Style::new(Color::Black, Color::LightPink, '@'),
+            ThemeElement::BoxInGoal => Style::new(Color::LightYellow, Color::Default, '+'),
+            ThemeElement::Goal => Style::new(Color::LightYellow, Color::Default, 'x'),
+            ThemeElement::Hole => Style::new(Color::LightBlue, Color::Default, 'O'),
+            ThemeElement::BoxInHole => Style::new(Color::Black, Color::LightBlue, '.'),
+            ThemeElement::Secret => Style::new(Color::LightBlack, Color::Default, '+'),
+
+            ThemeElement::LevelCompleted => Style::new(Color::Black, Color::LightCyan, '|'),
+            ThemeElement::LevelNext => Style::new(Color::Black, Color::LightYellow, '<'),
+            ThemeElement::LevelLocked => Style::new(Color::LightWhite, Color::LightBlack, '|'),
+            ThemeElement::Selection => Style::new(Color::LightYellow, Color::Default, ' '),
+            ThemeElement::HintText => Style::new(Color::LightYellow, Color::Default, ' '),
+            ThemeElement::DeadlockText => Style::new(Color::LightPink, Color::Default, ' '),
+
+            element => Self::default_style(element),
+        }
+    }
+
+    fn no_color_style(element: ThemeElement) -> Style {
+        let glyph = match element {
+            ThemeElement::PlayerInGoal => '&',
+            ThemeElement::KeyInGoal => '~',
+            ThemeElement::BoxDeadlocked => '!',
+            ThemeElement::BoxInGoal => '+',
+            ThemeElement::BoxInHole => '.',
+            ThemeElement::Secret => '%',
+
+            ThemeElement::LevelNext => '<',
+            ThemeElement::LevelLocked => '.',
+
+            element => Self::default_style(element).glyph,
+        };
+
+        Style::new(Color::Default, Color::Default, glyph)
+    }
+}
+
+#[derive(Debug)]
+pub struct ThemeError {
+    message: String
+}
+
+impl ThemeError {
+    fn new(message: impl Into<String>) -> Self {
+        Self { message: message.into() }
+    }
+}
+
+impl Display for ThemeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl Error for ThemeError {}
@@ -0,0 +1,101 @@
+use console_lib::Color;
+use crate::game::level::Tile;
+use crate::game::theme::*;
+
+#[test]
+fn built_in_themes() {
+    for name in Theme::BUILT_IN_NAMES {
+        let theme = Theme::built_in(name).unwrap();
+        assert_eq!(theme.name(), name);
+        assert!(Theme::is_valid_name(name));
+    }
+
+    assert_eq!(Theme::built_in("pink"), None);
+
+    let theme = Theme::built_in(Theme::DEFAULT_NAME).unwrap();
+    assert_eq!(theme.style(ThemeElement::Wall), Style::new(Color::LightGreen, Color::Default, '#'));
+    assert_eq!(theme.style(ThemeElement::from_tile(&Tile::BoxInGoal)), Style::new(Color::LightPink, Color::Default, '@'));
+}
+
+#[test]
+fn no_color_theme_has_distinct_tile_glyphs() {
+    let theme = Theme::built_in("no_color").unwrap();
+
+    let tiles = [
+        Tile::Empty, Tile::OneWayLeft, Tile::OneWayUp, Tile::OneWayRight, Tile::OneWayDown, Tile::Wall, Tile::Player,
+        Tile::Key, Tile::KeyInGoal, Tile::LockedDoor, Tile::Box, Tile::BoxInGoal, Tile::Goal, Tile::Hole,
+        Tile::BoxInHole, Tile::DecorationBlank, Tile::Secret,
+    ];
+    for (i, tile) in tiles.iter().enumerate() {
+        let style = theme.style(ThemeElement::from_tile(tile));
+        assert_eq!((style.fg, style.bg), (Color::Default, Color::Default));
+
+        for other_tile in &tiles[i + 1..] {
+            assert_ne!(style.glyph, theme.style(ThemeElement::from_tile(other_tile)).glyph, "{tile:?} and {other_tile:?}");
+        }
+    }
+
+    //Deadlocked boxes can not be distinguished by their color
+    let box_deadlocked_glyph = theme.style(ThemeElement::BoxDeadlocked).glyph;
+    for tile in &tiles {
+        assert_ne!(box_deadlocked_glyph, theme.style(ThemeElement::from_tile(tile)).glyph, "{tile:?}");
+    }
+
+    let level_glyphs = [ThemeElement::LevelCompleted, ThemeElement::LevelNext, ThemeElement::LevelLocked].
+            map(|element| theme.style(element).glyph);
+    assert_ne!(level_glyphs[0], level_glyphs[1]);
+    assert_ne!(level_glyphs[0], level_glyphs[2]);
+    assert_ne!(level_glyphs[1], level_glyphs[2]);
+}
+
+#[test]
+fn read_user_theme() {
+    let theme = Theme::read_from_str("my_theme", "# Comment\n\nbase = no_color\nwall = light_white black\nbox = cyan default SPACE\n").unwrap();
+    assert_eq!(theme.name(), "my_theme");
+    assert_eq!(theme.style(ThemeElement::Wall), Style::new(Color::LightWhite, Color::Black, '#'));
+    assert_eq!(theme.style(ThemeElement::Box), Style::new(Color::Cyan, Color::Default, ' '));
+
+    //Elements which are not listed use the style of the base theme
+    assert_eq!(theme.style(ThemeElement::BoxInGoal), Style::new(Color::Default, Color::Default, '+'));
+
+    //Without a base theme the default theme is used
+    let theme = Theme::read_from_str("my_theme", "goal = red default o").unwrap();
+    assert_eq!(theme.style(ThemeElement::Goal), Style::new(Color::Red, Color::Default, 'o'));
+    assert_eq!(theme.style(ThemeElement::Wall), Style::new(Color::LightGreen, Color::Default, '#'));
+}
+
+#[test]
+fn read_errors() {
+    let err = |theme_data| Theme::read_from_str("test", theme_data).unwrap_err().to_string();
+
+    assert_eq!(err("wall red default"), "Line 1: \"=\" is missing");
+    assert_eq!(err("\nfloor = red default"), "Line 2: \"floor\" is not a valid element");
+    assert_eq!(err("wall = red default\nwall = red default"), "Line 2: Element \"wall\" is defined multiple times");
+    assert_eq!(err("wall = orange default"), "Line 1: \"orange\" is not a valid color");
+    assert_eq!(err("wall = red"), "Line 1: Expected \"<foreground color> <background color> [<glyph>]\"");
+    assert_eq!(err("wall = red default ##"), "Line 1: \"##\" is not a valid glyph (Expected a single ASCII character)");
+    assert_eq!(err("\n\nwall = red default █"), "Line 3: \"\\u{2588}\" is not a valid glyph (Expected a single ASCII character)");
+    assert_eq!(err("base = pink"), "Line 1: \"pink\" is not a built-in theme");
+    assert_eq!(err("wall = red default\nbase = light"), "Line 2: The base theme must be defined before all elements");
+}
+
+#[test]
+fn write_user_theme() {
+    let theme = Theme::read_from_str("my_theme", "base = high_contrast\nwall = red black\nempty = default default SPACE").unwrap();
+
+    let theme_str = theme.to_str();
+    assert!(theme_str.contains("\nwall = red black #\n"), "{theme_str}");
+    assert!(theme_str.contains("\nempty = default default SPACE\n"), "{theme_str}");
+
+    //Round trip
+    assert_eq!(Theme::read_from_str("my_theme", &theme_str).unwrap(), theme);
+}
+
+#[test]
+fn theme_names() {
+    assert!(Theme::is_valid_name("my-theme_2"));
+    assert!(!Theme::is_valid_name(""));
+    assert!(!Theme::is_valid_name("../theme"));
+    assert!(!Theme::is_valid_name("my theme"));
+    assert!(!Theme::is_valid_name(&"a".repeat(Theme::MAX_NAME_LEN + 1)));
+}
@@ -0,0 +1,212 @@
+use std::collections::VecDeque;
+use std::fmt::{Display, Formatter};
+use crate::game::Game;
+use crate::game::engine::Direction;
+use crate::game::level::{Level, Tile};
+
+#[cfg(test)]
+mod tests;
+
+/// A problem of a level which can be detected without playing it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LevelProblem {
+    TooLarge {
+        width: usize,
+        height: usize,
+    },
+
+    NoPlayer,
+    /// A player tile in addition to the first player tile
+    AdditionalPlayer((usize, usize)),
+
+    NoGoals,
+    /// Goals which contain keys must be filled with boxes as well
+    NotEnoughBoxes {
+        box_count: usize,
+        goal_count: usize,
+    },
+    /// A goal which can neither be reached by the player nor by a box
+    UnreachableGoal((usize, usize)),
+}
+
+impl LevelProblem {
+    /// Returns the position of the tile which causes the problem
+    pub fn pos(&self) -> Option<(usize, usize)> {
+        match self {
+            LevelProblem::AdditionalPlayer(pos) | LevelProblem::UnreachableGoal(pos) => Some(*pos),
+
+            _ => None,
+        }
+    }
+
+    /// Returns true if the level can not be played because of this problem
+    pub fn prevents_playing(&self) -> bool {
+        matches!(self, LevelProblem::TooLarge { .. } | LevelProblem::NoPlayer | LevelProblem::AdditionalPlayer(_))
+    }
+}
+
+impl Display for LevelProblem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LevelProblem::TooLarge { width, height } => write!(
+                f, "Level is too large ({width}x{height}, Max: {}x{})",
+                Game::LEVEL_MAX_WIDTH, Game::LEVEL_MAX_HEIGHT,
+            ),
+
+            LevelProblem::NoPlayer => f.write_str("Level does not contain a player tile"),
+            LevelProblem::AdditionalPlayer(_) => f.write_str("Additional player tile"),
+
+            LevelProblem::NoGoals => f.write_str("Level does not contain any goals"),
+            LevelProblem::NotEnoughBoxes { box_count, goal_count } => write!(
+                f, "There are fewer boxes ({box_count}) than goals ({goal_count})",
+            ),
+            LevelProblem::UnreachableGoal(_) => f.write_str("Goal is unreachable"),
+        }
+    }
+}
+
+/// Returns all problems of the level (Ordered by kind and position), the solvability is not checked
+pub fn validate_level(level: &Level) -> Vec<LevelProblem> {
+    let mut problems = Vec::new();
+
+    if level.width() > Game::LEVEL_MAX_WIDTH || level.height() > Game::LEVEL_MAX_HEIGHT {
+        problems.push(LevelProblem::TooLarge {
+            width: level.width(),
+            height: level.height(),
+        });
+    }
+
+    let mut player_positions = positions_of(level, Tile::is_player);
+    if player_positions.is_empty() {
+        problems.push(LevelProblem::NoPlayer);
+    }else {
+        problems.extend(player_positions.drain(1..).map(LevelProblem::AdditionalPlayer));
+    }
+
+    let box_count = positions_of(level, |tile| matches!(tile, Tile::Box | Tile::BoxInGoal)).len();
+    let goal_count = positions_of(level, |tile| matches!(tile, Tile::Goal | Tile::PlayerInGoal | Tile::BoxInGoal | Tile::KeyInGoal)).len();
+    if goal_count == 0 {
+        problems.push(LevelProblem::NoGoals);
+    }else if box_count < goal_count {
+        problems.push(LevelProblem::NotEnoughBoxes { box_count, goal_count });
+    }
+
+    if let Some(player_pos) = player_positions.first() {
+        let reachable = reachable_positions(level, *player_pos);
+
+        problems.extend(positions_of(level, |tile| matches!(tile, Tile::Goal | Tile::PlayerInGoal | Tile::KeyInGoal)).
+                into_iter().
+                filter(|(x, y)| !reachable[x + y * level.width()]).
+                map(LevelProblem::UnreachableGoal));
+    }
+
+    problems
+}
+
+/// Tile counts and checks of a level which are shown live in the level editor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LevelStatus {
+    pub player_count: usize,
+
+    /// Boxes and boxes in goals
+    pub box_count: usize,
+    /// Goals, boxes in goals and keys in goals
+    pub goal_count: usize,
+    /// Holes which are not yet filled with a box
+    pub hole_count: usize,
+
+    /// Keys and keys in goals
+    pub key_count: usize,
+    pub locked_door_count: usize,
+
+    /// Goals which can not be reached from the first player tile (0 if there is no player tile)
+    pub unreachable_goal_count: usize,
+
+    /// All goals are already filled with boxes in the start state
+    pub is_solved: bool,
+}
+
+impl LevelStatus {
+    /// Returns true if there are fewer boxes than goals and unfilled holes (Holes are not required to be filled)
+    pub fn may_lack_boxes(&self) -> bool {
+        self.box_count < self.goal_count + self.hole_count
+    }
+}
+
+pub fn level_status(level: &Level) -> LevelStatus {
+    let count = |predicate: fn(&Tile) -> bool| level.tiles().iter().
+            filter(|tile| predicate(tile)).
+            count();
+
+    let player_positions = positions_of(level, Tile::is_player);
+    let unreachable_goal_count = player_positions.first().map_or(0, |player_pos| {
+        let reachable = reachable_positions(level, *player_pos);
+
+        positions_of(level, |tile| matches!(tile, Tile::Goal | Tile::PlayerInGoal | Tile::KeyInGoal)).
+                into_iter().
+                filter(|(x, y)| !reachable[x + y * level.width()]).
+                count()
+    });
+
+    let goal_count = count(|tile| matches!(tile, Tile::Goal | Tile::PlayerInGoal | Tile::BoxInGoal | Tile::KeyInGoal));
+
+    LevelStatus {
+        player_count: player_positions.len(),
+
+        box_count: count(|tile| matches!(tile, Tile::Box | Tile::BoxInGoal)),
+        goal_count,
+        hole_count: count(|tile| *tile == Tile::Hole),
+
+        key_count: count(|tile| matches!(tile, Tile::Key | Tile::KeyInGoal)),
+        locked_door_count: count(|tile| *tile == Tile::LockedDoor),
+
+        unreachable_goal_count,
+
+        is_solved: goal_count > 0 && count(|tile| matches!(tile, Tile::Goal | Tile::PlayerInGoal | Tile::KeyInGoal)) == 0,
+    }
+}
+
+fn positions_of(level: &Level, predicate: impl Fn(&Tile) -> bool) -> Vec<(usize, usize)> {
+    level.tiles().iter().
+            enumerate().
+            filter(|(_, tile)| predicate(tile)).
+            map(|(index, _)| (index % level.width(), index / level.width())).
+            collect()
+}
+
+/// Returns all positions which the player could reach if all boxes, keys, doors and holes were out of the way
+/// (Indexed by `x + y * width`)
+pub fn reachable_positions(level: &Level, player_pos: (usize, usize)) -> Vec<bool> {
+    let width = level.width();
+    let height = level.height();
+
+    let mut reachable = vec![false; width * height];
+    let mut queue = VecDeque::new();
+
+    reachable[player_pos.0 + player_pos.1 * width] = true;
+    queue.push_back(player_pos);
+
+    while let Some(pos) = queue.pop_front() {
+        for direction in Direction::ALL {
+            let pos_to = direction.next_pos(width, height, pos);
+            let index_to = pos_to.0 + pos_to.1 * width;
+            if reachable[index_to] {
+                continue;
+            }
+
+            let can_enter = match level.get_tile(pos_to.0, pos_to.1).unwrap() {
+                Tile::Wall | Tile::DecorationBlank | Tile::Secret => false,
+                tile @ (Tile::OneWayLeft | Tile::OneWayUp | Tile::OneWayRight | Tile::OneWayDown) => *tile == direction.one_way_door_tile(),
+
+                _ => true,
+            };
+
+            if can_enter {
+                reachable[index_to] = true;
+                queue.push_back(pos_to);
+            }
+        }
+    }
+
+    reachable
+}
@@ -0,0 +1,95 @@
+use std::str::FromStr;
+use crate::game::validation::*;
+
+fn validate(level: &str) -> Vec<LevelProblem> {
+    validate_level(&Level::from_str(level).unwrap())
+}
+
+#[test]
+fn valid_level() {
+    assert_eq!(validate("w: 5, h: 3\n#####\n#P@x#\n#####"), vec![]);
+
+    //Additional boxes are allowed
+    assert_eq!(validate("w: 6, h: 3\n######\n#P@@x#\n######"), vec![]);
+}
+
+#[test]
+fn player_count() {
+    assert_eq!(validate("w: 5, h: 3\n#####\n#-@x#\n#####"), vec![LevelProblem::NoPlayer]);
+    assert_eq!(
+        validate("w: 6, h: 3\n######\n#P@xP#\n####P#"),
+        vec![LevelProblem::AdditionalPlayer((4, 1)), LevelProblem::AdditionalPlayer((4, 2))],
+    );
+}
+
+#[test]
+fn box_count() {
+    assert_eq!(validate("w: 5, h: 3\n#####\n#P--#\n#####"), vec![LevelProblem::NoGoals]);
+
+    //Keys in goals must be replaced by boxes
+    assert_eq!(
+        validate("w: 6, h: 3\n######\n#P@x~#\n######"),
+        vec![LevelProblem::NotEnoughBoxes { box_count: 1, goal_count: 2 }],
+    );
+    assert_eq!(validate("w: 6, h: 3\n######\n#P@+x#\n######"), vec![]);
+
+    //The goal below the player must be filled as well
+    assert_eq!(
+        validate("w: 5, h: 3\n#####\n#&@x#\n#####"),
+        vec![LevelProblem::NotEnoughBoxes { box_count: 1, goal_count: 2 }],
+    );
+}
+
+#[test]
+fn unreachable_goals() {
+    assert_eq!(
+        validate("w: 7, h: 3\n#######\n#P@#@x#\n#######"),
+        vec![LevelProblem::UnreachableGoal((5, 1))],
+    );
+
+    //Levels wrap around at the edges
+    assert_eq!(validate("w: 7, h: 3\n#######\n-P@#@x-\n#######"), vec![]);
+
+    //One-way doors can only be passed in their direction
+    assert_eq!(validate("w: 7, h: 3\n#######\n#P@>@x#\n#######"), vec![]);
+    assert_eq!(
+        validate("w: 7, h: 3\n#######\n#P@<@x#\n#######"),
+        vec![LevelProblem::UnreachableGoal((5, 1))],
+    );
+}
+
+#[test]
+fn too_large() {
+    let width = Game::LEVEL_MAX_WIDTH + 1;
+    let level = format!("w: {width}, h: 1\nP@x{}", "-".repeat(width - 3));
+
+    assert_eq!(validate(&level), vec![LevelProblem::TooLarge { width, height: 1 }]);
+}
+
+#[test]
+fn status() {
+    let status = level_status(&Level::from_str("w: 9, h: 4\n#########\n#P@~*=o.#\n#########\n####x####").unwrap());
+    assert_eq!(status, LevelStatus {
+        player_count: 1,
+
+        box_count: 1,
+        goal_count: 2,
+        hole_count: 1,
+
+        key_count: 2,
+        locked_door_count: 1,
+
+        unreachable_goal_count: 1,
+
+        is_solved: false,
+    });
+    assert!(status.may_lack_boxes());
+
+    let status = level_status(&Level::from_str("w: 6, h: 3\n######\n#P+-P#\n######").unwrap());
+    assert_eq!(status.player_count, 2);
+    assert!(status.is_solved);
+    assert!(!status.may_lack_boxes());
+
+    //Levels without goals are not solved
+    assert!(!level_status(&Level::from_str("w: 3, h: 1\nP--").unwrap()).is_solved);
+}
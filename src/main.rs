@@ -8,6 +8,11 @@ mod game;
 pub mod collections;
 
 fn main() -> ExitCode {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    if let Some(exit_code) = game::cli::run(&args) {
+        return exit_code;
+    }
+
     let console = Console::new().unwrap();
 
     let game = Game::new(&console);
@@ -16,7 +21,7 @@ fn main() -> ExitCode {
         Err(err) => {
             drop(console);
 
-            eprintln!("{err}");
+            game::cli::print_error(err.as_ref());
 
             return ExitCode::FAILURE;
         },
@@ -24,6 +29,14 @@ fn main() -> ExitCode {
 
     loop {
         if game.update() {
+            let warnings = game.warnings().to_vec();
+            drop(game);
+            drop(console);
+
+            for warning in warnings {
+                eprintln!("Warning: {warning} (Defaults were used)");
+            }
+
             return ExitCode::SUCCESS;
         }
 